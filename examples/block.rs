@@ -0,0 +1,14 @@
+use procsys::sysfs;
+
+fn main() {
+    let block_devices = sysfs::block::collect().expect("block devices information");
+
+    // print all block devices information in json output
+    match serde_json::to_string_pretty(&block_devices) {
+        Ok(output) => println!("{}", output),
+        Err(err) => {
+            log::error!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use procsys::{swaps, sysfs};
+
+fn main() {
+    let topology = sysfs::block_topology::collect().expect("block devices topology");
+
+    // print the disks backing each swap device or file
+    for swap in swaps::collect().expect("swaps information") {
+        match topology.backing_disks(&swap.filename) {
+            Ok(disks) => println!("{}: {}", swap.filename, disks.join(", ")),
+            Err(err) => log::error!("{}: {}", swap.filename, err),
+        }
+    }
+
+    match serde_json::to_string_pretty(&topology) {
+        Ok(output) => println!("{}", output),
+        Err(err) => {
+            log::error!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use procsys::capture::Capture;
+
+fn main() {
+    // captures the files read by the collectors (and by the per process
+    // collectors of this process) into the archive given as argument
+    let filename = std::env::args()
+        .nth(1)
+        .unwrap_or("procsys-capture.ttar".to_string());
+
+    let sys_capture = Capture::default().process(std::process::id() as usize);
+
+    match sys_capture.write_to(&filename) {
+        Ok(_) => println!("captured files written to {}", filename),
+        Err(err) => {
+            log::error!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use procsys::{delta, net_dev};
+
+fn main() {
+    let previous = net_dev::collect().expect("network devices");
+    let start = Instant::now();
+
+    thread::sleep(Duration::from_secs(1));
+
+    let current = net_dev::collect().expect("network devices");
+    let rates = delta::rates_by(&current, &previous, start.elapsed(), |d| d.name.to_owned());
+
+    match serde_json::to_string_pretty(&rates) {
+        Ok(output) => println!("{}", output),
+        Err(err) => {
+            log::error!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use procsys::diskstats;
+
+fn main() {
+    let sys_diskstats = diskstats::collect().expect("disk statistics");
+
+    match serde_json::to_string_pretty(&sys_diskstats) {
+        Ok(output) => println!("{}", output),
+        Err(err) => {
+            log::error!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use procsys::{
+    nonblocking::{AsyncProcFs, AsyncSysFs},
+    ProcFs, SysFs,
+};
+
+#[tokio::main]
+async fn main() {
+    let procfs = AsyncProcFs::with_concurrency(ProcFs::default(), 16);
+    let sysfs = AsyncSysFs::new(SysFs::default());
+
+    // the system wide collectors run concurrently
+    let (sys_loadavg, thermal_zones) = tokio::join!(procfs.loadavg(), sysfs.class_thermal());
+
+    match sys_loadavg {
+        Ok(sys_loadavg) => println!("load1: {}", sys_loadavg.load1),
+        Err(err) => println!("loadavg: {}", err),
+    }
+
+    match thermal_zones {
+        Ok(thermal_zones) => println!("thermal zones: {}", thermal_zones.len()),
+        Err(err) => println!("class_thermal: {}", err),
+    }
+
+    // reads the memory usage of every process, at most 16 files at a time
+    let scan = match procfs.scan(|proc| proc.status()).await {
+        Ok(scan) => scan,
+        Err(err) => {
+            log::error!("{}", err);
+            std::process::exit(1);
+        }
+    };
+
+    for (pid, status) in scan {
+        if let Ok(status) = status {
+            println!("{}: {} {:?}", pid, status.name, status.vm_rss);
+        }
+    }
+}
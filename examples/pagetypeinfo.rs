@@ -0,0 +1,13 @@
+use procsys::pagetypeinfo;
+
+fn main() {
+    let sys_pagetypeinfo = pagetypeinfo::collect().expect("page type information");
+
+    match serde_json::to_string_pretty(&sys_pagetypeinfo) {
+        Ok(output) => println!("{}", output),
+        Err(err) => {
+            log::error!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use procsys::partitions;
+
+fn main() {
+    let sys_partitions = partitions::collect().expect("partitions information");
+
+    match serde_json::to_string_pretty(&sys_partitions) {
+        Ok(output) => println!("{}", output),
+        Err(err) => {
+            log::error!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
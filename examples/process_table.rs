@@ -0,0 +1,30 @@
+use procsys::process_table::{ProcessField, ProcessTable};
+
+fn main() {
+    // reads only the stat and status files of every process
+    let table = match ProcessTable::scan(&[ProcessField::Stat, ProcessField::Status]) {
+        Ok(table) => table,
+        Err(err) => {
+            log::error!("{}", err);
+            std::process::exit(1);
+        }
+    };
+
+    for row in &table.rows {
+        let (Some(stat), Some(status)) = (&row.stat, &row.status) else {
+            continue;
+        };
+
+        println!(
+            "{} {} utime={} vm_rss={:?}",
+            row.pid, stat.comm, stat.utime, status.vm_rss
+        );
+    }
+
+    println!(
+        "processes: {}, exited during the scan: {}, unreadable fields: {}",
+        table.len(),
+        table.vanished,
+        table.errors.len()
+    );
+}
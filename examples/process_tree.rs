@@ -0,0 +1,17 @@
+use procsys::process_tree;
+
+fn main() {
+    let proc_tree = process_tree::collect().expect("system process tree");
+    let pid = std::env::args()
+        .nth(1)
+        .and_then(|p| p.parse::<usize>().ok());
+
+    match pid {
+        Some(pid) => {
+            print!("{}", proc_tree.render(pid));
+            println!("ancestors: {:?}", proc_tree.ancestors(pid));
+            println!("usage: {:?}", proc_tree.subtree_usage(pid));
+        }
+        None => print!("{}", proc_tree),
+    }
+}
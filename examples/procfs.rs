@@ -0,0 +1,25 @@
+use procsys::{ProcFs, SysFs};
+
+fn main() {
+    let procfs = ProcFs::new(std::env::var("PROCFS_ROOT").unwrap_or("/proc".to_string()));
+    let sysfs = SysFs::new(std::env::var("SYSFS_ROOT").unwrap_or("/sys".to_string()));
+
+    let sys_meminfo = procfs.meminfo().expect("memory information");
+    let thermal_zones = sysfs.class_thermal().expect("thermal zones information");
+
+    match serde_json::to_string_pretty(&sys_meminfo) {
+        Ok(output) => println!("{}", output),
+        Err(err) => {
+            log::error!("{}", err);
+            std::process::exit(1);
+        }
+    }
+
+    match serde_json::to_string_pretty(&thermal_zones) {
+        Ok(output) => println!("{}", output),
+        Err(err) => {
+            log::error!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
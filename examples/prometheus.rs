@@ -0,0 +1,17 @@
+use procsys::{collector::Registry, prometheus, ProcFs, SysFs};
+
+fn main() {
+    let procfs = ProcFs::new(std::env::var("PROCFS_ROOT").unwrap_or("/proc".to_string()));
+    let sysfs = SysFs::new(std::env::var("SYSFS_ROOT").unwrap_or("/sys".to_string()));
+    let registry = Registry::with_mounts(&procfs, &sysfs);
+
+    match std::env::args().nth(1) {
+        Some(addr) => {
+            if let Err(err) = prometheus::serve(addr, &registry) {
+                log::error!("{}", err);
+                std::process::exit(1);
+            }
+        }
+        None => print!("{}", prometheus::render_registry(&registry)),
+    }
+}
@@ -0,0 +1,19 @@
+use procsys::collector::Registry;
+
+fn main() {
+    let mut registry = Registry::default();
+
+    for name in std::env::args().skip(1) {
+        if let Err(err) = registry.disable(&name) {
+            log::error!("{}", err);
+            std::process::exit(1);
+        }
+    }
+
+    for (name, result) in registry.collect_all() {
+        match result {
+            Ok(value) => println!("{}: {}", name, value),
+            Err(err) => println!("{}: {}", name, err),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use procsys::snapshot::{self, Snapshot};
+
+fn main() {
+    // reads back and prints the snapshot file given as argument, or takes
+    // a new snapshot of the system and writes it to the file
+    let filename = std::env::args()
+        .nth(1)
+        .unwrap_or("procsys-snapshot.json".to_string());
+
+    let sys_snapshot = match Snapshot::read_from(&filename) {
+        Ok(stored_snapshot) => stored_snapshot,
+        Err(_) => {
+            let new_snapshot = snapshot::collect();
+            if let Err(err) = new_snapshot.write_to(&filename) {
+                log::error!("{}", err);
+                std::process::exit(1);
+            }
+
+            new_snapshot
+        }
+    };
+
+    println!("hostname: {}", sys_snapshot.hostname);
+    println!("timestamp: {:?}", sys_snapshot.timestamp);
+
+    for (name, entry) in &sys_snapshot.collectors {
+        match &entry.error {
+            Some(err) => println!("{}: {}", name, err),
+            None => println!("{}: {:?}", name, entry.source),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use procsys::stat;
+
+fn main() {
+    let sys_stat = stat::collect().expect("stat information");
+
+    match serde_json::to_string_pretty(&sys_stat) {
+        Ok(output) => println!("{}", output),
+        Err(err) => {
+            log::error!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use procsys::{capture::ARCHIVE_ROOT, filesystem::TtarFileSystem, ProcFs, SysFs};
+
+fn main() {
+    // reads the memory information and the network devices from a ttar archive
+    // (e.g. written by the capture example) without extracting it
+    let filename = std::env::args()
+        .nth(1)
+        .unwrap_or("test_data/fixtures.ttar".to_string());
+
+    let ttarfs = match TtarFileSystem::open(&filename) {
+        Ok(ttarfs) => Arc::new(ttarfs),
+        Err(err) => {
+            log::error!("{}", err);
+            std::process::exit(1);
+        }
+    };
+
+    let procfs = ProcFs::with_filesystem(format!("{}/proc", ARCHIVE_ROOT), ttarfs.clone());
+    let sysfs = SysFs::with_filesystem(format!("{}/sys", ARCHIVE_ROOT), ttarfs);
+
+    match procfs.meminfo() {
+        Ok(sys_meminfo) => println!("mem_total: {:?}", sys_meminfo.mem_total),
+        Err(err) => println!("meminfo: {}", err),
+    }
+
+    for net_dev in procfs.net_dev().unwrap_or_default() {
+        println!("{}: rx_bytes {}", net_dev.name, net_dev.rx_bytes);
+    }
+
+    match sysfs.class_thermal() {
+        Ok(thermal_zones) => println!("thermal zones: {}", thermal_zones.len()),
+        Err(err) => println!("class_thermal: {}", err),
+    }
+}
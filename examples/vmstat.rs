@@ -0,0 +1,13 @@
+use procsys::vmstat;
+
+fn main() {
+    let sys_vmstat = vmstat::collect().expect("virtual memory statistics");
+
+    match serde_json::to_string_pretty(&sys_vmstat) {
+        Ok(output) => println!("{}", output),
+        Err(err) => {
+            log::error!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
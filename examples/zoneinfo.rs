@@ -0,0 +1,13 @@
+use procsys::zoneinfo;
+
+fn main() {
+    let sys_zoneinfo = zoneinfo::collect().expect("zone information");
+
+    match serde_json::to_string_pretty(&sys_zoneinfo) {
+        Ok(output) => println!("{}", output),
+        Err(err) => {
+            log::error!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
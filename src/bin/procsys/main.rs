@@ -0,0 +1,404 @@
+mod output;
+
+use std::{
+    path::{Path, PathBuf},
+    process::exit,
+    thread,
+    time::Duration,
+};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use procsys::{
+    capture::{Capture, Redaction},
+    collector::{self, Collector},
+    error::CollectResult,
+    ProcFs, SysFs,
+};
+
+use crate::output::Format;
+
+/// procsys retrieves system, kernel, and process metrics from the pseudo-filesystems /proc and /sys
+#[derive(Debug, Parser)]
+#[command(name = "procsys", version, about)]
+struct Cli {
+    /// output format
+    #[arg(short, long, global = true, value_enum, default_value_t = Format::Json)]
+    format: Format,
+
+    /// root directory the proc and sys filesystems are mounted under (e.g. /host)
+    #[arg(long, global = true, default_value = "/")]
+    root: PathBuf,
+
+    /// collect repeatedly at the given interval (e.g. 2, 1.5s, 500ms, 1m)
+    #[arg(short, long, global = true, value_parser = parse_interval)]
+    watch: Option<Duration>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// memory fragmentation from /proc/buddyinfo
+    Buddyinfo,
+    /// kernel boot parameters from /proc/cmdline
+    Cmdline,
+    /// cpu information from /proc/cpuinfo
+    Cpuinfo,
+    /// cryptographic ciphers from /proc/crypto
+    Crypto,
+    /// block devices I/O statistics from /proc/diskstats
+    Diskstats,
+    /// kernel random number generator from /proc/sys/kernel/random
+    KernelRandom,
+    /// system load average from /proc/loadavg
+    Loadavg,
+    /// memory information from /proc/meminfo
+    Meminfo,
+    /// free memory per migrate type from /proc/pagetypeinfo
+    Pagetypeinfo,
+    /// block devices and partitions from /proc/partitions
+    Partitions,
+    /// running processes from /proc/<pid>
+    Processes,
+    /// softirqs per cpu from /proc/softirqs
+    Softirqs,
+    /// kernel/system statistics from /proc/stat
+    Stat,
+    /// swap devices from /proc/swaps
+    Swaps,
+    /// virtual memory statistics from /proc/vmstat
+    Vmstat,
+    /// memory zones watermarks and counters from /proc/zoneinfo
+    Zoneinfo,
+    /// network statistics from /proc/net
+    Net {
+        #[arg(value_enum)]
+        module: NetModule,
+    },
+    /// class and device information from /sys
+    Sys {
+        #[arg(value_enum)]
+        module: SysModule,
+    },
+    /// process information from /proc/<pid>
+    Proc {
+        pid: usize,
+        #[arg(value_enum)]
+        module: ProcModule,
+    },
+    /// capture the files read by the collectors into a ttar archive (e.g. for a bug report)
+    Capture {
+        /// archive file to write
+        output: PathBuf,
+
+        /// capture the files of the process as well (repeatable)
+        #[arg(long = "pid")]
+        pids: Vec<usize>,
+
+        /// identifying information to keep instead of redacting it (repeatable)
+        #[arg(long, value_enum)]
+        keep: Vec<RedactionKind>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum RedactionKind {
+    Serials,
+    MacAddresses,
+    Uuids,
+    Environ,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum NetModule {
+    Arp,
+    Dev,
+    Protocols,
+    Sockstat,
+    Sockstat6,
+    Unix,
+    Wireless,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SysModule {
+    Block,
+    Clocksource,
+    Cooling,
+    Dmi,
+    Fibrechannel,
+    Infiniband,
+    Nvme,
+    PowerSupply,
+    SasDevice,
+    SasHost,
+    SasPhy,
+    SasPort,
+    ScsiTape,
+    Thermal,
+    Watchdog,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ProcModule {
+    Cgroup,
+    Cmdline,
+    Comm,
+    Environ,
+    Fds,
+    Io,
+    Limits,
+    Maps,
+    NetSnmp,
+    NetSnmp6,
+    Netstat,
+    Ns,
+    Schedstat,
+    Smaps,
+    SmapsRollup,
+    Stat,
+    Status,
+    Wchan,
+}
+
+impl Command {
+    // returns the name of the collector backing the subcommand
+    fn collector_name(&self) -> Option<String> {
+        let name = match self {
+            Command::Buddyinfo => "buddyinfo".to_string(),
+            Command::Cmdline => "cmdline".to_string(),
+            Command::Cpuinfo => "cpuinfo".to_string(),
+            Command::Crypto => "crypto".to_string(),
+            Command::Diskstats => "diskstats".to_string(),
+            Command::KernelRandom => "kernel_random".to_string(),
+            Command::Loadavg => "loadavg".to_string(),
+            Command::Meminfo => "meminfo".to_string(),
+            Command::Pagetypeinfo => "pagetypeinfo".to_string(),
+            Command::Partitions => "partitions".to_string(),
+            Command::Processes => "processes".to_string(),
+            Command::Softirqs => "softirqs".to_string(),
+            Command::Stat => "stat".to_string(),
+            Command::Swaps => "swaps".to_string(),
+            Command::Vmstat => "vmstat".to_string(),
+            Command::Zoneinfo => "zoneinfo".to_string(),
+            Command::Net { module } => format!("net_{}", module_name(module)),
+            Command::Sys {
+                module: SysModule::Block,
+            } => "block".to_string(),
+            Command::Sys {
+                module: SysModule::Clocksource,
+            } => "clocksource".to_string(),
+            Command::Sys { module } => format!("class_{}", module_name(module)),
+            Command::Proc { module, .. } => format!("process_{}", module_name(module)),
+            Command::Capture { .. } => return None,
+        };
+
+        Some(name)
+    }
+
+    // returns the collector backing the subcommand reading from the given mount points
+    fn collector(&self, procfs: &ProcFs, sysfs: &SysFs) -> Option<Box<dyn Collector>> {
+        let name = self.collector_name()?;
+        let collectors = match self {
+            Command::Proc { pid, .. } => collector::process_collectors(procfs, *pid),
+            _ => collector::builtin_collectors(procfs, sysfs),
+        };
+
+        collectors.into_iter().find(|c| c.name() == name)
+    }
+}
+
+fn module_name<T: ValueEnum>(module: &T) -> String {
+    module
+        .to_possible_value()
+        .map(|v| v.get_name().replace('-', "_"))
+        .unwrap_or_default()
+}
+
+// parses a watch interval in seconds, with an optional ms, s or m unit suffix
+fn parse_interval(interval: &str) -> Result<Duration, String> {
+    let (value, unit_seconds) = if let Some(value) = interval.strip_suffix("ms") {
+        (value, 0.001)
+    } else if let Some(value) = interval.strip_suffix('s') {
+        (value, 1.0)
+    } else if let Some(value) = interval.strip_suffix('m') {
+        (value, 60.0)
+    } else {
+        (interval, 1.0)
+    };
+
+    match value.trim().parse::<f64>() {
+        Ok(value) if value > 0.0 => Duration::try_from_secs_f64(value * unit_seconds)
+            .map_err(|_| format!("invalid interval: {}", interval)),
+        _ => Err(format!("invalid interval: {}", interval)),
+    }
+}
+
+fn capture(
+    procfs: &ProcFs,
+    sysfs: &SysFs,
+    output: &Path,
+    pids: &[usize],
+    keep: &[RedactionKind],
+) -> CollectResult<()> {
+    let redaction = Redaction {
+        serials: !keep.contains(&RedactionKind::Serials),
+        mac_addresses: !keep.contains(&RedactionKind::MacAddresses),
+        uuids: !keep.contains(&RedactionKind::Uuids),
+        environ: !keep.contains(&RedactionKind::Environ),
+    };
+
+    let mut sys_capture = Capture::with_mounts(procfs, sysfs).redaction(redaction);
+    for pid in pids {
+        sys_capture = sys_capture.process(*pid);
+    }
+
+    sys_capture.write_to(output)
+}
+
+fn main() {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+
+    let cli = Cli::parse();
+    let procfs = ProcFs::new(cli.root.join("proc"));
+    let sysfs = SysFs::new(cli.root.join("sys"));
+
+    if let Command::Capture { output, pids, keep } = &cli.command {
+        if let Err(err) = capture(&procfs, &sysfs, output, pids, keep) {
+            log::error!("{}", err);
+            exit(1);
+        }
+
+        return;
+    }
+
+    let collector = match cli.command.collector(&procfs, &sysfs) {
+        Some(collector) => collector,
+        None => {
+            log::error!("collector ({:?}) not found", cli.command.collector_name());
+            exit(1);
+        }
+    };
+
+    let mut first = true;
+
+    loop {
+        let output = collector
+            .collect()
+            .map_err(|err| err.to_string())
+            .and_then(|value| {
+                output::render(&value, cli.format, first).map_err(|err| err.to_string())
+            });
+
+        match output {
+            Ok(output) => {
+                print!("{}", output);
+                first = false;
+            }
+            // a transient error (e.g. the process exited) does not end the watch
+            Err(err) if cli.watch.is_some() => log::error!("{}", err),
+            Err(err) => {
+                log::error!("{}", err);
+                exit(1);
+            }
+        }
+
+        match cli.watch {
+            Some(interval) => thread::sleep(interval),
+            None => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subcommand_collectors() {
+        let procfs = ProcFs::new("test_data/fixtures/proc");
+        let sysfs = SysFs::new("test_data/fixtures/sys");
+
+        let mut commands = vec![
+            Command::Buddyinfo,
+            Command::Cmdline,
+            Command::Cpuinfo,
+            Command::Crypto,
+            Command::Diskstats,
+            Command::KernelRandom,
+            Command::Loadavg,
+            Command::Meminfo,
+            Command::Pagetypeinfo,
+            Command::Partitions,
+            Command::Processes,
+            Command::Softirqs,
+            Command::Stat,
+            Command::Swaps,
+            Command::Vmstat,
+            Command::Zoneinfo,
+        ];
+        commands.extend(
+            NetModule::value_variants()
+                .iter()
+                .map(|m| Command::Net { module: *m }),
+        );
+        commands.extend(
+            SysModule::value_variants()
+                .iter()
+                .map(|m| Command::Sys { module: *m }),
+        );
+        commands.extend(ProcModule::value_variants().iter().map(|m| Command::Proc {
+            pid: 26231,
+            module: *m,
+        }));
+
+        for command in &commands {
+            assert!(
+                command.collector(&procfs, &sysfs).is_some(),
+                "{:?} collector",
+                command.collector_name(),
+            );
+        }
+
+        let cli = Cli::parse_from(["procsys", "proc", "26231", "io", "--format", "csv"]);
+        assert_eq!(cli.format, Format::Csv);
+        assert_eq!(cli.command.collector_name().unwrap(), "process_io");
+
+        let cli = Cli::parse_from(["procsys", "--root", "/host", "sys", "power-supply"]);
+        assert_eq!(cli.root, PathBuf::from("/host"));
+        assert_eq!(cli.command.collector_name().unwrap(), "class_power_supply");
+
+        let cli = Cli::parse_from([
+            "procsys", "capture", "out.ttar", "--pid", "1", "--pid", "2", "--keep", "uuids",
+        ]);
+        match cli.command {
+            Command::Capture { output, pids, keep } => {
+                assert_eq!(output, PathBuf::from("out.ttar"));
+                assert_eq!(pids, [1, 2]);
+                assert_eq!(keep, [RedactionKind::Uuids]);
+            }
+            command => panic!("unexpected command: {:?}", command),
+        }
+
+        let collector = Cli::parse_from(["procsys", "net", "dev"])
+            .command
+            .collector(&procfs, &sysfs)
+            .unwrap();
+        assert_eq!(collector.collect().unwrap()[0]["name"], "vethf345468");
+    }
+
+    #[test]
+    fn watch_interval() {
+        assert_eq!(parse_interval("2"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_interval("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_interval("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_interval("1m"), Ok(Duration::from_secs(60)));
+        assert!(parse_interval("0").is_err());
+        assert!(parse_interval("-1s").is_err());
+        assert!(parse_interval("fast").is_err());
+        assert!(parse_interval("1e300").is_err());
+        assert!(parse_interval("1e20m").is_err());
+        assert!(parse_interval("inf").is_err());
+    }
+}
@@ -0,0 +1,288 @@
+use std::error::Error;
+
+use clap::ValueEnum;
+use serde_json::Value;
+
+// column holding the key of the entries of a map of items (e.g. nvme0 of class_nvme)
+const KEY_COLUMN: &str = "name";
+
+// column holding list items which are not objects (e.g. cmdline parameters)
+const VALUE_COLUMN: &str = "value";
+
+/// Format is the output format of the collected metrics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Json,
+    Yaml,
+    Table,
+    Csv,
+}
+
+/// Rows is the tabular representation of a collected value, nested fields
+/// are flattened into dot separated columns (e.g. `cpu_total.user`)
+#[derive(Debug, Default, PartialEq)]
+pub struct Rows {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Rows {
+    /// returns the rows of a value, a list or a map of objects has a row per item
+    /// while any other value is a single row
+    pub fn from_value(value: &Value) -> Self {
+        let items: Vec<(Option<&String>, &Value)> = match value {
+            Value::Array(items) => items.iter().map(|item| (None, item)).collect(),
+            Value::Object(fields)
+                if !fields.is_empty() && fields.values().all(Value::is_object) =>
+            {
+                fields.iter().map(|(key, item)| (Some(key), item)).collect()
+            }
+            _ => vec![(None, value)],
+        };
+
+        let mut table = Rows::default();
+        let mut cells_rows = Vec::new();
+
+        for (key, item) in items {
+            let mut cells = Vec::new();
+            flatten("", item, &mut cells);
+
+            if let Some(key) = key {
+                if !cells.iter().any(|(column, _)| column == KEY_COLUMN) {
+                    cells.insert(0, (KEY_COLUMN.to_string(), key.to_owned()));
+                }
+            }
+
+            for (column, _) in &cells {
+                if !table.columns.contains(column) {
+                    table.columns.push(column.to_owned());
+                }
+            }
+
+            cells_rows.push(cells);
+        }
+
+        for cells in cells_rows {
+            let row = table
+                .columns
+                .iter()
+                .map(|column| {
+                    cells
+                        .iter()
+                        .find(|(name, _)| name == column)
+                        .map(|(_, cell)| cell.to_owned())
+                        .unwrap_or_default()
+                })
+                .collect();
+
+            table.rows.push(row);
+        }
+
+        table
+    }
+
+    /// returns the rows as aligned columns, a single row is printed as field/value pairs
+    pub fn to_table(&self) -> String {
+        match self.rows.len() {
+            1 => {
+                let pairs: Vec<Vec<String>> = self
+                    .columns
+                    .iter()
+                    .zip(&self.rows[0])
+                    .map(|(column, cell)| vec![column.to_owned(), cell.to_owned()])
+                    .collect();
+
+                align(&["FIELD".to_string(), "VALUE".to_string()], &pairs)
+            }
+            _ => {
+                let header: Vec<String> = self.columns.iter().map(|c| c.to_uppercase()).collect();
+
+                align(&header, &self.rows)
+            }
+        }
+    }
+
+    /// returns the rows as comma separated values (RFC 4180), with or without the header line
+    pub fn to_csv(&self, header: bool) -> String {
+        let mut output = String::new();
+
+        if header {
+            output.push_str(&csv_line(&self.columns));
+        }
+
+        for row in &self.rows {
+            output.push_str(&csv_line(row));
+        }
+
+        output
+    }
+}
+
+/// renders a collected value in the given format, `first` is false for the
+/// following samples of a watch (e.g. the csv header is only printed once)
+pub fn render(value: &Value, format: Format, first: bool) -> Result<String, Box<dyn Error>> {
+    let output = match format {
+        Format::Json => format!("{}\n", serde_json::to_string_pretty(value)?),
+        Format::Yaml => match first {
+            true => serde_yaml::to_string(value)?,
+            false => format!("---\n{}", serde_yaml::to_string(value)?),
+        },
+        Format::Table => match first {
+            true => Rows::from_value(value).to_table(),
+            false => format!("\n{}", Rows::from_value(value).to_table()),
+        },
+        Format::Csv => Rows::from_value(value).to_csv(first),
+    };
+
+    Ok(output)
+}
+
+fn flatten(prefix: &str, value: &Value, cells: &mut Vec<(String, String)>) {
+    let child_name = |name: &str| match prefix.is_empty() {
+        true => name.to_string(),
+        false => format!("{}.{}", prefix, name),
+    };
+
+    match value {
+        Value::Object(fields) => {
+            for (name, field_value) in fields {
+                flatten(&child_name(name), field_value, cells);
+            }
+        }
+        // a list of plain values (e.g. cpu flags) is kept in a single space separated cell
+        Value::Array(items) if items.iter().all(|i| !i.is_object() && !i.is_array()) => {
+            let cell: Vec<String> = items.iter().map(cell_value).collect();
+            cells.push((column_name(prefix), cell.join(" ")));
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                flatten(&child_name(&index.to_string()), item, cells);
+            }
+        }
+        _ => cells.push((column_name(prefix), cell_value(value))),
+    }
+}
+
+fn column_name(prefix: &str) -> String {
+    match prefix.is_empty() {
+        true => VALUE_COLUMN.to_string(),
+        false => prefix.to_string(),
+    }
+}
+
+fn cell_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.to_owned(),
+        _ => value.to_string(),
+    }
+}
+
+fn align(header: &[String], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+
+    for row in rows {
+        for (index, cell) in row.iter().enumerate() {
+            widths[index] = widths[index].max(cell.chars().count());
+        }
+    }
+
+    let mut output = String::new();
+
+    for row in std::iter::once(header).chain(rows.iter().map(|r| r.as_slice())) {
+        let line: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| format!("{:width$}", cell, width = widths[index]))
+            .collect();
+
+        output.push_str(line.join("  ").trim_end());
+        output.push('\n');
+    }
+
+    output
+}
+
+fn csv_line(cells: &[String]) -> String {
+    let fields: Vec<String> = cells
+        .iter()
+        .map(
+            |cell| match cell.contains([',', '"', '\n', '\r']) || cell.trim() != cell {
+                true => format!("\"{}\"", cell.replace('"', "\"\"")),
+                false => cell.to_owned(),
+            },
+        )
+        .collect();
+
+    format!("{}\n", fields.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn rows_from_value() {
+        let devices = json!([
+            {"name": "eth0", "rx_bytes": 10, "flags": ["up", "running"]},
+            {"name": "lo", "rx_bytes": 5, "tx_bytes": 5, "addr": null},
+        ]);
+
+        let rows = Rows::from_value(&devices);
+        assert_eq!(
+            rows.columns,
+            ["flags", "name", "rx_bytes", "addr", "tx_bytes"]
+        );
+        assert_eq!(rows.rows[0], ["up running", "eth0", "10", "", ""]);
+        assert_eq!(rows.rows[1], ["", "lo", "5", "", "5"]);
+
+        let nvme = json!({"nvme0": {"serial": "S680", "state": "live"}});
+        let rows = Rows::from_value(&nvme);
+        assert_eq!(rows.columns, ["name", "serial", "state"]);
+        assert_eq!(rows.rows, [["nvme0", "S680", "live"]]);
+
+        let stat = json!({"cpu_total": {"user": 1, "idle": 2}, "ctxt": 3, "intr": [[4]]});
+        let rows = Rows::from_value(&stat);
+        assert_eq!(
+            rows.columns,
+            ["cpu_total.idle", "cpu_total.user", "ctxt", "intr.0"],
+        );
+        assert_eq!(rows.rows, [["2", "1", "3", "4"]]);
+
+        let cmdline = json!(["ro", "quiet"]);
+        assert_eq!(Rows::from_value(&cmdline).rows, [["ro"], ["quiet"]]);
+    }
+
+    #[test]
+    fn render_formats() {
+        let loadavg = json!({"load1": 0.5, "load5": 1.25, "name": "a,\"b\""});
+
+        assert_eq!(
+            render(&loadavg, Format::Table, true).unwrap(),
+            "FIELD  VALUE\nload1  0.5\nload5  1.25\nname   a,\"b\"\n",
+        );
+        assert_eq!(
+            render(&loadavg, Format::Csv, true).unwrap(),
+            "load1,load5,name\n0.5,1.25,\"a,\"\"b\"\"\"\n",
+        );
+        assert_eq!(
+            render(&loadavg, Format::Csv, false).unwrap(),
+            "0.5,1.25,\"a,\"\"b\"\"\"\n",
+        );
+        assert_eq!(
+            render(&loadavg, Format::Yaml, false).unwrap(),
+            "---\nload1: 0.5\nload5: 1.25\nname: a,\"b\"\n",
+        );
+        assert!(render(&loadavg, Format::Json, true)
+            .unwrap()
+            .contains("\"load5\": 1.25"));
+
+        let devices = json!([{"name": "eth0", "mtu": 1500}, {"name": "lo", "mtu": 65536}]);
+        assert_eq!(
+            render(&devices, Format::Table, true).unwrap(),
+            "MTU    NAME\n1500   eth0\n65536  lo\n",
+        );
+    }
+}
@@ -1,14 +1,33 @@
-use serde::Serialize;
+use std::path::Path;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    error::{CollectResult, MetricError},
+    error::{CollectResult, MetricError, Partial},
+    procfs::ProcFs,
     utils,
 };
 
+// default page size used when it cannot be queried
+const DEFAULT_PAGE_SIZE: u64 = 4096;
+
+/// returns the page size of the system in bytes
+pub fn page_size() -> u64 {
+    // SAFETY: sysconf has no side effects and is always safe to call
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+
+    match size > 0 {
+        true => size as u64,
+        false => DEFAULT_PAGE_SIZE,
+    }
+}
+
 /// BuddyInfo is the details parsed from /proc/buddyinfo
 /// The data is comprised of an array of free fragments of each size
 /// The sizes are 2^n*PAGE_SIZE, where n is the array index
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BuddyInfo {
     pub node: String,
     pub zone: String,
@@ -19,6 +38,134 @@ impl BuddyInfo {
     fn new() -> Self {
         Default::default()
     }
+
+    /// returns the number of free pages of the zone
+    pub fn free_pages(&self) -> u64 {
+        self.sizes
+            .iter()
+            .enumerate()
+            .fold(0, |pages, (order, count)| {
+                pages.saturating_add(order_pages(*count, order))
+            })
+    }
+
+    /// returns the free memory of the zone in bytes
+    pub fn free_bytes(&self) -> u64 {
+        self.free_pages().saturating_mul(page_size())
+    }
+
+    /// returns the order of the largest free block, None if the zone has no free pages
+    pub fn largest_block_order(&self) -> Option<usize> {
+        self.sizes.iter().rposition(|count| *count > 0)
+    }
+
+    /// returns the size in bytes of the largest free block
+    pub fn largest_block_bytes(&self) -> u64 {
+        match self.largest_block_order() {
+            Some(order) => order_pages(1, order).saturating_mul(page_size()),
+            None => 0,
+        }
+    }
+
+    /// returns the unusable free space index of the given order, between 0 and 1.
+    /// It is the fraction of the free memory which cannot be used for an allocation
+    /// of 2^order pages, as reported by the kernel in `extfrag/unusable_index`.
+    pub fn unusable_free_space_index(&self, order: usize) -> f64 {
+        let info = ContigPageInfo::new(&self.sizes, order);
+
+        if info.free_pages == 0 {
+            return 1.0;
+        }
+
+        let suitable_pages = order_pages(info.free_blocks_suitable, order).min(info.free_pages);
+        let unusable = u128::from(info.free_pages - suitable_pages) * 1000;
+
+        (unusable / u128::from(info.free_pages)) as f64 / 1000.0
+    }
+
+    /// returns the fragmentation index of the given order, as reported by the kernel in
+    /// `extfrag/extfrag_index`. It is -1 if an allocation of 2^order pages would succeed,
+    /// otherwise the closer to 0 the more an allocation failure is due to a lack of memory,
+    /// the closer to 1 the more it is due to fragmentation.
+    /// # Example
+    /// ```
+    /// use procsys::buddyinfo;
+    ///
+    /// // order of the 2MiB huge pages with 4KiB pages
+    /// let huge_page_order = 9;
+    ///
+    /// for zone in buddyinfo::collect().expect("buddy information") {
+    ///     if zone.fragmentation_index(huge_page_order) > 0.5 {
+    ///         println!("node {} {} is fragmented", zone.node, zone.zone);
+    ///     }
+    /// }
+    ///
+    /// ```
+    pub fn fragmentation_index(&self, order: usize) -> f64 {
+        let info = ContigPageInfo::new(&self.sizes, order);
+
+        if info.free_blocks_total == 0 {
+            return 0.0;
+        }
+
+        if info.free_blocks_suitable > 0 {
+            return -1.0;
+        }
+
+        // signed like the kernel, the index of a zone with only a few free blocks
+        // can be below 0
+        let requested = i128::from(order_pages(1, order));
+        let free_pages = i128::from(info.free_pages);
+        let index =
+            1000 - (1000 + free_pages * 1000 / requested) / i128::from(info.free_blocks_total);
+
+        index as f64 / 1000.0
+    }
+}
+
+// ContigPageInfo is the free pages summary of a zone for an allocation order,
+// computed like fill_contig_page_info of mm/vmstat.c
+struct ContigPageInfo {
+    free_pages: u64,
+    free_blocks_total: u64,
+    free_blocks_suitable: u64,
+}
+
+impl ContigPageInfo {
+    fn new(sizes: &[u64], suitable_order: usize) -> Self {
+        let mut info = Self {
+            free_pages: 0,
+            free_blocks_total: 0,
+            free_blocks_suitable: 0,
+        };
+
+        for (order, blocks) in sizes.iter().enumerate() {
+            info.free_blocks_total = info.free_blocks_total.saturating_add(*blocks);
+            info.free_pages = info.free_pages.saturating_add(order_pages(*blocks, order));
+
+            if order >= suitable_order {
+                info.free_blocks_suitable = info
+                    .free_blocks_suitable
+                    .saturating_add(order_pages(*blocks, order - suitable_order));
+            }
+        }
+
+        info
+    }
+}
+
+// returns the number of pages of blocks of 2^order pages, saturated at u64::MAX
+// (e.g. for the orders of 64 and above)
+fn order_pages(blocks: u64, order: usize) -> u64 {
+    if blocks == 0 {
+        return 0;
+    }
+
+    u32::try_from(order)
+        .ok()
+        .and_then(|order| 1u64.checked_shl(order))
+        .and_then(|block_pages| blocks.checked_mul(block_pages))
+        .unwrap_or(u64::MAX)
 }
 
 /// collects reads the buddyinfo statistics from the specified `proc` filesystem
@@ -27,43 +174,67 @@ impl BuddyInfo {
 /// use procsys::buddyinfo;
 ///
 /// let binfo = buddyinfo::collect().expect("buddy information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&binfo).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<Vec<BuddyInfo>> {
-    collect_from("/proc/buddyinfo")
+    ProcFs::default().buddyinfo()
 }
 
-fn collect_from(base_path: &str) -> CollectResult<Vec<BuddyInfo>> {
-    let mut system_buddyinfo = Vec::new();
+/// collects the memory zones of /proc/buddyinfo, a malformed zone line is reported
+/// with its line number
+pub fn collect_lenient() -> CollectResult<Partial<Vec<BuddyInfo>>> {
+    ProcFs::default().buddyinfo_lenient()
+}
+
+pub(crate) fn collect_from(base_path: &Path) -> CollectResult<Vec<BuddyInfo>> {
+    collect_lenient_from(base_path)?.into_result()
+}
 
-    for line in utils::read_file_lines(base_path)? {
-        let fields: Vec<&str> = line.trim().split(' ').filter(|s| !s.is_empty()).collect();
+pub(crate) fn collect_lenient_from(base_path: &Path) -> CollectResult<Partial<Vec<BuddyInfo>>> {
+    let mut system_buddyinfo: Partial<Vec<BuddyInfo>> = Partial::default();
 
-        if fields.len() < 4 {
-            return Err(MetricError::InvalidFieldNumberError(
-                "buddyinfo".to_string(),
-                fields.len(),
-                line,
-            ));
+    for (line_index, line) in utils::read_file_lines(base_path)?.iter().enumerate() {
+        match parse_buddyinfo(line) {
+            Ok(buddyinfo) => system_buddyinfo.items.push(buddyinfo),
+            Err(err) => system_buddyinfo.push_error(
+                &format!("line {}", line_index + 1),
+                base_path,
+                err.with_line(base_path, line_index + 1),
+            ),
         }
+    }
 
-        let mut buddyinfo = BuddyInfo::new();
+    Ok(system_buddyinfo)
+}
 
-        buddyinfo.node = fields[1].replace(',', "");
-        buddyinfo.zone = fields[3].replace(',', "");
+fn parse_buddyinfo(line: &str) -> CollectResult<BuddyInfo> {
+    let fields: Vec<&str> = line.trim().split(' ').filter(|s| !s.is_empty()).collect();
 
-        for item in &fields[4..] {
-            buddyinfo
-                .sizes
-                .push((*item).parse::<u64>().unwrap_or_default())
-        }
+    if fields.len() < 4 {
+        return Err(MetricError::InvalidFieldNumberError(
+            "buddyinfo".to_string(),
+            fields.len(),
+            line.to_string(),
+        ));
+    }
 
-        system_buddyinfo.push(buddyinfo);
+    let mut buddyinfo = BuddyInfo::new();
+
+    buddyinfo.node = fields[1].replace(',', "");
+    buddyinfo.zone = fields[3].replace(',', "");
+
+    for item in &fields[4..] {
+        buddyinfo
+            .sizes
+            .push((*item).parse::<u64>().unwrap_or_default())
     }
 
-    Ok(system_buddyinfo)
+    Ok(buddyinfo)
 }
 
 #[cfg(test)]
@@ -72,7 +243,7 @@ mod tests {
 
     #[test]
     fn buddyinfo() {
-        let binfolist = collect_from("test_data/fixtures/proc/buddyinfo")
+        let binfolist = collect_from(Path::new("test_data/fixtures/proc/buddyinfo"))
             .expect("collecting buddy information");
 
         assert_eq!(binfolist.len(), 3);
@@ -96,4 +267,72 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn buddyinfo_fragmentation() {
+        let binfolist = collect_from(Path::new("test_data/fixtures/proc/buddyinfo"))
+            .expect("collecting buddy information");
+
+        let normal = binfolist.iter().find(|b| b.zone == "Normal").unwrap();
+        assert_eq!(normal.free_pages(), 32139);
+        assert_eq!(normal.free_bytes(), 32139 * page_size());
+        assert_eq!(normal.largest_block_order(), Some(6));
+        assert_eq!(normal.largest_block_bytes(), 64 * page_size());
+
+        assert_eq!(normal.unusable_free_space_index(0), 0.0);
+        assert_eq!(normal.unusable_free_space_index(3), 0.227);
+        assert_eq!(normal.unusable_free_space_index(9), 1.0);
+
+        assert_eq!(normal.fragmentation_index(6), -1.0);
+        assert_eq!(normal.fragmentation_index(7), 0.968);
+        assert_eq!(normal.fragmentation_index(9), 0.992);
+
+        let dma = binfolist.iter().find(|b| b.zone == "DMA").unwrap();
+        assert_eq!(dma.largest_block_order(), Some(10));
+        assert_eq!(dma.fragmentation_index(10), -1.0);
+
+        // zones without free pages cannot be fragmented
+        let empty = BuddyInfo {
+            sizes: vec![0; 11],
+            ..Default::default()
+        };
+        assert_eq!(empty.free_bytes(), 0);
+        assert_eq!(empty.largest_block_order(), None);
+        assert_eq!(empty.largest_block_bytes(), 0);
+        assert_eq!(empty.unusable_free_space_index(9), 1.0);
+        assert_eq!(empty.fragmentation_index(9), 0.0);
+
+        // orders beyond the u64 shifts have no suitable block
+        assert_eq!(normal.unusable_free_space_index(64), 1.0);
+        assert_eq!(normal.unusable_free_space_index(usize::MAX), 1.0);
+        assert_eq!(normal.fragmentation_index(64), 1.0);
+        assert_eq!(normal.fragmentation_index(usize::MAX), 1.0);
+
+        let single = BuddyInfo {
+            sizes: vec![1],
+            ..Default::default()
+        };
+        assert_eq!(single.fragmentation_index(0), -1.0);
+        assert_eq!(single.fragmentation_index(1), -0.5);
+
+        // blocks of the orders beyond the u64 shifts saturate the free memory
+        let mut overlong = BuddyInfo {
+            sizes: vec![0; 70],
+            ..Default::default()
+        };
+        overlong.sizes[0] = 3;
+        overlong.sizes[65] = 1;
+        assert_eq!(overlong.free_pages(), u64::MAX);
+        assert_eq!(overlong.free_bytes(), u64::MAX);
+        assert_eq!(overlong.largest_block_order(), Some(65));
+        assert_eq!(overlong.largest_block_bytes(), u64::MAX);
+        assert_eq!(overlong.unusable_free_space_index(0), 0.0);
+        assert_eq!(overlong.fragmentation_index(66), 0.5);
+
+        overlong.sizes[65] = 0;
+        overlong.sizes[63] = 2;
+        assert_eq!(overlong.free_pages(), u64::MAX);
+        overlong.sizes[63] = 1;
+        assert_eq!(overlong.free_pages(), (1 << 63) + 3);
+    }
 }
@@ -0,0 +1,590 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::{self, File},
+    io::Read,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+use regex::bytes::Regex;
+
+use crate::{
+    collector,
+    error::{CollectResult, MetricError},
+    procfs::ProcFs,
+    sysfs::SysFs,
+    ttar::{TtarArchive, TtarEntry},
+};
+
+/// name of the top directory of the captured archives, the proc and sys directories
+/// of an extracted archive can be used as mount points (e.g. `ProcFs::new("fixtures/proc")`)
+pub const ARCHIVE_ROOT: &str = "fixtures";
+
+// replacement of the redacted values
+const REDACTED: &str = "REDACTED";
+
+// files larger than this are skipped
+const MAX_FILE_SIZE: u64 = 8 * 1024 * 1024;
+
+// directories are captured up to this depth below the collector source
+const MAX_DEPTH: usize = 8;
+
+// symbolic links are followed up to this depth below the collector source
+// (e.g. /sys/class/sas_device/<name> and its device link)
+const MAX_FOLLOW_DEPTH: usize = 2;
+
+// links to the kernel objects shared by many devices are recorded but never followed
+const NOFOLLOW_LINKS: [&str; 5] = [
+    "driver",
+    "firmware_node",
+    "iommu_group",
+    "module",
+    "subsystem",
+];
+
+// files holding a hardware serial number
+const SERIAL_FILES: [&str; 5] = [
+    "board_serial",
+    "chassis_serial",
+    "product_serial",
+    "serial",
+    "serial_number",
+];
+
+// MAC addresses which do not identify a device
+const ANONYMOUS_MAC_ADDRESSES: [&str; 2] = ["00:00:00:00:00:00", "ff:ff:ff:ff:ff:ff"];
+
+/// Redaction selects the identifying information removed from the captured files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Redaction {
+    /// hardware serial numbers (e.g. nvme serial, dmi product_serial and board_serial)
+    pub serials: bool,
+
+    /// MAC addresses, each address is replaced by a distinct locally administered address
+    pub mac_addresses: bool,
+
+    /// UUIDs, each UUID is replaced by a distinct placeholder UUID
+    pub uuids: bool,
+
+    /// values of the process environment variables, the variable names are kept
+    pub environ: bool,
+}
+
+impl Default for Redaction {
+    fn default() -> Self {
+        Self {
+            serials: true,
+            mac_addresses: true,
+            uuids: true,
+            environ: true,
+        }
+    }
+}
+
+impl Redaction {
+    /// returns a redaction which keeps the captured files unchanged
+    pub fn none() -> Self {
+        Self {
+            serials: false,
+            mac_addresses: false,
+            uuids: false,
+            environ: false,
+        }
+    }
+}
+
+struct Redactor {
+    redaction: Redaction,
+    mac_regex: Regex,
+    mac_address_regex: Regex,
+    uuid_regex: Regex,
+    serial_regex: Regex,
+    mac_addresses: BTreeMap<String, String>,
+    uuids: BTreeMap<String, String>,
+}
+
+impl Redactor {
+    fn new(redaction: Redaction) -> CollectResult<Self> {
+        let compile = |pattern: &str| Regex::new(pattern).map_err(MetricError::RegexError);
+
+        Ok(Self {
+            redaction,
+            // a MAC address is checked against the whole run of hex digits and colons
+            // to not match a part of a longer identifier (e.g. a WWN)
+            mac_regex: compile(r"(?i-u)[0-9a-f:]{17,}")?,
+            mac_address_regex: compile(r"(?i-u)^[0-9a-f]{2}(:[0-9a-f]{2}){5}$")?,
+            uuid_regex: compile(
+                r"(?i-u)[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}",
+            )?,
+            // KEY=value lines of uevent files (e.g. POWER_SUPPLY_SERIAL_NUMBER)
+            serial_regex: compile(r"(?m-u)^([A-Z0-9_]*SERIAL[A-Z0-9_]*)=.*$")?,
+            mac_addresses: BTreeMap::new(),
+            uuids: BTreeMap::new(),
+        })
+    }
+
+    fn redact(&mut self, file_name: &str, content: Vec<u8>) -> Vec<u8> {
+        let mut content = content;
+
+        if self.redaction.environ && file_name == "environ" {
+            content = redact_environ(&content);
+        }
+
+        if self.redaction.serials {
+            if SERIAL_FILES.contains(&file_name) && !content.trim_ascii().is_empty() {
+                content = redact_value(&content);
+            }
+
+            content = self
+                .serial_regex
+                .replace_all(&content, format!("${{1}}={}", REDACTED).as_bytes())
+                .into_owned();
+        }
+
+        if self.redaction.mac_addresses {
+            let mac_address_regex = &self.mac_address_regex;
+            let mac_addresses = &mut self.mac_addresses;
+
+            content = self
+                .mac_regex
+                .replace_all(&content, |caps: &regex::bytes::Captures| {
+                    let value = String::from_utf8_lossy(&caps[0]).to_lowercase();
+                    if !mac_address_regex.is_match(value.as_bytes())
+                        || ANONYMOUS_MAC_ADDRESSES.contains(&value.as_str())
+                    {
+                        return caps[0].to_vec();
+                    }
+
+                    pseudonym(mac_addresses, value, |n| {
+                        format!("02:00:00:00:{:02x}:{:02x}", (n >> 8) & 0xff, n & 0xff)
+                    })
+                })
+                .into_owned();
+        }
+
+        if self.redaction.uuids {
+            let uuids = &mut self.uuids;
+
+            content = self
+                .uuid_regex
+                .replace_all(&content, |caps: &regex::bytes::Captures| {
+                    let value = String::from_utf8_lossy(&caps[0]).to_lowercase();
+                    pseudonym(uuids, value, |n| {
+                        format!("00000000-0000-4000-8000-{:012x}", n)
+                    })
+                })
+                .into_owned();
+        }
+
+        content
+    }
+}
+
+// returns the placeholder of a value, the same value always gets the same placeholder
+fn pseudonym<F>(pseudonyms: &mut BTreeMap<String, String>, value: String, placeholder: F) -> Vec<u8>
+where
+    F: Fn(usize) -> String,
+{
+    let next = pseudonyms.len() + 1;
+
+    pseudonyms
+        .entry(value)
+        .or_insert_with(|| placeholder(next))
+        .as_bytes()
+        .to_vec()
+}
+
+fn redact_value(content: &[u8]) -> Vec<u8> {
+    let mut redacted = REDACTED.as_bytes().to_vec();
+
+    if content.ends_with(b"\n") {
+        redacted.push(b'\n');
+    }
+
+    redacted
+}
+
+fn redact_environ(content: &[u8]) -> Vec<u8> {
+    let variables: Vec<Vec<u8>> = content
+        .split(|b| *b == 0)
+        .map(|variable| match variable.iter().position(|b| *b == b'=') {
+            Some(pos) => [&variable[..=pos], REDACTED.as_bytes()].concat(),
+            None => variable.to_vec(),
+        })
+        .collect();
+
+    variables.join(&0)
+}
+
+/// Capture copies the files read by the collectors from a live system into a ttar
+/// archive (the format of the procsys test fixtures), e.g. to reproduce a parser failure
+pub struct Capture {
+    procfs: ProcFs,
+    sysfs: SysFs,
+    redaction: Redaction,
+    #[cfg(feature = "process")]
+    pids: Vec<usize>,
+}
+
+impl Default for Capture {
+    fn default() -> Self {
+        Self::with_mounts(&ProcFs::default(), &SysFs::default())
+    }
+}
+
+impl Capture {
+    /// returns a capture of the built-in collectors files reading from the given mount
+    /// points, all identifying information is redacted by default
+    pub fn with_mounts(procfs: &ProcFs, sysfs: &SysFs) -> Self {
+        Self {
+            procfs: procfs.clone(),
+            sysfs: sysfs.clone(),
+            redaction: Redaction::default(),
+            #[cfg(feature = "process")]
+            pids: Vec::new(),
+        }
+    }
+
+    /// sets the identifying information to redact
+    pub fn redaction(mut self, redaction: Redaction) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    /// adds the files read by the per process collectors of the given pid
+    #[cfg(feature = "process")]
+    pub fn process(mut self, pid: usize) -> Self {
+        self.pids.push(pid);
+        self
+    }
+
+    /// captures the files into an archive, unreadable files are skipped
+    pub fn archive(&self) -> CollectResult<TtarArchive> {
+        let mut archive = TtarArchive::new();
+        let mut redactor = Redactor::new(self.redaction)?;
+
+        let collectors = collector::builtin_collectors(&self.procfs, &self.sysfs).into_iter();
+
+        #[cfg(feature = "process")]
+        let collectors = collectors.chain(
+            self.pids
+                .iter()
+                .flat_map(|pid| collector::process_collectors(&self.procfs, *pid)),
+        );
+
+        let mut walkers = [
+            Walker::new(self.procfs.root(), "proc"),
+            Walker::new(self.sysfs.root(), "sys"),
+        ];
+
+        for source in collectors.map(|c| c.source()) {
+            for walker in walkers.iter_mut() {
+                if let Ok(relative) = source.strip_prefix(&walker.mount_root) {
+                    // the processes collector reads the whole mount point,
+                    // processes are captured per pid instead
+                    if relative.as_os_str().is_empty() {
+                        break;
+                    }
+
+                    walker.capture(relative, 0, &mut archive, &mut redactor);
+                    break;
+                }
+            }
+        }
+
+        Ok(archive)
+    }
+
+    /// captures the files and writes the archive to the given file
+    pub fn write_to<P: AsRef<Path>>(&self, filename: P) -> CollectResult<()> {
+        self.archive()?.write_to(filename)
+    }
+}
+
+struct Walker {
+    mount_root: PathBuf,
+    real_root: Option<PathBuf>,
+    archive_dir: String,
+    visited: BTreeSet<PathBuf>,
+}
+
+impl Walker {
+    fn new(mount_root: &Path, name: &str) -> Self {
+        Self {
+            mount_root: mount_root.to_path_buf(),
+            real_root: fs::canonicalize(mount_root).ok(),
+            archive_dir: format!("{}/{}", ARCHIVE_ROOT, name),
+            visited: BTreeSet::new(),
+        }
+    }
+
+    fn capture(
+        &mut self,
+        relative: &Path,
+        depth: usize,
+        archive: &mut TtarArchive,
+        redactor: &mut Redactor,
+    ) {
+        if !self.visited.insert(relative.to_path_buf()) {
+            return;
+        }
+
+        let path = self.mount_root.join(relative);
+        let archive_path = format!("{}/{}", self.archive_dir, relative.to_string_lossy());
+        let file_name = relative
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let metadata = match fs::symlink_metadata(&path) {
+            Ok(m) => m,
+            Err(_) => return,
+        };
+        let mode = metadata.permissions().mode() & 0o7777;
+
+        if metadata.file_type().is_symlink() {
+            let target = match fs::read_link(&path) {
+                Ok(t) => t,
+                Err(err) => {
+                    logging!(debug, "skipping link {:?}: {}", path, err);
+                    return;
+                }
+            };
+
+            archive.insert(
+                &archive_path,
+                TtarEntry::Symlink {
+                    target: target.to_string_lossy().to_string(),
+                },
+            );
+
+            if depth <= MAX_FOLLOW_DEPTH && !NOFOLLOW_LINKS.contains(&file_name.as_str()) {
+                if let Some(real_relative) = self.real_relative(&path) {
+                    self.capture(&real_relative, depth, archive, redactor);
+                }
+            }
+        } else if metadata.is_dir() {
+            archive.insert(&archive_path, TtarEntry::Directory { mode });
+
+            if depth >= MAX_DEPTH {
+                return;
+            }
+
+            let mut dir_entries: Vec<PathBuf> = match fs::read_dir(&path) {
+                Ok(entries) => entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| relative.join(e.file_name()))
+                    .collect(),
+                Err(err) => {
+                    logging!(debug, "skipping directory {:?}: {}", path, err);
+                    return;
+                }
+            };
+            dir_entries.sort();
+
+            for dir_entry in dir_entries {
+                self.capture(&dir_entry, depth + 1, archive, redactor);
+            }
+        } else if metadata.is_file() {
+            match read_file(&path) {
+                Ok(content) => {
+                    let content = redactor.redact(&file_name, content);
+                    archive.insert(&archive_path, TtarEntry::File { content, mode });
+                }
+                Err(err) => logging!(debug, "skipping file {:?}: {}", path, err),
+            }
+        }
+    }
+
+    // returns the path a link resolves to relative to the mount point,
+    // links outside of the mount point (e.g. /proc/<pid>/exe) are not followed
+    fn real_relative(&self, path: &Path) -> Option<PathBuf> {
+        let real_path = fs::canonicalize(path).ok()?;
+        let real_root = self.real_root.as_ref()?;
+
+        real_path
+            .strip_prefix(real_root)
+            .ok()
+            .map(|p| p.to_path_buf())
+    }
+}
+
+fn read_file(path: &Path) -> std::io::Result<Vec<u8>> {
+    let mut content = Vec::new();
+    File::open(path)?
+        .take(MAX_FILE_SIZE + 1)
+        .read_to_end(&mut content)?;
+
+    if content.len() as u64 > MAX_FILE_SIZE {
+        return Err(std::io::Error::other("file too large"));
+    }
+
+    Ok(content)
+}
+
+/// captures the files read by the built-in collectors of the system
+/// into a ttar archive with all identifying information redacted
+/// # Example
+/// ```
+/// use procsys::capture::{self, Capture, Redaction};
+///
+/// let sys_archive = capture::collect().expect("captured files");
+/// println!("{} files captured", sys_archive.len());
+///
+/// // capture the files of the current process as well, keeping the serial numbers
+/// let filename = std::env::temp_dir().join("procsys-capture-example.ttar");
+/// # #[cfg(feature = "process")]
+/// Capture::default()
+///     .process(std::process::id() as usize)
+///     .redaction(Redaction { serials: false, ..Default::default() })
+///     .write_to(&filename)
+///     .expect("archive written");
+///
+/// ```
+pub fn collect() -> CollectResult<TtarArchive> {
+    Capture::default().archive()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(all(
+        feature = "process",
+        feature = "net",
+        feature = "sysfs-storage",
+        feature = "sysfs-hw"
+    ))]
+    const SAS_END_DEVICE: &str = "pci0000:00/0000:00:03.0/0000:03:00.0/host11/port-11:0/\
+        expander-11:0/port-11:0:0/end_device-11:0:0/sas_device/end_device-11:0:0";
+
+    #[cfg(all(
+        feature = "process",
+        feature = "net",
+        feature = "sysfs-storage",
+        feature = "sysfs-hw"
+    ))]
+    fn fixtures_capture() -> Capture {
+        Capture::with_mounts(
+            &ProcFs::new("test_data/fixtures/proc"),
+            &SysFs::new("test_data/fixtures/sys"),
+        )
+        .process(26231)
+    }
+
+    #[cfg(all(
+        feature = "process",
+        feature = "net",
+        feature = "sysfs-storage",
+        feature = "sysfs-hw"
+    ))]
+    fn file_content(archive: &TtarArchive, path: &str) -> String {
+        match archive.get(path) {
+            Some(TtarEntry::File { content, .. }) => String::from_utf8_lossy(content).to_string(),
+            entry => panic!("unexpected {} entry: {:?}", path, entry),
+        }
+    }
+
+    #[cfg(all(
+        feature = "process",
+        feature = "net",
+        feature = "sysfs-storage",
+        feature = "sysfs-hw"
+    ))]
+    #[test]
+    fn capture_fixtures() {
+        let archive = fixtures_capture()
+            .redaction(Redaction::none())
+            .archive()
+            .expect("captured fixtures");
+
+        assert_eq!(
+            file_content(&archive, "fixtures/proc/meminfo"),
+            fs::read_to_string("test_data/fixtures/proc/meminfo").unwrap(),
+        );
+        assert_eq!(
+            archive.get("fixtures/proc/26231/cmdline"),
+            Some(&TtarEntry::File {
+                content: b"vim\0test.go\0+10\0".to_vec(),
+                mode: 0o644,
+            }),
+        );
+        assert_eq!(
+            file_content(&archive, "fixtures/sys/class/nvme/nvme0/serial"),
+            "S680HF8N190894I\n",
+        );
+        assert!(archive.get("fixtures/proc/26232/cmdline").is_none());
+
+        // class entries are links to the device directories which are captured as well
+        assert_eq!(
+            archive.get("fixtures/sys/class/sas_device/end_device-11:0:0"),
+            Some(&TtarEntry::Symlink {
+                target: format!("../../devices/{}", SAS_END_DEVICE),
+            }),
+        );
+        assert!(archive
+            .get(&format!("fixtures/sys/devices/{}/device", SAS_END_DEVICE))
+            .is_some());
+    }
+
+    #[cfg(all(
+        feature = "process",
+        feature = "net",
+        feature = "sysfs-storage",
+        feature = "sysfs-hw"
+    ))]
+    #[test]
+    fn capture_redaction() {
+        let archive = fixtures_capture().archive().expect("captured fixtures");
+
+        assert_eq!(
+            file_content(&archive, "fixtures/sys/class/nvme/nvme0/serial"),
+            "REDACTED\n",
+        );
+        assert_eq!(
+            file_content(&archive, "fixtures/sys/class/dmi/id/product_serial"),
+            "REDACTED\n",
+        );
+        assert!(
+            file_content(&archive, "fixtures/sys/class/dmi/id/product_uuid")
+                .starts_with("00000000-0000-4000-8000-0000000000")
+        );
+
+        let arp = file_content(&archive, "fixtures/proc/net/arp");
+        assert!(!arp.contains("00:50:56:c0:00:08"));
+        assert!(arp.contains("02:00:00:00:00:01"));
+        assert!(arp.contains("00:00:00:00:00:00"));
+
+        let environ = file_content(&archive, "fixtures/proc/26231/environ");
+        assert!(environ.starts_with("PATH=REDACTED\0HOSTNAME=REDACTED\0TERM=REDACTED\0"));
+
+        let cgroup = file_content(&archive, "fixtures/proc/26231/cgroup");
+        assert!(!cgroup.contains("fd5b6c83-c316-470a-9732-4db75febce50"));
+    }
+
+    #[test]
+    fn redactor_values() {
+        let mut redactor = Redactor::new(Redaction::default()).expect("redactor");
+
+        assert_eq!(
+            redactor.redact(
+                "uevent",
+                b"POWER_SUPPLY_NAME=BAT0\nPOWER_SUPPLY_SERIAL_NUMBER=38109\n".to_vec()
+            ),
+            b"POWER_SUPPLY_NAME=BAT0\nPOWER_SUPPLY_SERIAL_NUMBER=REDACTED\n",
+        );
+        assert_eq!(
+            redactor.redact("address", b"AA:BB:cc:dd:ee:ff\n".to_vec()),
+            b"02:00:00:00:00:01\n",
+        );
+        assert_eq!(
+            redactor.redact("arp", b"aa:bb:cc:dd:ee:ff 11:22:33:44:55:66".to_vec()),
+            b"02:00:00:00:00:01 02:00:00:00:00:02",
+        );
+        assert_eq!(
+            redactor.redact("port_name", b"50:01:43:80:00:00:12:34\n".to_vec()),
+            b"50:01:43:80:00:00:12:34\n",
+        );
+        assert_eq!(redactor.redact("serial", b"\n".to_vec()), b"\n");
+    }
+}
@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use crate::{error::CollectResult, utils};
+use crate::{error::CollectResult, procfs::ProcFs, utils};
 
 /// collects information about system boot cmdline
 /// # Example
@@ -11,20 +11,14 @@ use crate::{error::CollectResult, utils};
 /// println!("{:?}", sys_cmdline);
 ///
 pub fn collect() -> CollectResult<Vec<String>> {
-    collect_from("cmdline", Path::new("/proc"))
+    ProcFs::default().cmdline()
 }
 
-fn collect_from(filename: &str, base_path: &Path) -> CollectResult<Vec<String>> {
+pub(crate) fn collect_from(filename: &str, base_path: &Path) -> CollectResult<Vec<String>> {
     let mut boot_cmdline: Vec<String> = Vec::new();
 
-    let bootcmd = utils::collect_info_string(filename, base_path)?;
-
-    if bootcmd.is_some() {
-        boot_cmdline = bootcmd
-            .unwrap()
-            .split_whitespace()
-            .map(str::to_string)
-            .collect();
+    if let Some(bootcmd) = utils::collect_info_string(filename, base_path)? {
+        boot_cmdline = bootcmd.split_whitespace().map(str::to_string).collect();
     }
 
     Ok(boot_cmdline)
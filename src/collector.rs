@@ -0,0 +1,426 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    error::{CollectResult, MetricError},
+    procfs::ProcFs,
+    sysfs::SysFs,
+};
+
+/// Collector is the common interface implemented by every procsys metrics source
+pub trait Collector: Send + Sync {
+    /// returns the unique name of the collector (e.g. `meminfo`, `class_nvme`)
+    fn name(&self) -> &str;
+
+    /// returns the file or directory the collector reads from
+    fn source(&self) -> PathBuf;
+
+    /// collects the metrics and returns them as a serializable value
+    fn collect(&self) -> CollectResult<Value>;
+}
+
+type CollectFn = dyn Fn() -> CollectResult<Value> + Send + Sync;
+
+/// FnCollector is a collector backed by a closure
+pub struct FnCollector {
+    name: String,
+    source: PathBuf,
+    collect_fn: Box<CollectFn>,
+}
+
+impl FnCollector {
+    /// returns a new collector, the closure output is converted into a serializable value
+    /// # Example
+    /// ```
+    /// use procsys::collector::{Collector, FnCollector};
+    /// use procsys::meminfo;
+    ///
+    /// let mem_collector = FnCollector::new("meminfo", "/proc/meminfo", meminfo::collect);
+    /// let mem_value = mem_collector.collect().expect("memory information");
+    /// println!("{}", mem_value["mem_total"]);
+    ///
+    /// ```
+    pub fn new<T, F>(name: &str, source: impl Into<PathBuf>, collect_fn: F) -> Self
+    where
+        T: Serialize,
+        F: Fn() -> CollectResult<T> + Send + Sync + 'static,
+    {
+        Self {
+            name: name.to_string(),
+            source: source.into(),
+            collect_fn: Box::new(move || to_value(collect_fn()?)),
+        }
+    }
+}
+
+impl Collector for FnCollector {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source(&self) -> PathBuf {
+        self.source.clone()
+    }
+
+    fn collect(&self) -> CollectResult<Value> {
+        (self.collect_fn)()
+    }
+}
+
+fn to_value<T: Serialize>(data: T) -> CollectResult<Value> {
+    serde_json::to_value(data).map_err(MetricError::SerdeJsonError)
+}
+
+/// returns the collectors of every system wide module reading from the given mount points
+#[cfg_attr(
+    not(any(
+        feature = "sysfs-storage",
+        feature = "sysfs-power",
+        feature = "sysfs-hw"
+    )),
+    allow(unused_variables)
+)]
+pub fn builtin_collectors(procfs: &ProcFs, sysfs: &SysFs) -> Vec<Box<dyn Collector>> {
+    let mut collectors: Vec<Box<dyn Collector>> = Vec::new();
+
+    // registers a collector named after the ProcFs/SysFs method it calls
+    macro_rules! fs_collector {
+        ($fs:ident, $name:ident, $source:expr) => {{
+            let fs = $fs.clone();
+            collectors.push(Box::new(FnCollector::new(
+                stringify!($name),
+                $fs.path($source),
+                move || fs.$name(),
+            )));
+        }};
+    }
+
+    fs_collector!(procfs, buddyinfo, "buddyinfo");
+    fs_collector!(procfs, cmdline, "cmdline");
+    fs_collector!(procfs, cpuinfo, "cpuinfo");
+    fs_collector!(procfs, crypto, "crypto");
+    fs_collector!(procfs, diskstats, "diskstats");
+    fs_collector!(procfs, kernel_random, "sys/kernel/random");
+    fs_collector!(procfs, loadavg, "loadavg");
+    fs_collector!(procfs, meminfo, "meminfo");
+    #[cfg(feature = "net")]
+    fs_collector!(procfs, net_arp, "net/arp");
+    #[cfg(feature = "net")]
+    fs_collector!(procfs, net_dev, "net/dev");
+    #[cfg(feature = "net")]
+    fs_collector!(procfs, net_protocols, "net/protocols");
+    #[cfg(feature = "net")]
+    fs_collector!(procfs, net_sockstat, "net/sockstat");
+    #[cfg(feature = "net")]
+    fs_collector!(procfs, net_sockstat6, "net/sockstat6");
+    #[cfg(feature = "net")]
+    fs_collector!(procfs, net_unix, "net/unix");
+    #[cfg(feature = "net")]
+    fs_collector!(procfs, net_wireless, "net/wireless");
+    fs_collector!(procfs, pagetypeinfo, "pagetypeinfo");
+    fs_collector!(procfs, partitions, "partitions");
+    #[cfg(feature = "process")]
+    fs_collector!(procfs, processes, "");
+    fs_collector!(procfs, softirqs, "softirqs");
+    fs_collector!(procfs, stat, "stat");
+    fs_collector!(procfs, swaps, "swaps");
+    fs_collector!(procfs, vmstat, "vmstat");
+    fs_collector!(procfs, zoneinfo, "zoneinfo");
+
+    #[cfg(feature = "sysfs-storage")]
+    fs_collector!(sysfs, block, "block");
+    #[cfg(feature = "sysfs-power")]
+    fs_collector!(sysfs, class_cooling, "class/thermal");
+    #[cfg(feature = "sysfs-hw")]
+    fs_collector!(sysfs, class_dmi, "class/dmi/id");
+    #[cfg(feature = "sysfs-storage")]
+    fs_collector!(sysfs, class_fibrechannel, "class/fc_host");
+    #[cfg(feature = "sysfs-hw")]
+    fs_collector!(sysfs, class_infiniband, "class/infiniband");
+    #[cfg(feature = "sysfs-storage")]
+    fs_collector!(sysfs, class_nvme, "class/nvme");
+    #[cfg(feature = "sysfs-power")]
+    fs_collector!(sysfs, class_power_supply, "class/power_supply");
+    #[cfg(feature = "sysfs-storage")]
+    fs_collector!(sysfs, class_sas_device, "class/sas_device");
+    #[cfg(feature = "sysfs-storage")]
+    fs_collector!(sysfs, class_sas_host, "class/sas_host");
+    #[cfg(feature = "sysfs-storage")]
+    fs_collector!(sysfs, class_sas_phy, "class/sas_phy");
+    #[cfg(feature = "sysfs-storage")]
+    fs_collector!(sysfs, class_sas_port, "class/sas_port");
+    #[cfg(feature = "sysfs-storage")]
+    fs_collector!(sysfs, class_scsi_tape, "class/scsi_tape");
+    #[cfg(feature = "sysfs-power")]
+    fs_collector!(sysfs, class_thermal, "class/thermal");
+    #[cfg(feature = "sysfs-hw")]
+    fs_collector!(sysfs, class_watchdog, "class/watchdog");
+    #[cfg(feature = "sysfs-hw")]
+    fs_collector!(sysfs, clocksource, "devices/system/clocksource");
+
+    collectors
+}
+
+/// returns the collectors of every per process module for the given pid
+#[cfg(feature = "process")]
+pub fn process_collectors(procfs: &ProcFs, pid: usize) -> Vec<Box<dyn Collector>> {
+    let mut collectors: Vec<Box<dyn Collector>> = Vec::new();
+    let proc_path = procfs.path(&pid.to_string());
+
+    // registers a collector which calls the given Process method
+    macro_rules! process_collector {
+        ($name:expr, $method:ident, $source:expr) => {{
+            let fs = procfs.clone();
+            collectors.push(Box::new(FnCollector::new(
+                $name,
+                proc_path.join($source),
+                move || fs.process(pid)?.$method(),
+            )));
+        }};
+    }
+
+    process_collector!("process_cgroup", cgroup, "cgroup");
+    process_collector!("process_cmdline", cmdline, "cmdline");
+    process_collector!("process_comm", comm, "comm");
+    process_collector!("process_environ", environ, "environ");
+    process_collector!("process_fds", fds, "fd");
+    process_collector!("process_io", io, "io");
+    process_collector!("process_limits", limits, "limits");
+    process_collector!("process_maps", maps, "maps");
+    process_collector!("process_net_snmp", net_snmp, "net/snmp");
+    process_collector!("process_net_snmp6", net_snmp6, "net/snmp6");
+    process_collector!("process_netstat", netstat, "net/netstat");
+    process_collector!("process_ns", namespaces, "ns");
+    process_collector!("process_schedstat", schedstat, "schedstat");
+    process_collector!("process_smaps", smaps, "smaps");
+    process_collector!("process_smaps_rollup", smaps_rollup, "smaps_rollup");
+    process_collector!("process_stat", stat, "stat");
+    process_collector!("process_status", status, "status");
+    process_collector!("process_wchan", wchan, "wchan");
+
+    collectors
+}
+
+struct RegistryEntry {
+    collector: Box<dyn Collector>,
+    enabled: bool,
+}
+
+/// Registry holds a set of collectors which can be enumerated, enabled/disabled and run by name
+pub struct Registry {
+    entries: Vec<RegistryEntry>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::with_mounts(&ProcFs::default(), &SysFs::default())
+    }
+}
+
+impl Registry {
+    /// returns an empty registry
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// returns a registry of all built-in collectors (enabled) reading from the given mount points
+    /// # Example
+    /// ```
+    /// use procsys::{collector::Registry, ProcFs, SysFs};
+    ///
+    /// let mut registry = Registry::with_mounts(&ProcFs::new("/proc"), &SysFs::new("/sys"));
+    /// # #[cfg(feature = "process")]
+    /// registry.disable("processes").expect("processes collector");
+    ///
+    /// for (name, result) in registry.collect_all() {
+    ///     match result {
+    ///         Ok(value) => println!("{}: {}", name, value),
+    ///         Err(err) => println!("{}: {}", name, err),
+    ///     }
+    /// }
+    ///
+    /// ```
+    pub fn with_mounts(procfs: &ProcFs, sysfs: &SysFs) -> Self {
+        let mut registry = Self::new();
+
+        for collector in builtin_collectors(procfs, sysfs) {
+            registry.register(collector);
+        }
+
+        registry
+    }
+
+    /// registers an enabled collector, replacing any collector with the same name
+    pub fn register(&mut self, collector: Box<dyn Collector>) {
+        let entry = RegistryEntry {
+            collector,
+            enabled: true,
+        };
+
+        match self.position(entry.collector.name()) {
+            Some(index) => self.entries[index] = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    /// returns the names of all registered collectors in registration order
+    pub fn names(&self) -> Vec<&str> {
+        self.entries.iter().map(|e| e.collector.name()).collect()
+    }
+
+    /// returns the registered collector with the given name
+    pub fn get(&self, name: &str) -> Option<&dyn Collector> {
+        self.position(name)
+            .map(|index| self.entries[index].collector.as_ref())
+    }
+
+    /// enables the collector with the given name
+    pub fn enable(&mut self, name: &str) -> CollectResult<()> {
+        self.set_enabled(name, true)
+    }
+
+    /// disables the collector with the given name
+    pub fn disable(&mut self, name: &str) -> CollectResult<()> {
+        self.set_enabled(name, false)
+    }
+
+    /// returns true if the collector with the given name is registered and enabled
+    pub fn is_enabled(&self, name: &str) -> bool {
+        match self.position(name) {
+            Some(index) => self.entries[index].enabled,
+            None => false,
+        }
+    }
+
+    /// runs the collector with the given name, whether or not it is enabled
+    pub fn collect(&self, name: &str) -> CollectResult<Value> {
+        match self.get(name) {
+            Some(collector) => collector.collect(),
+            None => Err(MetricError::CollectorNotFound(name.to_string())),
+        }
+    }
+
+    /// runs all enabled collectors and returns their results keyed by collector name
+    pub fn collect_all(&self) -> BTreeMap<String, CollectResult<Value>> {
+        let mut results = BTreeMap::new();
+
+        for entry in self.entries.iter().filter(|e| e.enabled) {
+            results.insert(
+                entry.collector.name().to_string(),
+                entry.collector.collect(),
+            );
+        }
+
+        results
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.entries.iter().position(|e| e.collector.name() == name)
+    }
+
+    fn set_enabled(&mut self, name: &str, enabled: bool) -> CollectResult<()> {
+        match self.position(name) {
+            Some(index) => {
+                self.entries[index].enabled = enabled;
+                Ok(())
+            }
+            None => Err(MetricError::CollectorNotFound(name.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    fn fixtures_registry() -> Registry {
+        Registry::with_mounts(
+            &ProcFs::new("test_data/fixtures/proc"),
+            &SysFs::new("test_data/fixtures/sys"),
+        )
+    }
+
+    fn builtin_count() -> usize {
+        builtin_collectors(
+            &ProcFs::new("test_data/fixtures/proc"),
+            &SysFs::new("test_data/fixtures/sys"),
+        )
+        .len()
+    }
+
+    #[test]
+    fn registry_builtin_collectors() {
+        let registry = fixtures_registry();
+
+        assert_eq!(registry.names().len(), builtin_count());
+        for name in ["meminfo", "stat", "vmstat", "zoneinfo", "partitions"] {
+            assert!(registry.names().contains(&name), "{} collector", name);
+        }
+        assert!(registry.is_enabled("meminfo"));
+        assert!(!registry.is_enabled("unknown"));
+        assert_eq!(
+            registry.get("diskstats").unwrap().source(),
+            Path::new("test_data/fixtures/proc/diskstats"),
+        );
+
+        let meminfo = registry.collect("meminfo").expect("meminfo collector");
+        assert_eq!(meminfo["mem_total"], 16042172416u64);
+
+        #[cfg(feature = "sysfs-storage")]
+        {
+            let nvme = registry
+                .collect("class_nvme")
+                .expect("class_nvme collector");
+            assert_eq!(nvme["nvme0"]["serial"], "S680HF8N190894I");
+        }
+
+        assert!(registry.collect("unknown").is_err());
+    }
+
+    #[test]
+    fn registry_enable_disable() {
+        let mut registry = fixtures_registry();
+
+        registry.disable("meminfo").expect("meminfo collector");
+        assert!(!registry.is_enabled("meminfo"));
+        assert!(registry.disable("unknown").is_err());
+
+        let results = registry.collect_all();
+        assert_eq!(results.len(), builtin_count() - 1);
+        assert!(!results.contains_key("meminfo"));
+        assert!(results["loadavg"].is_ok());
+
+        registry.enable("meminfo").expect("meminfo collector");
+        assert!(registry.collect_all().contains_key("meminfo"));
+    }
+
+    #[test]
+    fn registry_custom_collector() {
+        let mut registry = Registry::new();
+        registry.register(Box::new(FnCollector::new("answer", "/dev/null", || Ok(42))));
+        registry.register(Box::new(FnCollector::new("answer", "/dev/null", || Ok(43))));
+
+        assert_eq!(registry.names(), ["answer"]);
+        assert_eq!(registry.collect("answer").unwrap(), 43);
+    }
+
+    #[test]
+    #[cfg(feature = "process")]
+    fn process_collectors_by_pid() {
+        let procfs = ProcFs::new("test_data/fixtures/proc");
+        let collectors = process_collectors(&procfs, 26231);
+
+        let io = collectors
+            .iter()
+            .find(|c| c.name() == "process_io")
+            .unwrap();
+        assert_eq!(io.source(), Path::new("test_data/fixtures/proc/26231/io"));
+        assert_eq!(io.collect().unwrap()["rchar"], 750339);
+    }
+}
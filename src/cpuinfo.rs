@@ -1,7 +1,11 @@
-use serde::Serialize;
+use std::path::Path;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::{
     error::{CollectResult, MetricError},
+    procfs::ProcFs,
     utils,
 };
 
@@ -72,7 +76,8 @@ impl CpuInfoInfo {
 }
 
 /// CpuInfo contains general information about a system CPU found in /proc/cpuinfo
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CpuInfo {
     pub processor: u32,
     pub vendor_id: String,
@@ -115,15 +120,18 @@ impl CpuInfo {
 /// use procsys::cpuinfo;
 ///
 /// let sys_cpuinfo = cpuinfo::collect().expect("cpu information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&sys_cpuinfo).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<Vec<CpuInfo>> {
-    collect_from("/proc/cpuinfo")
+    ProcFs::default().cpuinfo()
 }
 
-fn collect_from(filename: &str) -> CollectResult<Vec<CpuInfo>> {
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<Vec<CpuInfo>> {
     let mut sys_cpuinfo: Vec<CpuInfo> = Vec::new();
 
     let mut info_index = 0;
@@ -369,8 +377,8 @@ mod tests {
 
     #[test]
     fn cpuinfo() {
-        let sys_cpuinfo =
-            collect_from("test_data/fixtures/proc/cpuinfo").expect("collecting cpu information");
+        let sys_cpuinfo = collect_from(Path::new("test_data/fixtures/proc/cpuinfo"))
+            .expect("collecting cpu information");
         assert_eq!(sys_cpuinfo.len(), 2);
 
         for cpu in sys_cpuinfo {
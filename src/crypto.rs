@@ -1,64 +1,68 @@
-use serde::Serialize;
+use std::path::Path;
 
-use crate::{error::CollectResult, utils};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{error::CollectResult, procfs::ProcFs, utils};
 
 /// Crypto holds info parsed from /proc/crypto.
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Crypto {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub alignmask: Option<u64>,
 
     pub cryptoasync: bool,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub blocksize: Option<u64>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub chunksize: Option<u64>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub ctzsize: Option<u64>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub digestsize: Option<u64>,
 
     pub driver: String,
     pub geniv: String,
     pub internal: String,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub ivsize: Option<u64>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub max_authsize: Option<u64>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub max_keysize: Option<u64>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub min_keysize: Option<u64>,
 
     pub module: String,
 
     pub name: String,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub priority: Option<i64>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub refcnt: Option<i64>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub seedsize: Option<u64>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub statesize: Option<u64>,
 
     pub selftest: String,
 
     pub cryptotype: String,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub walksize: Option<u64>,
 }
 
@@ -74,15 +78,18 @@ impl Crypto {
 /// use procsys::crypto;
 ///
 /// let crypto_info = crypto::collect().expect("crypto information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&crypto_info).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<Vec<Crypto>> {
-    collect_from("/proc/crypto")
+    ProcFs::default().crypto()
 }
 
-fn collect_from(filename: &str) -> CollectResult<Vec<Crypto>> {
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<Vec<Crypto>> {
     let mut crypto_info: Vec<Crypto> = Vec::new();
     let mut info_index = 0;
 
@@ -188,8 +195,8 @@ mod tests {
 
     #[test]
     fn crypto_information() {
-        let crypto_info =
-            collect_from("test_data/fixtures/proc/crypto").expect("collecting crypto information");
+        let crypto_info = collect_from(Path::new("test_data/fixtures/proc/crypto"))
+            .expect("collecting crypto information");
 
         assert_eq!(crypto_info.len(), 2);
 
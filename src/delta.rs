@@ -0,0 +1,534 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{diskstats::DiskStats, softirqs::Softirqs};
+
+#[cfg(feature = "net")]
+use crate::net_dev::NetDev;
+#[cfg(feature = "sysfs-hw")]
+use crate::sysfs::class_infiniband::InfiniBandCounters;
+#[cfg(feature = "sysfs-storage")]
+use crate::sysfs::{
+    class_fibrechannel::FibreChannelHostCounters, class_scsi_tape::ScsiTapeCounters,
+};
+#[cfg(feature = "process")]
+use crate::{process_io::ProcessIO, process_netstat::ProcessNetstat};
+
+// 32 bits counters wrap around after this value
+const COUNTER_32_MAX: u64 = u32::MAX as u64;
+
+/// CounterDelta holds the increase of each counter between two samples
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct CounterDelta {
+    /// counters have been reset between the two samples (e.g. device reappeared)
+    pub reset: bool,
+
+    /// counter increase keyed by counter name
+    pub counters: BTreeMap<String, u64>,
+}
+
+/// CounterRates holds the per-second rate of each counter between two samples
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct CounterRates {
+    /// counters have been reset between the two samples (e.g. device reappeared)
+    pub reset: bool,
+
+    /// per-second rate keyed by counter name
+    pub rates: BTreeMap<String, f64>,
+}
+
+impl CounterDelta {
+    /// returns the per-second rates of the counter increases over the elapsed time,
+    /// all rates are zero if no time has elapsed
+    pub fn rates(&self, elapsed: Duration) -> CounterRates {
+        let seconds = elapsed.as_secs_f64();
+
+        let rates = self
+            .counters
+            .iter()
+            .map(|(name, value)| {
+                let rate = match seconds > 0.0 {
+                    true => *value as f64 / seconds,
+                    false => 0.0,
+                };
+
+                (name.to_owned(), rate)
+            })
+            .collect();
+
+        CounterRates {
+            reset: self.reset,
+            rates,
+        }
+    }
+}
+
+/// Delta turns two consecutive samples of monotonic counters into increases and rates.
+/// Counters are the numeric fields of the serialized struct, nested fields are joined
+/// with a dot (e.g. `tcp_ext.tcp_timeouts`) and list items with their index (e.g. `timer.0`).
+pub trait Delta: Serialize {
+    /// returns the name of the numeric fields which are not monotonic counters
+    fn gauges() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// returns the name of the 32 bits counters which wrap around, a name matches
+    /// the field and its items (e.g. `timer` matches `timer.0`)
+    fn wrapping_counters() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// returns true if the counters have been reset since the previous sample
+    fn is_reset(&self, _previous: &Self) -> bool {
+        false
+    }
+
+    /// returns the counter values of the sample keyed by counter name
+    fn counters(&self) -> BTreeMap<String, u64> {
+        let mut counters = BTreeMap::new();
+
+        if let Ok(value) = serde_json::to_value(self) {
+            flatten_counters("", &value, &mut counters);
+        }
+
+        for gauge in Self::gauges() {
+            counters.remove(*gauge);
+        }
+
+        counters
+    }
+
+    /// returns the increase of each counter since the previous sample,
+    /// counters missing in one of the samples are skipped
+    fn delta(&self, previous: &Self) -> CounterDelta {
+        let previous_counters = previous.counters();
+        let current_counters = self.counters();
+        let wrapping = Self::wrapping_counters();
+        let reset = self.is_reset(previous)
+            || counters_restarted(&previous_counters, &current_counters, wrapping);
+
+        let counters = current_counters
+            .into_iter()
+            .filter_map(|(name, current)| {
+                let previous_value = previous_counters.get(&name)?;
+                let increase = match (reset, is_field_counter(&name, wrapping)) {
+                    (true, _) => current,
+                    (false, true) => counter32_increase(*previous_value, current),
+                    (false, false) => counter_increase(*previous_value, current),
+                };
+
+                Some((name, increase))
+            })
+            .collect();
+
+        CounterDelta { reset, counters }
+    }
+
+    /// returns the per-second rate of each counter since the previous sample
+    fn rate(&self, previous: &Self, elapsed: Duration) -> CounterRates {
+        self.delta(previous).rates(elapsed)
+    }
+}
+
+/// returns the increase of a counter between two samples, a decrease is handled as
+/// a reset (the counter restarted from zero)
+pub fn counter_increase(previous: u64, current: u64) -> u64 {
+    match current >= previous {
+        true => current - previous,
+        false => current,
+    }
+}
+
+/// returns the increase of a 32 bits counter between two samples, a decrease is
+/// handled as a wrap around
+pub fn counter32_increase(previous: u64, current: u64) -> u64 {
+    if current >= previous {
+        return current - previous;
+    }
+
+    if previous <= COUNTER_32_MAX {
+        return (COUNTER_32_MAX - previous) + current + 1;
+    }
+
+    current
+}
+
+/// returns the per-second rates of the samples keyed by name (e.g. device or interface name),
+/// entries without a previous sample (new devices) are skipped
+/// # Example
+/// ```
+/// use std::{collections::HashMap, thread, time::{Duration, Instant}};
+/// # #[cfg(feature = "net")]
+/// # fn main() {
+/// use procsys::{delta, net_dev};
+///
+/// let sample = || -> HashMap<String, net_dev::NetDev> {
+///     net_dev::collect()
+///         .unwrap_or_default()
+///         .into_iter()
+///         .map(|d| (d.name.to_owned(), d))
+///         .collect()
+/// };
+///
+/// let previous = sample();
+/// let start = Instant::now();
+/// thread::sleep(Duration::from_millis(100));
+/// let current = sample();
+///
+/// for (name, rates) in delta::rates(&current, &previous, start.elapsed()) {
+///     println!("{}: {:?}", name, rates.rates.get("rx_bytes"));
+/// }
+/// # }
+/// # #[cfg(not(feature = "net"))]
+/// # fn main() {}
+/// ```
+pub fn rates<K, T>(
+    current: &HashMap<K, T>,
+    previous: &HashMap<K, T>,
+    elapsed: Duration,
+) -> BTreeMap<K, CounterRates>
+where
+    K: Eq + Hash + Ord + Clone,
+    T: Delta,
+{
+    current
+        .iter()
+        .filter_map(|(key, sample)| {
+            let previous_sample = previous.get(key)?;
+            Some((key.clone(), sample.rate(previous_sample, elapsed)))
+        })
+        .collect()
+}
+
+/// returns the per-second rates of the samples keyed by the given key function
+/// (e.g. the interface name of [`NetDev`]), entries without a previous sample are skipped
+pub fn rates_by<K, T, F>(
+    current: &[T],
+    previous: &[T],
+    elapsed: Duration,
+    key: F,
+) -> BTreeMap<K, CounterRates>
+where
+    K: Ord,
+    T: Delta,
+    F: Fn(&T) -> K,
+{
+    let previous_samples: BTreeMap<K, &T> = previous.iter().map(|s| (key(s), s)).collect();
+
+    current
+        .iter()
+        .filter_map(|sample| {
+            let sample_key = key(sample);
+            let previous_sample = previous_samples.get(&sample_key)?;
+            let sample_rates = sample.rate(previous_sample, elapsed);
+
+            Some((sample_key, sample_rates))
+        })
+        .collect()
+}
+
+// a device which reappeared (e.g. driver reload) or a reused pid starts its counters
+// from zero: any decrease of a counter which does not wrap around is a restart, while
+// a wrap around only affects a few of the 32 bits counters at a time
+fn counters_restarted(
+    previous: &BTreeMap<String, u64>,
+    current: &BTreeMap<String, u64>,
+    wrapping: &[&str],
+) -> bool {
+    let mut wrapping_counters = 0;
+    let mut wrapping_decreases = 0;
+
+    for (name, previous_value) in previous.iter().filter(|(_, v)| **v > 0) {
+        let Some(current_value) = current.get(name) else {
+            continue;
+        };
+
+        let decreased = current_value < previous_value;
+
+        match is_field_counter(name, wrapping) {
+            true => {
+                wrapping_counters += 1;
+                if decreased {
+                    wrapping_decreases += 1;
+                }
+            }
+            false if decreased => return true,
+            false => {}
+        }
+    }
+
+    wrapping_decreases * 2 > wrapping_counters
+}
+
+// returns true if the counter is one of the fields or one of their items
+fn is_field_counter(name: &str, fields: &[&str]) -> bool {
+    fields.iter().any(|field| {
+        name.strip_prefix(field)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+    })
+}
+
+fn flatten_counters(prefix: &str, value: &Value, counters: &mut BTreeMap<String, u64>) {
+    let child_name = |name: &str| match prefix.is_empty() {
+        true => name.to_string(),
+        false => format!("{}.{}", prefix, name),
+    };
+
+    match value {
+        Value::Number(number) => {
+            if let Some(counter) = number.as_u64() {
+                counters.insert(prefix.to_string(), counter);
+            }
+        }
+        Value::Object(fields) => {
+            for (name, field_value) in fields {
+                flatten_counters(&child_name(name), field_value, counters);
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                flatten_counters(&child_name(&index.to_string()), item, counters);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(feature = "net")]
+impl Delta for NetDev {}
+
+#[cfg(feature = "process")]
+impl Delta for ProcessIO {}
+
+#[cfg(feature = "process")]
+impl Delta for ProcessNetstat {}
+
+impl Delta for DiskStats {
+    fn gauges() -> &'static [&'static str] {
+        &["major", "minor", "io_in_progress"]
+    }
+
+    fn wrapping_counters() -> &'static [&'static str] {
+        &[
+            "read_time",
+            "write_time",
+            "io_time",
+            "weighted_io_time",
+            "discard_time",
+            "flush_time",
+        ]
+    }
+}
+
+impl Delta for Softirqs {
+    fn wrapping_counters() -> &'static [&'static str] {
+        &[
+            "hi", "timer", "net_tx", "net_rx", "block", "irq_poll", "tasklet", "sched", "hr_timer",
+            "rcu",
+        ]
+    }
+}
+
+#[cfg(feature = "sysfs-hw")]
+impl Delta for InfiniBandCounters {}
+
+#[cfg(feature = "sysfs-storage")]
+impl Delta for FibreChannelHostCounters {
+    fn gauges() -> &'static [&'static str] {
+        &["seconds_since_last_reset"]
+    }
+
+    fn is_reset(&self, previous: &Self) -> bool {
+        match (
+            self.seconds_since_last_reset,
+            previous.seconds_since_last_reset,
+        ) {
+            (Some(current), Some(previous)) => current < previous,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "sysfs-storage")]
+impl Delta for ScsiTapeCounters {
+    fn gauges() -> &'static [&'static str] {
+        &["in_flight"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "net")]
+    fn test_net_dev(name: &str, rx_bytes: u64, tx_bytes: u64) -> NetDev {
+        NetDev {
+            name: name.to_string(),
+            rx_bytes,
+            tx_bytes,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn counter_increase_wrap_and_reset() {
+        assert_eq!(counter_increase(100, 150), 50);
+        assert_eq!(counter_increase(1000, 5), 5);
+        assert_eq!(counter_increase(u64::MAX - 9, 5), 5);
+        assert_eq!(counter_increase(1 << 40, 10), 10);
+
+        assert_eq!(counter32_increase(100, 150), 50);
+        assert_eq!(counter32_increase(u32::MAX as u64 - 9, 5), 15);
+        assert_eq!(counter32_increase(1 << 40, 10), 10);
+    }
+
+    #[test]
+    #[cfg(feature = "net")]
+    fn net_dev_rates() {
+        let previous = vec![test_net_dev("eth0", 1000, 500), test_net_dev("wlan0", 0, 0)];
+        let current = vec![
+            test_net_dev("eth0", 3000, 1500),
+            test_net_dev("docker0", 10, 10),
+        ];
+
+        let rates = rates_by(&current, &previous, Duration::from_secs(2), |d| {
+            d.name.to_owned()
+        });
+
+        assert_eq!(rates.len(), 1);
+        assert!(!rates["eth0"].reset);
+        assert_eq!(rates["eth0"].rates["rx_bytes"], 1000.0);
+        assert_eq!(rates["eth0"].rates["tx_bytes"], 500.0);
+        assert_eq!(rates["eth0"].rates["rx_packets"], 0.0);
+        assert!(!rates["eth0"].rates.contains_key("name"));
+
+        let reappeared = test_net_dev("eth0", 20, 10);
+        let delta = reappeared.delta(&current[0]);
+        assert!(delta.reset);
+        assert_eq!(delta.counters["rx_bytes"], 20);
+
+        // a single 64 bits counter decreased, the device has been reset
+        let restarted = test_net_dev("eth0", 5, 2000);
+        let delta = restarted.delta(&test_net_dev("eth0", 1000, 1000));
+        assert!(delta.reset);
+        assert_eq!(delta.counters["rx_bytes"], 5);
+        assert_eq!(delta.counters["tx_bytes"], 2000);
+
+        let delta = current[0].delta(&previous[0]);
+        assert_eq!(delta.counters["rx_bytes"], 2000);
+        assert_eq!(delta.rates(Duration::ZERO).rates["rx_bytes"], 0.0,);
+    }
+
+    #[test]
+    fn diskstats_wrap_and_reset() {
+        let previous = DiskStats {
+            reads_completed: 1000,
+            read_time: u32::MAX as u64 - 9,
+            io_time: 2000,
+            ..Default::default()
+        };
+        let current = DiskStats {
+            reads_completed: 1100,
+            read_time: 5,
+            io_time: 2500,
+            ..Default::default()
+        };
+
+        let delta = current.delta(&previous);
+        assert!(!delta.reset);
+        assert_eq!(delta.counters["reads_completed"], 100);
+        assert_eq!(delta.counters["read_time"], 15);
+        assert_eq!(delta.counters["io_time"], 500);
+
+        let reattached = DiskStats {
+            reads_completed: 5,
+            read_time: 20,
+            io_time: 3000,
+            ..Default::default()
+        };
+
+        let delta = reattached.delta(&previous);
+        assert!(delta.reset);
+        assert_eq!(delta.counters["reads_completed"], 5);
+        assert_eq!(delta.counters["read_time"], 20);
+        assert_eq!(delta.counters["io_time"], 3000);
+    }
+
+    #[test]
+    #[cfg(feature = "sysfs-storage")]
+    fn fibrechannel_reset() {
+        let previous = FibreChannelHostCounters {
+            rx_frames: Some(1 << 40),
+            tx_frames: Some(100),
+            seconds_since_last_reset: Some(1000),
+            ..Default::default()
+        };
+        let current = FibreChannelHostCounters {
+            rx_frames: Some(50),
+            tx_frames: Some(150),
+            seconds_since_last_reset: Some(10),
+            ..Default::default()
+        };
+
+        let delta = current.delta(&previous);
+        assert!(delta.reset);
+        assert_eq!(delta.counters["rx_frames"], 50);
+        assert_eq!(delta.counters["tx_frames"], 150);
+        assert!(!delta.counters.contains_key("seconds_since_last_reset"));
+        assert!(!delta.counters.contains_key("dumped_frames"));
+
+        let mut map_current = HashMap::new();
+        map_current.insert("host0".to_string(), current);
+        let mut map_previous = HashMap::new();
+        map_previous.insert("host0".to_string(), previous);
+
+        let host_rates = rates(&map_current, &map_previous, Duration::from_secs(10));
+        assert_eq!(host_rates["host0"].rates["rx_frames"], 5.0);
+    }
+
+    #[test]
+    fn nested_counters() {
+        let previous = Softirqs {
+            timer: vec![100, 200],
+            ..Default::default()
+        };
+        let current = Softirqs {
+            timer: vec![150, 260],
+            ..Default::default()
+        };
+
+        let delta = current.delta(&previous);
+        assert_eq!(delta.counters["timer.0"], 50);
+        assert_eq!(delta.counters["timer.1"], 60);
+
+        // a single per-cpu counter wrapped around
+        let wrapped = Softirqs {
+            timer: vec![10, 300],
+            ..Default::default()
+        };
+        let delta = wrapped.delta(&Softirqs {
+            timer: vec![u32::MAX as u64, 260],
+            ..Default::default()
+        });
+        assert!(!delta.reset);
+        assert_eq!(delta.counters["timer.0"], 11);
+        assert_eq!(delta.counters["timer.1"], 40);
+
+        #[cfg(feature = "sysfs-storage")]
+        {
+            let tape = ScsiTapeCounters {
+                in_flight: 3,
+                read_cnt: 10,
+                ..Default::default()
+            };
+            assert!(!tape.counters().contains_key("in_flight"));
+            assert_eq!(tape.counters()["read_cnt"], 10);
+        }
+    }
+}
@@ -0,0 +1,418 @@
+use std::{path::Path, time::Duration};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{CollectResult, MetricError, Partial},
+    procfs::ProcFs,
+    utils,
+};
+
+/// size in bytes of the sectors counted by the kernel, whatever the block size of the device
+pub const SECTOR_SIZE: u64 = 512;
+
+/// DiskStats is the I/O statistics of a block device parsed from /proc/diskstats
+/// or /sys/block/<dev>/stat, the times are in milliseconds.
+/// The discard fields are reported since kernel 4.18 and the flush fields since kernel 5.5.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DiskStats {
+    pub major: u64,
+    pub minor: u64,
+    pub name: String,
+    pub reads_completed: u64,
+    pub reads_merged: u64,
+    pub sectors_read: u64,
+    pub read_time: u64,
+    pub writes_completed: u64,
+    pub writes_merged: u64,
+    pub sectors_written: u64,
+    pub write_time: u64,
+    pub io_in_progress: u64,
+    pub io_time: u64,
+    pub weighted_io_time: u64,
+    pub discards_completed: Option<u64>,
+    pub discards_merged: Option<u64>,
+    pub sectors_discarded: Option<u64>,
+    pub discard_time: Option<u64>,
+    pub flushes_completed: Option<u64>,
+    pub flush_time: Option<u64>,
+}
+
+/// DiskIoRates is the I/O activity of a block device between two samples
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DiskIoRates {
+    pub read_iops: f64,
+    pub write_iops: f64,
+    pub read_bytes_per_second: f64,
+    pub write_bytes_per_second: f64,
+
+    /// fraction of the elapsed time the device had I/O in progress, between 0 and 1
+    pub utilization: f64,
+
+    /// average time in milliseconds of the completed reads, queueing included
+    pub read_await: f64,
+
+    /// average time in milliseconds of the completed writes, queueing included
+    pub write_await: f64,
+
+    /// average time in milliseconds of the completed reads and writes
+    pub await_time: f64,
+}
+
+impl DiskStats {
+    fn new() -> Self {
+        Default::default()
+    }
+
+    /// returns the number of bytes read
+    pub fn bytes_read(&self) -> u64 {
+        self.sectors_read * SECTOR_SIZE
+    }
+
+    /// returns the number of bytes written
+    pub fn bytes_written(&self) -> u64 {
+        self.sectors_written * SECTOR_SIZE
+    }
+
+    /// returns the I/O activity of the device since the previous sample, the rates are
+    /// zero if no time has elapsed
+    /// # Example
+    /// ```
+    /// use std::{thread, time::{Duration, Instant}};
+    /// use procsys::diskstats;
+    ///
+    /// let previous = diskstats::collect().expect("disk statistics");
+    /// let start = Instant::now();
+    /// thread::sleep(Duration::from_millis(100));
+    /// let current = diskstats::collect().expect("disk statistics");
+    ///
+    /// for disk in &current {
+    ///     if let Some(prev) = previous.iter().find(|d| d.name == disk.name) {
+    ///         let rates = disk.io_rates(prev, start.elapsed());
+    ///         println!("{}: {:.1}% busy", disk.name, rates.utilization * 100.0);
+    ///     }
+    /// }
+    ///
+    /// ```
+    pub fn io_rates(&self, previous: &DiskStats, elapsed: Duration) -> DiskIoRates {
+        let seconds = elapsed.as_secs_f64();
+        if seconds <= 0.0 {
+            return DiskIoRates::default();
+        }
+
+        // a device re-attached under the same name restarts its counters from zero
+        let restarted = self.reads_completed < previous.reads_completed
+            || self.writes_completed < previous.writes_completed
+            || self.sectors_read < previous.sectors_read
+            || self.sectors_written < previous.sectors_written;
+
+        let zero = DiskStats::default();
+        let previous = match restarted {
+            true => &zero,
+            false => previous,
+        };
+
+        let reads = self.reads_completed - previous.reads_completed;
+        let writes = self.writes_completed - previous.writes_completed;
+        let sectors_read = self.sectors_read - previous.sectors_read;
+        let sectors_written = self.sectors_written - previous.sectors_written;
+        let read_time = time_increase(previous.read_time, self.read_time);
+        let write_time = time_increase(previous.write_time, self.write_time);
+        let io_time = time_increase(previous.io_time, self.io_time);
+
+        DiskIoRates {
+            read_iops: reads as f64 / seconds,
+            write_iops: writes as f64 / seconds,
+            read_bytes_per_second: (sectors_read * SECTOR_SIZE) as f64 / seconds,
+            write_bytes_per_second: (sectors_written * SECTOR_SIZE) as f64 / seconds,
+            utilization: (io_time as f64 / (seconds * 1000.0)).min(1.0),
+            read_await: average(read_time, reads),
+            write_await: average(write_time, writes),
+            await_time: average(read_time + write_time, reads + writes),
+        }
+    }
+}
+
+// the times are 32 bits counters of milliseconds in the kernel, they wrap around
+// after about 49 days
+fn time_increase(previous: u64, current: u64) -> u64 {
+    (current as u32).wrapping_sub(previous as u32) as u64
+}
+
+fn average(total: u64, count: u64) -> f64 {
+    match count > 0 {
+        true => total as f64 / count as f64,
+        false => 0.0,
+    }
+}
+
+/// collects the I/O statistics of the block devices
+/// # Example
+/// ```
+/// use procsys::diskstats;
+///
+/// let sys_diskstats = diskstats::collect().expect("disk statistics");
+///
+/// for disk in &sys_diskstats {
+///     println!(
+///         "{}: read {} bytes, written {} bytes",
+///         disk.name,
+///         disk.bytes_read(),
+///         disk.bytes_written(),
+///     );
+/// }
+///
+/// ```
+pub fn collect() -> CollectResult<Vec<DiskStats>> {
+    ProcFs::default().diskstats()
+}
+
+/// collects the I/O statistics, a malformed line of /proc/diskstats is reported under
+/// its device name
+pub fn collect_lenient() -> CollectResult<Partial<Vec<DiskStats>>> {
+    ProcFs::default().diskstats_lenient()
+}
+
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<Vec<DiskStats>> {
+    collect_lenient_from(filename)?.into_result()
+}
+
+pub(crate) fn collect_lenient_from(filename: &Path) -> CollectResult<Partial<Vec<DiskStats>>> {
+    let mut sys_diskstats: Partial<Vec<DiskStats>> = Partial::default();
+
+    for (line_index, line) in utils::read_file_lines(filename)?.iter().enumerate() {
+        match parse_diskstats(line) {
+            Ok(disk) => sys_diskstats.items.push(disk),
+            Err(err) => sys_diskstats.push_error(
+                &disk_item(line, line_index + 1),
+                filename,
+                err.with_line(filename, line_index + 1),
+            ),
+        }
+    }
+
+    Ok(sys_diskstats)
+}
+
+// returns the device name of a broken line, its third field
+fn disk_item(line: &str, line_number: usize) -> String {
+    match line.split_whitespace().nth(2) {
+        Some(name) => name.to_string(),
+        None => utils::line_item(line, line_number),
+    }
+}
+
+fn parse_diskstats(line: &str) -> CollectResult<DiskStats> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+
+    if fields.len() < 3 {
+        return Err(MetricError::InvalidFieldNumberError(
+            "diskstats".to_string(),
+            fields.len(),
+            line.to_string(),
+        ));
+    }
+
+    let mut disk = parse_stat_fields(&fields[3..], line)?;
+    disk.major = utils::convert_str_to_u64(fields[0])?;
+    disk.minor = utils::convert_str_to_u64(fields[1])?;
+    disk.name = fields[2].to_string();
+
+    Ok(disk)
+}
+
+/// parses the statistics fields following the device name in /proc/diskstats,
+/// which are the content of /sys/block/<dev>/stat
+pub(crate) fn parse_stat_fields(fields: &[&str], line: &str) -> CollectResult<DiskStats> {
+    let mut values = Vec::with_capacity(fields.len());
+    for field in fields {
+        values.push(utils::convert_str_to_u64(field)?);
+    }
+
+    let mut disk = DiskStats::new();
+
+    match values.len() {
+        // partitions of the kernels before 2.6.25
+        4 => {
+            disk.reads_completed = values[0];
+            disk.sectors_read = values[1];
+            disk.writes_completed = values[2];
+            disk.sectors_written = values[3];
+            return Ok(disk);
+        }
+        11 | 15 | 17 => {}
+        _ => {
+            return Err(MetricError::InvalidFieldNumberError(
+                "diskstats".to_string(),
+                fields.len(),
+                line.to_string(),
+            ))
+        }
+    }
+
+    disk.reads_completed = values[0];
+    disk.reads_merged = values[1];
+    disk.sectors_read = values[2];
+    disk.read_time = values[3];
+    disk.writes_completed = values[4];
+    disk.writes_merged = values[5];
+    disk.sectors_written = values[6];
+    disk.write_time = values[7];
+    disk.io_in_progress = values[8];
+    disk.io_time = values[9];
+    disk.weighted_io_time = values[10];
+
+    if values.len() >= 15 {
+        disk.discards_completed = Some(values[11]);
+        disk.discards_merged = Some(values[12]);
+        disk.sectors_discarded = Some(values[13]);
+        disk.discard_time = Some(values[14]);
+    }
+
+    if values.len() == 17 {
+        disk.flushes_completed = Some(values[15]);
+        disk.flush_time = Some(values[16]);
+    }
+
+    Ok(disk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diskstats() {
+        let sys_diskstats =
+            collect_from(Path::new("test_data/fixtures/proc/diskstats")).expect("disk statistics");
+
+        assert_eq!(sys_diskstats.len(), 11);
+
+        for disk in sys_diskstats {
+            match disk.name.as_str() {
+                "sda" => {
+                    assert_eq!((disk.major, disk.minor), (8, 0));
+                    assert_eq!(disk.reads_completed, 25354637);
+                    assert_eq!(disk.sectors_read, 1003346126);
+                    assert_eq!(disk.bytes_read(), 1003346126 * 512);
+                    assert_eq!(disk.write_time, 63877960);
+                    assert_eq!(disk.weighted_io_time, 82621804);
+                    assert_eq!(disk.discards_completed, None);
+                    assert_eq!(disk.flushes_completed, None);
+                }
+                "sda1" => {
+                    assert_eq!(disk.reads_completed, 250);
+                    assert_eq!(disk.sectors_read, 2000);
+                    assert_eq!(disk.reads_merged, 0);
+                }
+                "nvme0n1" => {
+                    assert_eq!((disk.major, disk.minor), (259, 0));
+                    assert_eq!(disk.io_time, 222766);
+                    assert_eq!(disk.discards_completed, Some(0));
+                    assert_eq!(disk.flushes_completed, None);
+                }
+                "sdb" => {
+                    assert_eq!(disk.io_in_progress, 2);
+                    assert_eq!(disk.sectors_discarded, Some(2019984));
+                    assert_eq!(disk.flushes_completed, Some(83612));
+                    assert_eq!(disk.flush_time, Some(140240));
+                }
+                "sda2" | "sda3" | "nvme0n1p1" | "dm-0" | "dm-1" | "dm-2" | "zram0" => {}
+                _ => panic!("invalid disk name: {}", disk.name),
+            }
+        }
+    }
+
+    #[test]
+    fn diskstats_invalid_line() {
+        let mut memfs = crate::filesystem::MemoryFileSystem::new();
+        memfs.insert_file(
+            "/proc/diskstats",
+            "   8       0 sda 1 2 3 4 5 6 7 8 0 9 10\n   8       1 sda1 1 2 3\n",
+        );
+        let procfs = ProcFs::with_filesystem("/proc", std::sync::Arc::new(memfs));
+
+        let partial = procfs.diskstats_lenient().expect("disk statistics");
+        assert_eq!(partial.items.len(), 1);
+        assert_eq!(partial.errors.len(), 1);
+        assert_eq!(partial.errors[0].item, "sda1");
+        assert!(procfs.diskstats().is_err());
+    }
+
+    #[test]
+    fn diskstats_io_rates() {
+        let previous = DiskStats {
+            reads_completed: 1000,
+            sectors_read: 8000,
+            read_time: 500,
+            writes_completed: 200,
+            sectors_written: 4000,
+            write_time: 1000,
+            io_time: 10000,
+            ..Default::default()
+        };
+        let current = DiskStats {
+            reads_completed: 1400,
+            sectors_read: 16000,
+            read_time: 1300,
+            writes_completed: 300,
+            sectors_written: 6000,
+            write_time: 1600,
+            io_time: 11000,
+            ..Default::default()
+        };
+
+        let rates = current.io_rates(&previous, Duration::from_secs(2));
+        assert_eq!(rates.read_iops, 200.0);
+        assert_eq!(rates.write_iops, 50.0);
+        assert_eq!(rates.read_bytes_per_second, 2048000.0);
+        assert_eq!(rates.write_bytes_per_second, 512000.0);
+        assert_eq!(rates.utilization, 0.5);
+        assert_eq!(rates.read_await, 2.0);
+        assert_eq!(rates.write_await, 6.0);
+        assert_eq!(rates.await_time, 2.8);
+
+        assert_eq!(
+            current.io_rates(&previous, Duration::ZERO),
+            DiskIoRates::default()
+        );
+
+        // the io time wrapped around
+        let wrapped = DiskStats {
+            io_time: 500,
+            ..current.clone()
+        };
+        let busy = DiskStats {
+            io_time: u32::MAX as u64 - 499,
+            ..current.clone()
+        };
+        assert_eq!(
+            wrapped.io_rates(&busy, Duration::from_secs(1)).utilization,
+            1.0
+        );
+        let busy = DiskStats {
+            io_time: u32::MAX as u64 - 99,
+            ..current.clone()
+        };
+        assert_eq!(
+            wrapped.io_rates(&busy, Duration::from_secs(1)).utilization,
+            0.6
+        );
+
+        // the disk was re-attached under the same name
+        let reattached = DiskStats {
+            reads_completed: 5,
+            sectors_read: 40,
+            read_time: 10,
+            ..Default::default()
+        };
+        let rates = reattached.io_rates(&current, Duration::from_secs(1));
+        assert_eq!(rates.read_iops, 5.0);
+        assert_eq!(rates.read_bytes_per_second, 20480.0);
+        assert_eq!(rates.read_await, 2.0);
+        assert_eq!(rates.utilization, 0.0);
+    }
+}
@@ -1,13 +1,87 @@
 use std::{
-    fmt,
+    error, fmt, io,
     num::{ParseFloatError, ParseIntError},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 pub type CollectResult<T> = std::result::Result<T, MetricError>;
 
-/// An error received from sysmetrics
+/// ItemError is the error of a single item (e.g. a device or a table line)
+/// of a lenient collection
 #[derive(Debug)]
+pub struct ItemError {
+    /// name of the item (e.g. `phy-0:0` or `line 3`)
+    pub item: String,
+
+    /// path the item has been read from
+    pub path: PathBuf,
+
+    pub error: MetricError,
+}
+
+impl fmt::Display for ItemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.item, self.error)
+    }
+}
+
+/// Partial holds the successfully collected items of a lenient collection
+/// along with the errors of the items which could not be collected
+#[derive(Debug, Default)]
+pub struct Partial<T> {
+    pub items: T,
+    pub errors: Vec<ItemError>,
+}
+
+impl<T> Partial<T> {
+    /// returns true if all items have been collected
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// returns the items if all of them have been collected, otherwise the
+    /// error of the first failed item
+    pub fn into_result(mut self) -> CollectResult<T> {
+        match self.errors.is_empty() {
+            true => Ok(self.items),
+            false => Err(self.errors.remove(0).error),
+        }
+    }
+
+    pub(crate) fn push_error(&mut self, item: &str, path: &Path, error: MetricError) {
+        self.errors.push(ItemError {
+            item: item.to_string(),
+            path: path.to_path_buf(),
+            error: error.with_path(path),
+        });
+    }
+}
+
+/// ErrorKind is the category of a [`MetricError`], e.g. to tell a process
+/// which has exited apart from a kernel format which has changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The file, process or collector does not exist (anymore)
+    NotFound,
+
+    /// The file cannot be read with the current privileges
+    PermissionDenied,
+
+    /// The content cannot be parsed
+    Parse,
+
+    /// The information is not supported by the platform
+    Unsupported,
+
+    /// Any other error
+    Other,
+}
+
+/// An error received from sysmetrics, the variants depend on the enabled features
+/// (e.g. `SerdeJsonError`, `RegexError`), match [`MetricError::kind`] rather than
+/// every variant
+#[derive(Debug)]
+#[non_exhaustive]
 pub enum MetricError {
     /// Platform does not support Desktop Management Interface (DMI) information
     DmiSupportError,
@@ -16,6 +90,7 @@ pub enum MetricError {
     IOError(PathBuf, std::io::Error),
 
     /// json serde pretty error
+    #[cfg(feature = "serde")]
     SerdeJsonError(serde_json::Error),
 
     /// parse int error
@@ -33,14 +108,124 @@ pub enum MetricError {
     /// Process not found
     ProcessNotFound(usize),
 
+    /// Process exited while one of its files was read
+    ProcessGone(usize, PathBuf),
+
     /// Path not found
     PathNotFound(PathBuf),
 
     /// Regex error
+    #[cfg(feature = "regex")]
     RegexError(regex::Error),
 
     /// General parse error
     ParseError(String),
+
+    /// Collector not found in registry
+    CollectorNotFound(String),
+
+    /// Collector failed when the snapshot was taken
+    SnapshotCollectorError(String, String),
+
+    /// Async collector task failed
+    TaskError(String),
+
+    /// Error of a file, and of one of its lines if known
+    ContextError(PathBuf, Option<usize>, Box<MetricError>),
+}
+
+impl MetricError {
+    /// returns the category of the error
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            MetricError::IOError(_, ref e) => io_error_kind(e),
+            MetricError::ProcessNotFound(_)
+            | MetricError::ProcessGone(_, _)
+            | MetricError::PathNotFound(_)
+            | MetricError::CollectorNotFound(_) => ErrorKind::NotFound,
+            #[cfg(feature = "serde")]
+            MetricError::SerdeJsonError(_) => ErrorKind::Parse,
+            MetricError::ParseIntError(_, _)
+            | MetricError::ParseFloatError(_, _)
+            | MetricError::ByteConvertError(_)
+            | MetricError::InvalidFieldNumberError(_, _, _)
+            | MetricError::ParseError(_) => ErrorKind::Parse,
+            MetricError::DmiSupportError => ErrorKind::Unsupported,
+            #[cfg(feature = "regex")]
+            MetricError::RegexError(_) => ErrorKind::Other,
+            MetricError::SnapshotCollectorError(_, _) | MetricError::TaskError(_) => {
+                ErrorKind::Other
+            }
+            MetricError::ContextError(_, _, ref e) => e.kind(),
+        }
+    }
+
+    /// returns the path of the file the error occurred on, if known
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            MetricError::IOError(ref p, _)
+            | MetricError::PathNotFound(ref p)
+            | MetricError::ProcessGone(_, ref p)
+            | MetricError::ContextError(ref p, _, _) => Some(p),
+            _ => None,
+        }
+    }
+
+    /// returns the (1-based) line number the error occurred on, if known
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            MetricError::ContextError(_, line, _) => *line,
+            _ => None,
+        }
+    }
+
+    // adds the path of the file which has been parsed, unless the error
+    // already carries one
+    pub(crate) fn with_path(self, path: &Path) -> Self {
+        match self.path() {
+            Some(_) => self,
+            None => MetricError::ContextError(path.to_path_buf(), None, Box::new(self)),
+        }
+    }
+
+    // adds the path and the line number of the line which has been parsed
+    pub(crate) fn with_line(self, path: &Path, line: usize) -> Self {
+        match self {
+            MetricError::ContextError(p, None, e) => MetricError::ContextError(p, Some(line), e),
+            MetricError::ContextError(_, Some(_), _) | MetricError::IOError(_, _) => self,
+            e => MetricError::ContextError(path.to_path_buf(), Some(line), Box::new(e)),
+        }
+    }
+}
+
+fn io_error_kind(err: &io::Error) -> ErrorKind {
+    if err.raw_os_error() == Some(libc::ESRCH) {
+        return ErrorKind::NotFound;
+    }
+
+    match err.kind() {
+        io::ErrorKind::NotFound => ErrorKind::NotFound,
+        io::ErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+        io::ErrorKind::InvalidData => ErrorKind::Parse,
+        io::ErrorKind::Unsupported => ErrorKind::Unsupported,
+        _ => ErrorKind::Other,
+    }
+}
+
+impl error::Error for MetricError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            MetricError::IOError(_, ref e) => Some(e),
+            #[cfg(feature = "serde")]
+            MetricError::SerdeJsonError(ref e) => Some(e),
+            MetricError::ParseIntError(_, ref e) => Some(e),
+            MetricError::ParseFloatError(_, ref e) => Some(e),
+            #[cfg(feature = "regex")]
+            MetricError::RegexError(ref e) => Some(e),
+            MetricError::ContextError(_, _, ref e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for MetricError {
@@ -53,6 +238,7 @@ impl fmt::Display for MetricError {
             MetricError::IOError(ref p, ref e) => {
                 write!(f, "cannot read sysfs {:?}: {}", p, e)
             }
+            #[cfg(feature = "serde")]
             MetricError::SerdeJsonError(ref e) => write!(f, "json pretty error: {}", e),
             MetricError::ParseIntError(ref item, ref e) => {
                 write!(f, "{} parse {} int error", item, e)
@@ -61,13 +247,89 @@ impl fmt::Display for MetricError {
                 write!(f, "{} parse {} float error", item, e)
             }
             MetricError::ProcessNotFound(ref pid) => write!(f, "process (pid={}) not found", pid),
+            MetricError::ProcessGone(ref pid, ref p) => {
+                write!(f, "process (pid={}) exited while reading {:?}", pid, p)
+            }
             MetricError::PathNotFound(ref p) => write!(f, "path ({:?}) not found", p),
             MetricError::ByteConvertError(ref unit) => write!(f, "invalid unit: {}", unit),
             MetricError::ParseError(ref msg) => write!(f, "parse error: {}", msg),
+            MetricError::CollectorNotFound(ref name) => {
+                write!(f, "collector ({}) not found", name)
+            }
+            MetricError::SnapshotCollectorError(ref name, ref e) => {
+                write!(f, "snapshot collector ({}) error: {}", name, e)
+            }
+            #[cfg(feature = "regex")]
             MetricError::RegexError(ref e) => write!(f, "regex error: {}", e),
+            MetricError::TaskError(ref e) => write!(f, "async task error: {}", e),
             MetricError::InvalidFieldNumberError(ref title, ref num, ref fields) => {
                 write!(f, "invalid {} fields number {}: {:?}", title, num, fields)
             }
+            MetricError::ContextError(ref p, Some(line), ref e) => {
+                write!(f, "{:?} line {}: {}", p, line, e)
+            }
+            MetricError::ContextError(ref p, None, ref e) => write!(f, "{:?}: {}", p, e),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::*;
+
+    #[test]
+    fn error_kind() {
+        let not_found = io::Error::from(io::ErrorKind::NotFound);
+        assert_eq!(
+            MetricError::IOError(PathBuf::from("/proc/1/stat"), not_found).kind(),
+            ErrorKind::NotFound,
+        );
+
+        let denied = io::Error::from(io::ErrorKind::PermissionDenied);
+        assert_eq!(
+            MetricError::IOError(PathBuf::from("/proc/1/environ"), denied).kind(),
+            ErrorKind::PermissionDenied,
+        );
+
+        let esrch = io::Error::from_raw_os_error(libc::ESRCH);
+        assert_eq!(
+            MetricError::IOError(PathBuf::from("/proc/1/stack"), esrch).kind(),
+            ErrorKind::NotFound,
+        );
+
+        assert_eq!(MetricError::DmiSupportError.kind(), ErrorKind::Unsupported);
+        assert_eq!(
+            MetricError::ParseError("invalid".to_string()).kind(),
+            ErrorKind::Parse
+        );
+    }
+
+    #[test]
+    fn error_context() {
+        let parse_err = "x".parse::<u64>().unwrap_err();
+        let err = MetricError::ParseIntError("rx_bytes".to_string(), parse_err)
+            .with_line(Path::new("/proc/net/dev"), 3);
+
+        assert_eq!(err.kind(), ErrorKind::Parse);
+        assert_eq!(err.path(), Some(Path::new("/proc/net/dev")));
+        assert_eq!(err.line(), Some(3));
+        assert!(err
+            .to_string()
+            .starts_with("\"/proc/net/dev\" line 3: rx_bytes"));
+
+        // the source chain leads to the original parse error
+        let source = err.source().expect("parse int error");
+        assert!(source.source().is_some());
+
+        // errors which already carry a path keep it
+        let io_err = MetricError::IOError(
+            PathBuf::from("/proc/net/dev"),
+            io::Error::from(io::ErrorKind::NotFound),
+        );
+        let io_err = io_err.with_path(Path::new("/proc"));
+        assert_eq!(io_err.path(), Some(Path::new("/proc/net/dev")));
+        assert!(io_err.source().is_some());
+    }
+}
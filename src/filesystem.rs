@@ -0,0 +1,575 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, VecDeque},
+    ffi::OsString,
+    fmt, fs,
+    io::{self, Read},
+    os::unix::fs::{FileTypeExt, MetadataExt},
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{
+    error::{CollectResult, MetricError},
+    ttar::{TtarArchive, TtarEntry},
+};
+
+// symbolic links followed while resolving a path before giving up (same as Linux)
+const MAX_SYMLINK_FOLLOWS: usize = 40;
+
+thread_local! {
+    // filesystem the parsers of the current thread read from, the real one if unset
+    static CURRENT: RefCell<Option<Arc<dyn FileSystem>>> = const { RefCell::new(None) };
+}
+
+/// FileSystem is the source every parser of procsys reads from, the proc and sys
+/// handles read from the real filesystem unless created with `with_filesystem`
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use procsys::{filesystem::MemoryFileSystem, ProcFs};
+///
+/// let mut memfs = MemoryFileSystem::new();
+/// memfs.insert_file("/proc/loadavg", "0.02 0.04 0.05 1/497 11947\n");
+///
+/// let procfs = ProcFs::with_filesystem("/proc", Arc::new(memfs));
+/// let sys_loadavg = procfs.loadavg().expect("system load average");
+/// assert_eq!(sys_loadavg.load1, 0.02);
+///
+/// ```
+pub trait FileSystem: fmt::Debug + Send + Sync {
+    /// returns the content of a file, symbolic links are followed
+    fn read_file(&self, path: &Path) -> io::Result<Vec<u8>>;
+
+    /// replaces the content of `buf` with the content of a file, the backends
+    /// may reuse the allocation of `buf` (e.g. when reading many small files)
+    fn read_file_into(&self, path: &Path, buf: &mut Vec<u8>) -> io::Result<()> {
+        *buf = self.read_file(path)?;
+        Ok(())
+    }
+
+    /// returns the target of a symbolic link
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf>;
+
+    /// returns the names of the entries of a directory sorted by name
+    fn list_dir(&self, path: &Path) -> io::Result<Vec<String>>;
+
+    /// returns true if the path exists, symbolic links are followed
+    fn exists(&self, path: &Path) -> bool;
+
+    /// returns true if the path is a directory, symbolic links are followed
+    fn is_dir(&self, path: &Path) -> bool {
+        self.list_dir(path).is_ok()
+    }
+
+    /// returns the device number of a block device node, or of the device holding
+    /// the filesystem of any other file, symbolic links are followed. The backends
+    /// without device numbers (e.g. the in-memory filesystems) are not supported.
+    fn device_number(&self, path: &Path) -> io::Result<u64> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("{}: device numbers are not supported", path.display()),
+        ))
+    }
+}
+
+/// RealFileSystem reads from the filesystem of the operating system
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealFileSystem;
+
+impl FileSystem for RealFileSystem {
+    fn read_file(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(path)
+    }
+
+    fn read_file_into(&self, path: &Path, buf: &mut Vec<u8>) -> io::Result<()> {
+        buf.clear();
+        fs::File::open(path)?.read_to_end(buf)?;
+        Ok(())
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        fs::read_link(path)
+    }
+
+    fn list_dir(&self, path: &Path) -> io::Result<Vec<String>> {
+        let mut names = Vec::new();
+
+        for dir_entry in fs::read_dir(path)? {
+            names.push(dir_entry?.file_name().to_string_lossy().to_string());
+        }
+
+        names.sort();
+
+        Ok(names)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    fn device_number(&self, path: &Path) -> io::Result<u64> {
+        let metadata = fs::metadata(path)?;
+
+        match metadata.file_type().is_block_device() {
+            true => Ok(metadata.rdev()),
+            false => Ok(metadata.dev()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MemoryEntry {
+    Directory,
+    File(Vec<u8>),
+    Symlink(PathBuf),
+}
+
+/// MemoryFileSystem holds files, directories and symbolic links in memory,
+/// absolute and relative paths share the same tree (`/proc` and `proc` are the same entry)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryFileSystem {
+    entries: BTreeMap<PathBuf, MemoryEntry>,
+}
+
+impl MemoryFileSystem {
+    /// returns an empty filesystem
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// adds a file, the missing parent directories are added as well
+    pub fn insert_file<P: AsRef<Path>, C: AsRef<[u8]>>(&mut self, path: P, content: C) {
+        self.insert(path.as_ref(), MemoryEntry::File(content.as_ref().to_vec()));
+    }
+
+    /// adds a symbolic link, relative targets are resolved from the link's directory
+    pub fn insert_symlink<P: AsRef<Path>, T: AsRef<Path>>(&mut self, path: P, target: T) {
+        self.insert(
+            path.as_ref(),
+            MemoryEntry::Symlink(target.as_ref().to_path_buf()),
+        );
+    }
+
+    /// adds an empty directory, the missing parent directories are added as well
+    pub fn insert_dir<P: AsRef<Path>>(&mut self, path: P) {
+        self.insert(path.as_ref(), MemoryEntry::Directory);
+    }
+
+    /// returns the number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// returns true if the filesystem has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn insert(&mut self, path: &Path, entry: MemoryEntry) {
+        let mut entry_path = PathBuf::new();
+
+        for name in path_components(path) {
+            if name == ".." {
+                entry_path.pop();
+                continue;
+            }
+
+            if !entry_path.as_os_str().is_empty() {
+                self.entries
+                    .entry(entry_path.clone())
+                    .or_insert(MemoryEntry::Directory);
+            }
+
+            entry_path.push(name);
+        }
+
+        if !entry_path.as_os_str().is_empty() {
+            self.entries.insert(entry_path, entry);
+        }
+    }
+
+    // returns the key of the entry a path points to, the last component is
+    // only resolved if it is a symbolic link and follow_last is set
+    fn resolve(&self, path: &Path, follow_last: bool) -> io::Result<PathBuf> {
+        let mut remaining: VecDeque<OsString> = path_components(path).collect();
+        let mut resolved = PathBuf::new();
+        let mut follows = 0;
+
+        while let Some(name) = remaining.pop_front() {
+            if name == ".." {
+                resolved.pop();
+                continue;
+            }
+
+            let entry_path = resolved.join(&name);
+
+            match self.entries.get(&entry_path) {
+                Some(MemoryEntry::Symlink(target)) if follow_last || !remaining.is_empty() => {
+                    follows += 1;
+                    if follows > MAX_SYMLINK_FOLLOWS {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "too many levels of symbolic links",
+                        ));
+                    }
+
+                    if target.is_absolute() {
+                        resolved = PathBuf::new();
+                    }
+
+                    for target_name in path_components(target).rev() {
+                        remaining.push_front(target_name);
+                    }
+                }
+                Some(MemoryEntry::File(_)) if !remaining.is_empty() => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "not a directory",
+                    ));
+                }
+                Some(_) => resolved = entry_path,
+                None => return Err(io::Error::from(io::ErrorKind::NotFound)),
+            }
+        }
+
+        Ok(resolved)
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn read_file(&self, path: &Path) -> io::Result<Vec<u8>> {
+        match self.entries.get(&self.resolve(path, true)?) {
+            Some(MemoryEntry::File(content)) => Ok(content.to_owned()),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "is a directory",
+            )),
+        }
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        match self.entries.get(&self.resolve(path, false)?) {
+            Some(MemoryEntry::Symlink(target)) => Ok(target.to_owned()),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "not a symbolic link",
+            )),
+        }
+    }
+
+    fn list_dir(&self, path: &Path) -> io::Result<Vec<String>> {
+        let dir_path = self.resolve(path, true)?;
+
+        if !dir_path.as_os_str().is_empty()
+            && self.entries.get(&dir_path) != Some(&MemoryEntry::Directory)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "not a directory",
+            ));
+        }
+
+        let names = self
+            .entries
+            .range(dir_path.clone()..)
+            .map(|(entry_path, _)| entry_path)
+            .take_while(|entry_path| entry_path.starts_with(&dir_path))
+            .filter(|entry_path| entry_path.parent() == Some(dir_path.as_path()))
+            .filter_map(|entry_path| entry_path.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .collect();
+
+        Ok(names)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.resolve(path, true).is_ok()
+    }
+}
+
+/// TtarFileSystem serves the content of a ttar archive (e.g. `test_data/fixtures.ttar`
+/// or an archive written by [`crate::capture::Capture`]) without extracting it
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use procsys::{filesystem::TtarFileSystem, ProcFs};
+///
+/// let ttarfs = TtarFileSystem::open("test_data/fixtures.ttar").expect("fixtures archive");
+///
+/// let procfs = ProcFs::with_filesystem("fixtures/proc", Arc::new(ttarfs));
+/// let sys_meminfo = procfs.meminfo().expect("memory information");
+/// println!("{:?}", sys_meminfo.mem_total);
+///
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TtarFileSystem {
+    memfs: MemoryFileSystem,
+}
+
+impl TtarFileSystem {
+    /// returns a filesystem with the entries of the given archive
+    pub fn from_archive(archive: &TtarArchive) -> Self {
+        let mut memfs = MemoryFileSystem::new();
+
+        for (path, entry) in archive.entries() {
+            match entry {
+                TtarEntry::Directory { .. } => memfs.insert_dir(path),
+                TtarEntry::File { content, .. } => memfs.insert_file(path, content),
+                TtarEntry::Symlink { target } => memfs.insert_symlink(path, target),
+            }
+        }
+
+        Self { memfs }
+    }
+
+    /// reads the given archive file
+    pub fn open<P: AsRef<Path>>(filename: P) -> CollectResult<Self> {
+        Ok(Self::from_archive(&TtarArchive::read_from(filename)?))
+    }
+}
+
+impl FileSystem for TtarFileSystem {
+    fn read_file(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.memfs.read_file(path)
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        self.memfs.read_link(path)
+    }
+
+    fn list_dir(&self, path: &Path) -> io::Result<Vec<String>> {
+        self.memfs.list_dir(path)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.memfs.exists(path)
+    }
+}
+
+// returns the components of a path as names, the root is dropped and `..` is kept
+fn path_components(path: &Path) -> impl DoubleEndedIterator<Item = OsString> + '_ {
+    path.components().filter_map(|component| match component {
+        Component::Normal(name) => Some(name.to_os_string()),
+        Component::ParentDir => Some(OsString::from("..")),
+        _ => None,
+    })
+}
+
+/// returns the real filesystem
+pub(crate) fn real() -> Arc<dyn FileSystem> {
+    Arc::new(RealFileSystem)
+}
+
+/// returns the filesystem the parsers of the current thread read from
+pub(crate) fn current() -> Arc<dyn FileSystem> {
+    CURRENT.with(|current| current.borrow().clone().unwrap_or_else(real))
+}
+
+/// runs f with the parsers of the current thread reading from the given filesystem
+pub(crate) fn scope<T>(fs: &Arc<dyn FileSystem>, f: impl FnOnce() -> T) -> T {
+    // restores the previous filesystem, even if f panics
+    struct Restore(Option<Arc<dyn FileSystem>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+        }
+    }
+
+    let _restore = Restore(CURRENT.with(|current| current.replace(Some(fs.clone()))));
+
+    f()
+}
+
+pub(crate) fn read_file(path: &Path) -> CollectResult<Vec<u8>> {
+    match current().read_file(path) {
+        Ok(content) => Ok(content),
+        Err(err) => Err(MetricError::IOError(path.to_path_buf(), err)),
+    }
+}
+
+pub(crate) fn read_to_string(path: &Path) -> CollectResult<String> {
+    match String::from_utf8(read_file(path)?) {
+        Ok(content) => Ok(content),
+        Err(err) => Err(MetricError::IOError(
+            path.to_path_buf(),
+            io::Error::new(io::ErrorKind::InvalidData, err),
+        )),
+    }
+}
+
+pub(crate) fn read_link(path: &Path) -> io::Result<PathBuf> {
+    current().read_link(path)
+}
+
+pub(crate) fn list_dir(path: &Path) -> io::Result<Vec<String>> {
+    current().list_dir(path)
+}
+
+pub(crate) fn exists(path: &Path) -> bool {
+    current().exists(path)
+}
+
+pub(crate) fn is_dir(path: &Path) -> bool {
+    current().is_dir(path)
+}
+
+pub(crate) fn device_number(path: &Path) -> CollectResult<u64> {
+    match current().device_number(path) {
+        Ok(device) => Ok(device),
+        Err(err) => Err(MetricError::IOError(path.to_path_buf(), err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_filesystem() {
+        let mut memfs = MemoryFileSystem::new();
+        memfs.insert_file("/sys/devices/virtual/nvme0/serial", "S680\n");
+        memfs.insert_symlink("/sys/class/nvme/nvme0", "../../devices/virtual/nvme0");
+        memfs.insert_symlink("/sys/class/nvme/nvme1", "/sys/class/nvme/nvme0");
+        memfs.insert_symlink("/sys/class/nvme/loop", "loop");
+
+        assert_eq!(
+            memfs
+                .read_file(Path::new("/sys/class/nvme/nvme1/serial"))
+                .unwrap(),
+            b"S680\n",
+        );
+        assert_eq!(
+            memfs.read_link(Path::new("sys/class/nvme/nvme0")).unwrap(),
+            PathBuf::from("../../devices/virtual/nvme0"),
+        );
+        assert_eq!(
+            memfs.list_dir(Path::new("/sys/class/nvme")).unwrap(),
+            ["loop", "nvme0", "nvme1"],
+        );
+        assert_eq!(
+            memfs.list_dir(Path::new("/sys/class/nvme/nvme0")).unwrap(),
+            ["serial"],
+        );
+        assert_eq!(memfs.list_dir(Path::new("/")).unwrap(), ["sys"]);
+
+        assert!(memfs.exists(Path::new("/sys/class/../devices")));
+        assert!(memfs.is_dir(Path::new("/sys/class/nvme/nvme0")));
+        assert!(!memfs.is_dir(Path::new("/sys/devices/virtual/nvme0/serial")));
+        assert!(!memfs.exists(Path::new("/sys/class/nvme/nvme2")));
+        assert!(!memfs.exists(Path::new("/sys/class/nvme/loop")));
+        assert!(memfs
+            .read_file(Path::new("/sys/devices/virtual/nvme0/serial/x"))
+            .is_err());
+    }
+
+    #[test]
+    fn filesystem_scope() {
+        let mut memfs = MemoryFileSystem::new();
+        memfs.insert_file("proc/loadavg", "1.00 2.00 3.00 1/1 1\n");
+        let memfs: Arc<dyn FileSystem> = Arc::new(memfs);
+
+        let content = scope(&memfs, || read_to_string(Path::new("proc/loadavg")));
+        assert_eq!(content.unwrap(), "1.00 2.00 3.00 1/1 1\n");
+
+        // the real filesystem is restored once the scope is left
+        assert!(read_to_string(Path::new("proc/loadavg")).is_err());
+        assert!(exists(Path::new("test_data/fixtures/proc/loadavg")));
+    }
+
+    #[test]
+    fn ttar_filesystem() {
+        let ttarfs = TtarFileSystem::open("test_data/fixtures.ttar").expect("fixtures archive");
+
+        assert_eq!(
+            ttarfs
+                .read_file(Path::new("fixtures/proc/loadavg"))
+                .unwrap(),
+            fs::read("test_data/fixtures/proc/loadavg").unwrap(),
+        );
+        assert_eq!(
+            ttarfs
+                .read_link(Path::new("fixtures/proc/26231/exe"))
+                .unwrap(),
+            PathBuf::from("/usr/bin/ls"),
+        );
+
+        assert_eq!(
+            ttarfs
+                .list_dir(Path::new("fixtures/proc/27079/task"))
+                .unwrap(),
+            ["27079", "27080", "27081", "27082", "27083"],
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "process"))]
+    fn ttar_filesystem_collectors() {
+        use crate::{collector::Registry, procfs::ProcFs, sysfs::SysFs};
+
+        let ttarfs: Arc<dyn FileSystem> =
+            Arc::new(TtarFileSystem::open("test_data/fixtures.ttar").expect("fixtures archive"));
+        let ttar_registry = Registry::with_mounts(
+            &ProcFs::with_filesystem("fixtures/proc", ttarfs.clone()),
+            &SysFs::with_filesystem("fixtures/sys", ttarfs),
+        );
+        let registry = Registry::with_mounts(
+            &ProcFs::new("test_data/fixtures/proc"),
+            &SysFs::new("test_data/fixtures/sys"),
+        );
+
+        let ttar_results = ttar_registry.collect_all();
+        for (name, result) in registry.collect_all() {
+            // processes hold the path they have been found at
+            if name == "processes" {
+                assert_eq!(
+                    ttar_results[&name]
+                        .as_ref()
+                        .unwrap()
+                        .as_array()
+                        .unwrap()
+                        .len(),
+                    7,
+                );
+                continue;
+            }
+
+            match (result, &ttar_results[&name]) {
+                (Ok(value), Ok(ttar_value)) => assert_eq!(&value, ttar_value, "{} collector", name),
+                (Err(_), Err(_)) => {}
+                (result, ttar_result) => panic!("{}: {:?} != {:?}", name, result, ttar_result),
+            }
+        }
+
+        let procfs = ttar_registry.get("processes").unwrap();
+        assert_eq!(procfs.source(), Path::new("fixtures/proc"));
+    }
+
+    #[test]
+    #[cfg(feature = "process")]
+    fn ttar_filesystem_process() {
+        use crate::procfs::ProcFs;
+
+        let ttarfs = TtarFileSystem::open("test_data/fixtures.ttar").expect("fixtures archive");
+        let procfs = ProcFs::with_filesystem("fixtures/proc", Arc::new(ttarfs));
+
+        let sys_proc = procfs.process(27079).expect("running proc 27079");
+        let sys_proc_tasks = sys_proc.tasks().expect("running proc 27079 tasks");
+        assert_eq!(sys_proc_tasks.len(), 5);
+        assert_eq!(
+            sys_proc_tasks[1].stat().expect("task stat").comm,
+            "pthread_load",
+        );
+
+        let sys_proc = procfs.process(26231).expect("running proc 26231");
+        assert_eq!(sys_proc.comm().unwrap(), "vim");
+        assert_eq!(sys_proc.io().unwrap().rchar, 750339);
+        assert_eq!(sys_proc.fds().unwrap().len(), 5);
+        assert!(procfs.process(2).is_err());
+    }
+}
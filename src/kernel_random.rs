@@ -1,8 +1,9 @@
 use std::path::Path;
 
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-use crate::{error::CollectResult, utils};
+use crate::{error::CollectResult, procfs::ProcFs, utils};
 
 enum KernelRandomInfo {
     EntropyAvaliable,
@@ -27,7 +28,8 @@ impl KernelRandomInfo {
 }
 
 /// KernelRandom contains information about to the kernel's random number generator
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct KernelRandom {
     pub entropy_available: Option<u64>,
     pub pool_size: Option<u64>,
@@ -50,16 +52,18 @@ impl KernelRandom {
 ///
 /// let krandom = kernel_random::collect().expect("kernel random generator");
 ///
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&krandom).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<KernelRandom> {
-    let krandom_dir = Path::new("/proc/sys/kernel/random");
-    collect_from(krandom_dir)
+    ProcFs::default().kernel_random()
 }
 
-fn collect_from(base_path: &Path) -> CollectResult<KernelRandom> {
+pub(crate) fn collect_from(base_path: &Path) -> CollectResult<KernelRandom> {
     let mut krandom = KernelRandom::new();
 
     for item in utils::list_dir_content(base_path, "", "random") {
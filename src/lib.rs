@@ -1,28 +1,110 @@
-#![doc = include_str!("../README.md")]
+// the readme example reads the watchdog devices and prints them as json
+#![cfg_attr(
+    all(feature = "sysfs-hw", feature = "serde"),
+    doc = include_str!("../README.md")
+)]
+// the internal helpers are shared by the collectors of several features, some
+// of them are unused when only a part of the collectors is enabled
+#![cfg_attr(
+    not(all(
+        feature = "process",
+        feature = "net",
+        feature = "sysfs-storage",
+        feature = "sysfs-power",
+        feature = "sysfs-hw"
+    )),
+    allow(dead_code)
+)]
+
+// logs a message through the log crate, the message is discarded when the
+// `logging` feature is disabled
+#[allow(unused_macros)]
+macro_rules! logging {
+    ($level:ident, $($arg:tt)+) => {{
+        #[cfg(feature = "logging")]
+        ::log::$level!($($arg)+);
+        #[cfg(not(feature = "logging"))]
+        let _ = format_args!($($arg)+);
+    }};
+}
 
 pub mod buddyinfo;
+#[cfg(all(feature = "serde", feature = "regex"))]
+pub mod capture;
 pub mod cmdline;
+#[cfg(feature = "serde")]
+pub mod collector;
 pub mod cpuinfo;
 pub mod crypto;
+#[cfg(feature = "serde")]
+pub mod delta;
+pub mod diskstats;
 pub mod error;
+pub mod filesystem;
 pub mod kernel_random;
 pub mod loadavg;
 pub mod meminfo;
+#[cfg(feature = "net")]
 pub mod net_arp;
+#[cfg(feature = "net")]
 pub mod net_dev;
+#[cfg(feature = "net")]
 pub mod net_protocols;
+#[cfg(feature = "net")]
 pub mod net_sockstat;
+#[cfg(feature = "net")]
 pub mod net_unix;
+#[cfg(feature = "net")]
 pub mod net_wireless;
+#[cfg(feature = "async")]
+pub mod nonblocking;
+pub mod pagetypeinfo;
+pub mod partitions;
+#[cfg(feature = "process")]
 pub mod process;
+#[cfg(feature = "process")]
 pub mod process_cgroup;
+#[cfg(feature = "process")]
+pub mod process_fd;
+#[cfg(feature = "process")]
 pub mod process_io;
+#[cfg(feature = "process")]
 pub mod process_limits;
+#[cfg(feature = "process")]
+pub mod process_maps;
+#[cfg(feature = "process")]
 pub mod process_net_snmp;
+#[cfg(feature = "process")]
 pub mod process_net_snmp6;
+#[cfg(feature = "process")]
 pub mod process_netstat;
+#[cfg(feature = "process")]
 pub mod process_ns;
+#[cfg(feature = "process")]
+pub mod process_schedstat;
+#[cfg(feature = "process")]
+pub mod process_stat;
+#[cfg(feature = "process")]
+pub mod process_status;
+#[cfg(feature = "process")]
+pub mod process_table;
+#[cfg(feature = "process")]
+pub mod process_task;
+#[cfg(feature = "process")]
+pub mod process_tree;
+pub mod procfs;
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
+#[cfg(feature = "serde")]
+pub mod snapshot;
 pub mod softirqs;
+pub mod stat;
 pub mod swaps;
 pub mod sysfs;
+pub mod ttar;
 mod utils;
+pub mod vmstat;
+pub mod zoneinfo;
+
+pub use procfs::ProcFs;
+pub use sysfs::SysFs;
@@ -1,14 +1,17 @@
 use std::path::Path;
 
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::{
     error::{CollectResult, MetricError},
+    procfs::ProcFs,
     utils,
 };
 
 /// LoadAvg represents an entry in /proc/loadavg
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LoadAvg {
     pub load1: f64,
     pub load5: f64,
@@ -33,10 +36,10 @@ impl LoadAvg {
 /// println!("load average 15: {}", sysload.load15);
 /// ```
 pub fn collect() -> CollectResult<LoadAvg> {
-    collect_from("loadavg", Path::new("/proc"))
+    ProcFs::default().loadavg()
 }
 
-fn collect_from(filename: &str, base_path: &Path) -> CollectResult<LoadAvg> {
+pub(crate) fn collect_from(filename: &str, base_path: &Path) -> CollectResult<LoadAvg> {
     let mut sysload = LoadAvg::new();
 
     match utils::collect_info_string(filename, Path::new(base_path))? {
@@ -1,7 +1,11 @@
-use serde::Serialize;
+use std::path::Path;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::{
     error::{CollectResult, MetricError},
+    procfs::ProcFs,
     utils,
 };
 
@@ -132,7 +136,8 @@ impl MeminfoType {
 }
 
 /// Meminfo represents memory statistics (bytes) retrieve from /proc/meminfo
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Meminfo {
     pub mem_total: Option<u64>,
     pub mem_free: Option<u64>,
@@ -205,15 +210,18 @@ impl Meminfo {
 /// use procsys::meminfo;
 ///
 /// let sys_meminfo = meminfo::collect().expect("memory information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&sys_meminfo).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<Meminfo> {
-    collect_from("/proc/meminfo")
+    ProcFs::default().meminfo()
 }
 
-fn collect_from(filename: &str) -> CollectResult<Meminfo> {
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<Meminfo> {
     let mut meminfo = Meminfo::new();
 
     for line in utils::read_file_lines(filename)? {
@@ -312,8 +320,8 @@ mod tests {
 
     #[test]
     fn mem_stats() {
-        let meminfo =
-            collect_from("test_data/fixtures/proc/meminfo").expect("collecting memory information");
+        let meminfo = collect_from(Path::new("test_data/fixtures/proc/meminfo"))
+            .expect("collecting memory information");
 
         assert_eq!(meminfo.mem_total.unwrap(), 16042172416);
         assert_eq!(meminfo.mem_free.unwrap(), 450891776);
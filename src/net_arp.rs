@@ -1,9 +1,11 @@
 use mac_address;
-use serde::Serialize;
-use std::net;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{net, path::Path};
 
 use crate::{
-    error::{CollectResult, MetricError},
+    error::{CollectResult, MetricError, Partial},
+    procfs::ProcFs,
     utils,
 };
 
@@ -16,7 +18,8 @@ const ATF_NETMASK: i32 = 0x20;
 const ATF_DONT_PUBLISH: i32 = 0x40;
 
 /// ARPEntry contains a network device information parsed from /proc/net/arp
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ARPEntry {
     pub ip_address: net::IpAddr,
     pub hw_address: mac_address::MacAddress,
@@ -65,16 +68,29 @@ impl ARPEntry {
 /// use procsys::net_arp;
 ///
 /// let net_arp_entries = net_arp::collect().expect("network arp entries");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&net_arp_entries).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<Vec<ARPEntry>> {
-    collect_from("/proc/net/arp")
+    ProcFs::default().net_arp()
 }
 
-fn collect_from(filename: &str) -> CollectResult<Vec<ARPEntry>> {
-    let mut arp_entries = Vec::new();
+/// collects the ARP entries, a malformed line of /proc/net/arp is reported under its
+/// IP address
+pub fn collect_lenient() -> CollectResult<Partial<Vec<ARPEntry>>> {
+    ProcFs::default().net_arp_lenient()
+}
+
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<Vec<ARPEntry>> {
+    collect_lenient_from(filename)?.into_result()
+}
+
+pub(crate) fn collect_lenient_from(filename: &Path) -> CollectResult<Partial<Vec<ARPEntry>>> {
+    let mut arp_entries: Partial<Vec<ARPEntry>> = Partial::default();
 
     let mut line_index = 0;
     for line in utils::read_file_lines(filename)? {
@@ -84,30 +100,50 @@ fn collect_from(filename: &str) -> CollectResult<Vec<ARPEntry>> {
             continue;
         }
 
-        let fields: Vec<&str> = line.trim().split(' ').filter(|s| !s.is_empty()).collect();
-        let mut arp_entry = ARPEntry::new();
+        match parse_arp_entry(&line) {
+            Ok(arp_entry) => arp_entries.items.push(arp_entry),
+            Err(err) => arp_entries.push_error(
+                &utils::line_item(&line, line_index),
+                filename,
+                err.with_line(filename, line_index),
+            ),
+        }
+    }
+
+    Ok(arp_entries)
+}
 
-        let ip_addr = match fields[0].parse::<net::IpAddr>() {
-            Ok(ip) => ip,
-            Err(err) => return Err(MetricError::ParseError(err.to_string())),
-        };
+fn parse_arp_entry(line: &str) -> CollectResult<ARPEntry> {
+    let fields: Vec<&str> = line.trim().split(' ').filter(|s| !s.is_empty()).collect();
 
-        let mac_addr = match fields[3].parse::<mac_address::MacAddress>() {
-            Ok(mac) => mac,
-            Err(err) => return Err(MetricError::ParseError(err.to_string())),
-        };
+    if fields.len() < 6 {
+        return Err(MetricError::InvalidFieldNumberError(
+            "arp".to_string(),
+            fields.len(),
+            line.to_string(),
+        ));
+    }
 
-        let arp_flag = utils::convert_hex_to_i32(fields[2])?;
+    let mut arp_entry = ARPEntry::new();
 
-        arp_entry.ip_address = ip_addr;
-        arp_entry.hw_address = mac_addr;
-        arp_entry.device = fields[5].to_string();
-        arp_entry.flags = arp_flag;
+    let ip_addr = match fields[0].parse::<net::IpAddr>() {
+        Ok(ip) => ip,
+        Err(err) => return Err(MetricError::ParseError(err.to_string())),
+    };
 
-        arp_entries.push(arp_entry);
-    }
+    let mac_addr = match fields[3].parse::<mac_address::MacAddress>() {
+        Ok(mac) => mac,
+        Err(err) => return Err(MetricError::ParseError(err.to_string())),
+    };
 
-    Ok(arp_entries)
+    let arp_flag = utils::convert_hex_to_i32(fields[2])?;
+
+    arp_entry.ip_address = ip_addr;
+    arp_entry.hw_address = mac_addr;
+    arp_entry.device = fields[5].to_string();
+    arp_entry.flags = arp_flag;
+
+    Ok(arp_entry)
 }
 
 #[cfg(test)]
@@ -116,8 +152,8 @@ mod tests {
 
     #[test]
     fn arp_entries() {
-        let arp_entries =
-            collect_from("test_data/fixtures/proc/net/arp").expect("arp entries information");
+        let arp_entries = collect_from(Path::new("test_data/fixtures/proc/net/arp"))
+            .expect("arp entries information");
         for arp_entry in arp_entries {
             match arp_entry.hw_address.to_string().as_str() {
                 "00:50:56:C0:00:08" => {
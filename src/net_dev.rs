@@ -1,12 +1,17 @@
-use serde::Serialize;
+use std::path::Path;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    error::{CollectResult, MetricError},
+    error::{CollectResult, MetricError, Partial},
+    procfs::ProcFs,
     utils,
 };
 
 /// NetDev contains a network device information parsed from /proc/net/dev
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NetDev {
     pub name: String,
     pub rx_bytes: u64,
@@ -39,16 +44,29 @@ impl NetDev {
 /// use procsys::net_dev;
 ///
 /// let net_devices = net_dev::collect().expect("network devices");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&net_devices).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<Vec<NetDev>> {
-    collect_from("/proc/net/dev")
+    ProcFs::default().net_dev()
+}
+
+/// collects the network devices, a malformed interface line is reported under its
+/// interface name
+pub fn collect_lenient() -> CollectResult<Partial<Vec<NetDev>>> {
+    ProcFs::default().net_dev_lenient()
+}
+
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<Vec<NetDev>> {
+    collect_lenient_from(filename)?.into_result()
 }
 
-fn collect_from(filename: &str) -> CollectResult<Vec<NetDev>> {
-    let mut net_devices = Vec::new();
+pub(crate) fn collect_lenient_from(filename: &Path) -> CollectResult<Partial<Vec<NetDev>>> {
+    let mut net_devices: Partial<Vec<NetDev>> = Partial::default();
 
     let mut line_index = 0;
 
@@ -59,51 +77,89 @@ fn collect_from(filename: &str) -> CollectResult<Vec<NetDev>> {
             continue;
         }
 
-        let fields: Vec<&str> = line.trim().split(' ').filter(|s| !s.is_empty()).collect();
-
-        if fields.len() != 17 {
-            return Err(MetricError::InvalidFieldNumberError(
-                "network".to_string(),
-                fields.len(),
-                line,
-            ));
+        match parse_net_dev(&line) {
+            Ok(net_device) => net_devices.items.push(net_device),
+            Err(err) => net_devices.push_error(
+                &utils::line_item(&line, line_index),
+                filename,
+                err.with_line(filename, line_index),
+            ),
         }
+    }
 
-        let mut net_device = NetDev::new();
-        net_device.name = fields[0].trim_matches(':').to_string();
-        net_device.rx_bytes = fields[1].parse::<u64>().unwrap_or_default();
-        net_device.rx_packets = fields[2].parse::<u64>().unwrap_or_default();
-        net_device.rx_errors = fields[3].parse::<u64>().unwrap_or_default();
-        net_device.rx_dropped = fields[4].parse::<u64>().unwrap_or_default();
-        net_device.rx_fifo = fields[5].parse::<u64>().unwrap_or_default();
-        net_device.rx_frame = fields[6].parse::<u64>().unwrap_or_default();
-        net_device.rx_compressed = fields[7].parse::<u64>().unwrap_or_default();
-        net_device.rx_multicast = fields[8].parse::<u64>().unwrap_or_default();
-        net_device.tx_bytes = fields[9].parse::<u64>().unwrap_or_default();
-        net_device.tx_packets = fields[10].parse::<u64>().unwrap_or_default();
-        net_device.tx_errors = fields[11].parse::<u64>().unwrap_or_default();
-        net_device.tx_dropped = fields[12].parse::<u64>().unwrap_or_default();
-        net_device.tx_fifo = fields[13].parse::<u64>().unwrap_or_default();
-        net_device.tx_collisions = fields[14].parse::<u64>().unwrap_or_default();
-        net_device.tx_carrier = fields[15].parse::<u64>().unwrap_or_default();
-        net_device.tx_compressed = fields[16].parse::<u64>().unwrap_or_default();
-
-        net_devices.push(net_device);
+    Ok(net_devices)
+}
 
-        line_index += 1;
+fn parse_net_dev(line: &str) -> CollectResult<NetDev> {
+    let fields: Vec<&str> = line.trim().split(' ').filter(|s| !s.is_empty()).collect();
+
+    if fields.len() != 17 {
+        return Err(MetricError::InvalidFieldNumberError(
+            "network".to_string(),
+            fields.len(),
+            line.to_string(),
+        ));
     }
 
-    Ok(net_devices)
+    let mut net_device = NetDev::new();
+    net_device.name = fields[0].trim_matches(':').to_string();
+    net_device.rx_bytes = fields[1].parse::<u64>().unwrap_or_default();
+    net_device.rx_packets = fields[2].parse::<u64>().unwrap_or_default();
+    net_device.rx_errors = fields[3].parse::<u64>().unwrap_or_default();
+    net_device.rx_dropped = fields[4].parse::<u64>().unwrap_or_default();
+    net_device.rx_fifo = fields[5].parse::<u64>().unwrap_or_default();
+    net_device.rx_frame = fields[6].parse::<u64>().unwrap_or_default();
+    net_device.rx_compressed = fields[7].parse::<u64>().unwrap_or_default();
+    net_device.rx_multicast = fields[8].parse::<u64>().unwrap_or_default();
+    net_device.tx_bytes = fields[9].parse::<u64>().unwrap_or_default();
+    net_device.tx_packets = fields[10].parse::<u64>().unwrap_or_default();
+    net_device.tx_errors = fields[11].parse::<u64>().unwrap_or_default();
+    net_device.tx_dropped = fields[12].parse::<u64>().unwrap_or_default();
+    net_device.tx_fifo = fields[13].parse::<u64>().unwrap_or_default();
+    net_device.tx_collisions = fields[14].parse::<u64>().unwrap_or_default();
+    net_device.tx_carrier = fields[15].parse::<u64>().unwrap_or_default();
+    net_device.tx_compressed = fields[16].parse::<u64>().unwrap_or_default();
+
+    Ok(net_device)
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use crate::filesystem::MemoryFileSystem;
+
     use super::*;
 
+    #[test]
+    fn net_devices_lenient() {
+        let mut memfs = MemoryFileSystem::new();
+        memfs.insert_file(
+            "proc/net/dev",
+            "Inter-|   Receive                                                |  Transmit\n \
+             face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed\n\
+             lo: 1664039048 1566805 0 0 0 0 0 0 1664039048 1566805 0 0 0 0 0 0\n\
+             eth0: 874354587 1036395 0 0\n",
+        );
+        let procfs = ProcFs::with_filesystem("/proc", Arc::new(memfs));
+
+        let partial = procfs
+            .net_dev_lenient()
+            .expect("collecting network devices");
+        assert_eq!(partial.items.len(), 1);
+        assert_eq!(partial.items[0].name, "lo");
+        assert_eq!(partial.errors.len(), 1);
+        assert_eq!(partial.errors[0].item, "eth0");
+        assert_eq!(partial.errors[0].path, Path::new("/proc/net/dev"));
+        assert!(!partial.is_complete());
+
+        assert!(procfs.net_dev().is_err());
+    }
+
     #[test]
     fn net_devices() {
-        let ndevices =
-            collect_from("test_data/fixtures/proc/net/dev").expect("collecting network devices");
+        let ndevices = collect_from(Path::new("test_data/fixtures/proc/net/dev"))
+            .expect("collecting network devices");
 
         for net_dev in ndevices {
             match net_dev.name.as_str() {
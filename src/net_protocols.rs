@@ -1,12 +1,17 @@
-use serde::Serialize;
+use std::path::Path;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    error::{CollectResult, MetricError},
+    error::{CollectResult, MetricError, Partial},
+    procfs::ProcFs,
     utils,
 };
 
 /// NetProtocol represents a single line parsed from /proc/net/protocols
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NetProtocol {
     pub name: String,
     pub size: u64,
@@ -20,7 +25,8 @@ pub struct NetProtocol {
 }
 
 /// NetProtocolCapabilities contains a list of capabilities for each protocol
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NetProtocolCapabilities {
     pub close: bool,
     pub connect: bool,
@@ -62,96 +68,111 @@ impl NetProtocolCapabilities {
 /// use procsys::net_protocols;
 ///
 /// let netprots = net_protocols::collect().expect("network protocols");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&netprots).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<Vec<NetProtocol>> {
-    collect_from("/proc/net/protocols")
+    ProcFs::default().net_protocols()
 }
 
-fn collect_from(filename: &str) -> CollectResult<Vec<NetProtocol>> {
-    let mut netprotos: Vec<NetProtocol> = Vec::new();
+/// collects the network protocols, a malformed line is reported under its protocol name
+pub fn collect_lenient() -> CollectResult<Partial<Vec<NetProtocol>>> {
+    ProcFs::default().net_protocols_lenient()
+}
 
-    let netprotos_info: Vec<String> = utils::read_file_lines(filename)?;
-    let header: Vec<&str> = netprotos_info[0]
-        .trim()
-        .split(' ')
-        .filter(|s| !s.is_empty())
-        .collect();
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<Vec<NetProtocol>> {
+    collect_lenient_from(filename)?.into_result()
+}
 
-    let sp_included = header[20] == "sp";
+pub(crate) fn collect_lenient_from(filename: &Path) -> CollectResult<Partial<Vec<NetProtocol>>> {
+    let mut netprotos: Partial<Vec<NetProtocol>> = Partial::default();
 
-    for line in &netprotos_info[1..] {
-        let net_info = line.to_owned();
-        let net_info_fields: Vec<&str> = net_info
-            .trim()
-            .split(' ')
-            .filter(|s| !s.is_empty())
-            .collect();
+    let netprotos_info: Vec<String> = utils::read_file_lines(filename)?;
+    let header: Vec<&str> = match netprotos_info.first() {
+        Some(header) => header.trim().split(' ').filter(|s| !s.is_empty()).collect(),
+        None => return Ok(netprotos),
+    };
+
+    let sp_included = header.get(20) == Some(&"sp");
 
-        if (sp_included && net_info_fields.len() < 27)
-            || (!sp_included && net_info_fields.len() < 26)
-        {
-            return Err(MetricError::InvalidFieldNumberError(
-                "net protocols".to_string(),
-                net_info_fields.len(),
-                line.to_owned(),
-            ));
+    for (line_index, line) in netprotos_info.iter().enumerate().skip(1) {
+        match parse_net_protocol(line, sp_included) {
+            Ok(net_proto) => netprotos.items.push(net_proto),
+            Err(err) => netprotos.push_error(
+                &utils::line_item(line, line_index + 1),
+                filename,
+                err.with_line(filename, line_index + 1),
+            ),
         }
+    }
+
+    Ok(netprotos)
+}
 
-        let mut net_proto = NetProtocol::new();
-        net_proto.capabilities = NetProtocolCapabilities::new();
+fn parse_net_protocol(line: &str, sp_included: bool) -> CollectResult<NetProtocol> {
+    let net_info_fields: Vec<&str> = line.trim().split(' ').filter(|s| !s.is_empty()).collect();
 
-        net_proto.name = net_info_fields[0].to_string();
-        net_proto.size = net_info_fields[1].parse::<u64>().unwrap_or_default();
-        net_proto.sockets = net_info_fields[2].parse::<i64>().unwrap_or_default();
-        net_proto.memory = net_info_fields[3].parse::<i64>().unwrap_or_default();
+    if (sp_included && net_info_fields.len() < 27) || (!sp_included && net_info_fields.len() < 26) {
+        return Err(MetricError::InvalidFieldNumberError(
+            "net protocols".to_string(),
+            net_info_fields.len(),
+            line.to_owned(),
+        ));
+    }
 
-        if net_info_fields[4] != "NI" {
-            net_proto.pressure = Some(net_info_fields[4] == "yes")
-        }
+    let mut net_proto = NetProtocol::new();
+    net_proto.capabilities = NetProtocolCapabilities::new();
 
-        net_proto.max_header = net_info_fields[5].parse::<u64>().unwrap_or_default();
-        net_proto.slab = net_info_fields[6] == "yes";
-        net_proto.module_name = net_info_fields[7].to_string();
+    net_proto.name = net_info_fields[0].to_string();
+    net_proto.size = net_info_fields[1].parse::<u64>().unwrap_or_default();
+    net_proto.sockets = net_info_fields[2].parse::<i64>().unwrap_or_default();
+    net_proto.memory = net_info_fields[3].parse::<i64>().unwrap_or_default();
 
-        // capabilities
-        let default_y = "y";
-        net_proto.capabilities.close = net_info_fields[8] == default_y;
-        net_proto.capabilities.connect = net_info_fields[9] == default_y;
-        net_proto.capabilities.disconnect = net_info_fields[10] == default_y;
-        net_proto.capabilities.accept = net_info_fields[11] == default_y;
-        net_proto.capabilities.ioctl = net_info_fields[12] == default_y;
-        net_proto.capabilities.init = net_info_fields[13] == default_y;
-        net_proto.capabilities.destroy = net_info_fields[14] == default_y;
-        net_proto.capabilities.shutdown = net_info_fields[15] == default_y;
-        net_proto.capabilities.set_socketopt = net_info_fields[16] == default_y;
-        net_proto.capabilities.get_socketopt = net_info_fields[17] == default_y;
-        net_proto.capabilities.send_msg = net_info_fields[18] == default_y;
-        net_proto.capabilities.recv_msg = net_info_fields[19] == default_y;
+    if net_info_fields[4] != "NI" {
+        net_proto.pressure = Some(net_info_fields[4] == "yes")
+    }
 
-        if sp_included {
-            net_proto.capabilities.send_page = Some(net_info_fields[20] == default_y);
-            net_proto.capabilities.bind = net_info_fields[21] == default_y;
-            net_proto.capabilities.backlog_rcv = net_info_fields[22] == default_y;
-            net_proto.capabilities.hash = net_info_fields[23] == default_y;
-            net_proto.capabilities.unhash = net_info_fields[24] == default_y;
-            net_proto.capabilities.get_port = net_info_fields[25] == default_y;
-            net_proto.capabilities.entry_memory_pressure = net_info_fields[26] == default_y;
-        } else {
-            net_proto.capabilities.bind = net_info_fields[20] == default_y;
-            net_proto.capabilities.backlog_rcv = net_info_fields[21] == default_y;
-            net_proto.capabilities.hash = net_info_fields[22] == default_y;
-            net_proto.capabilities.unhash = net_info_fields[23] == default_y;
-            net_proto.capabilities.get_port = net_info_fields[24] == default_y;
-            net_proto.capabilities.entry_memory_pressure = net_info_fields[25] == default_y;
-        }
+    net_proto.max_header = net_info_fields[5].parse::<u64>().unwrap_or_default();
+    net_proto.slab = net_info_fields[6] == "yes";
+    net_proto.module_name = net_info_fields[7].to_string();
 
-        netprotos.push(net_proto);
+    // capabilities
+    let default_y = "y";
+    net_proto.capabilities.close = net_info_fields[8] == default_y;
+    net_proto.capabilities.connect = net_info_fields[9] == default_y;
+    net_proto.capabilities.disconnect = net_info_fields[10] == default_y;
+    net_proto.capabilities.accept = net_info_fields[11] == default_y;
+    net_proto.capabilities.ioctl = net_info_fields[12] == default_y;
+    net_proto.capabilities.init = net_info_fields[13] == default_y;
+    net_proto.capabilities.destroy = net_info_fields[14] == default_y;
+    net_proto.capabilities.shutdown = net_info_fields[15] == default_y;
+    net_proto.capabilities.set_socketopt = net_info_fields[16] == default_y;
+    net_proto.capabilities.get_socketopt = net_info_fields[17] == default_y;
+    net_proto.capabilities.send_msg = net_info_fields[18] == default_y;
+    net_proto.capabilities.recv_msg = net_info_fields[19] == default_y;
+
+    if sp_included {
+        net_proto.capabilities.send_page = Some(net_info_fields[20] == default_y);
+        net_proto.capabilities.bind = net_info_fields[21] == default_y;
+        net_proto.capabilities.backlog_rcv = net_info_fields[22] == default_y;
+        net_proto.capabilities.hash = net_info_fields[23] == default_y;
+        net_proto.capabilities.unhash = net_info_fields[24] == default_y;
+        net_proto.capabilities.get_port = net_info_fields[25] == default_y;
+        net_proto.capabilities.entry_memory_pressure = net_info_fields[26] == default_y;
+    } else {
+        net_proto.capabilities.bind = net_info_fields[20] == default_y;
+        net_proto.capabilities.backlog_rcv = net_info_fields[21] == default_y;
+        net_proto.capabilities.hash = net_info_fields[22] == default_y;
+        net_proto.capabilities.unhash = net_info_fields[23] == default_y;
+        net_proto.capabilities.get_port = net_info_fields[24] == default_y;
+        net_proto.capabilities.entry_memory_pressure = net_info_fields[25] == default_y;
     }
 
-    Ok(netprotos)
+    Ok(net_proto)
 }
 
 #[cfg(test)]
@@ -160,7 +181,7 @@ mod tests {
 
     #[test]
     fn net_protocols() {
-        let netprotos = collect_from("test_data/fixtures/proc/net/protocols")
+        let netprotos = collect_from(Path::new("test_data/fixtures/proc/net/protocols"))
             .expect("collecting network protocols");
 
         assert_eq!(netprotos.len(), 4);
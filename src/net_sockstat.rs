@@ -1,15 +1,20 @@
-use serde::Serialize;
+use std::path::Path;
 
-use crate::{error::CollectResult, utils};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{error::CollectResult, procfs::ProcFs, utils};
 
 /// ARPEntry contains a network device information parsed from /proc/net/arp
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NetSockStat {
     pub used: Option<isize>,
     pub protocols: Vec<NetSockStatProtocol>,
 }
 
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NetSockStatProtocol {
     pub protocol: String,
     pub inuse: isize,
@@ -38,22 +43,28 @@ impl NetSockStatProtocol {
 /// use procsys::net_sockstat;
 ///
 /// let net_sockstat_info = net_sockstat::collect().expect("network sockstat information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&net_sockstat_info).unwrap();
 /// println!("sockstat:\n{}", json_output);
+/// # }
 ///
 /// let net_sockstat6_info = net_sockstat::collect6().expect("network sockstat6 information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&net_sockstat6_info).unwrap();
 /// println!("sockstat6:\n{}", json_output);
+/// # }
 /// ```
 pub fn collect() -> CollectResult<NetSockStat> {
-    collect_from("/proc/net/sockstat")
+    ProcFs::default().net_sockstat()
 }
 
 pub fn collect6() -> CollectResult<NetSockStat> {
-    collect_from("/proc/net/sockstat6")
+    ProcFs::default().net_sockstat6()
 }
 
-fn collect_from(filename: &str) -> CollectResult<NetSockStat> {
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<NetSockStat> {
     let mut net_sockstat_info = NetSockStat::new();
 
     for line in utils::read_file_lines(filename)? {
@@ -134,7 +145,7 @@ mod tests {
 
     #[test]
     fn sockstat_info() {
-        let sockstat = collect_from("test_data/fixtures/proc/net/sockstat")
+        let sockstat = collect_from(Path::new("test_data/fixtures/proc/net/sockstat"))
             .expect("sockstat entries information");
 
         assert_eq!(sockstat.used, Some(1602));
@@ -185,7 +196,7 @@ mod tests {
             }
         }
 
-        let sockstat6 = collect_from("test_data/fixtures/proc/net/sockstat6")
+        let sockstat6 = collect_from(Path::new("test_data/fixtures/proc/net/sockstat6"))
             .expect("sockstat entries information");
 
         assert_eq!(sockstat6.used, None);
@@ -1,12 +1,17 @@
-use serde::Serialize;
+use std::path::Path;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    error::{CollectResult, MetricError},
+    error::{CollectResult, MetricError, Partial},
+    procfs::ProcFs,
     utils,
 };
 
 /// NetUnix represents a line of /proc/net/unix
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NetUnix {
     pub kernel_ptr: String,
     pub ref_count: u64,
@@ -29,16 +34,29 @@ impl NetUnix {
 /// use procsys::net_unix;
 ///
 /// let netunix = net_unix::collect().expect("network unix information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&netunix).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<Vec<NetUnix>> {
-    collect_from("/proc/net/unix")
+    ProcFs::default().net_unix()
+}
+
+/// collects the unix domain sockets, a malformed socket line is reported under its
+/// kernel address
+pub fn collect_lenient() -> CollectResult<Partial<Vec<NetUnix>>> {
+    ProcFs::default().net_unix_lenient()
 }
 
-fn collect_from(filename: &str) -> CollectResult<Vec<NetUnix>> {
-    let mut all_net_unix: Vec<NetUnix> = Vec::new();
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<Vec<NetUnix>> {
+    collect_lenient_from(filename)?.into_result()
+}
+
+pub(crate) fn collect_lenient_from(filename: &Path) -> CollectResult<Partial<Vec<NetUnix>>> {
+    let mut all_net_unix: Partial<Vec<NetUnix>> = Partial::default();
 
     let mut line_index = 0;
     for line in utils::read_file_lines(filename)? {
@@ -48,32 +66,43 @@ fn collect_from(filename: &str) -> CollectResult<Vec<NetUnix>> {
             continue;
         }
 
-        let fields: Vec<&str> = line.trim().split(' ').filter(|s| !s.is_empty()).collect();
-
-        if fields.len() < 7 {
-            return Err(MetricError::InvalidFieldNumberError(
-                "net unix".to_string(),
-                fields.len(),
-                line,
-            ));
+        match parse_net_unix(&line) {
+            Ok(net_unix) => all_net_unix.items.push(net_unix),
+            Err(err) => all_net_unix.push_error(
+                &utils::line_item(&line, line_index),
+                filename,
+                err.with_line(filename, line_index),
+            ),
         }
+    }
 
-        let mut net_unix = NetUnix::new();
-        net_unix.kernel_ptr = fields[0].trim_matches(':').to_string();
-        net_unix.ref_count = u64::from_str_radix(fields[1], 16).unwrap_or_default();
-        net_unix.flags = u64::from_str_radix(fields[3], 16).unwrap_or_default();
-        net_unix.ntype = u64::from_str_radix(fields[4], 16).unwrap_or_default();
-        net_unix.state = u64::from_str_radix(fields[5], 16).unwrap_or_default();
-        net_unix.inode = fields[6].parse::<u64>().unwrap_or_default();
+    Ok(all_net_unix)
+}
 
-        if fields.len() > 7 && !fields[7].is_empty() {
-            net_unix.path = Some(fields[7].to_string())
-        }
+fn parse_net_unix(line: &str) -> CollectResult<NetUnix> {
+    let fields: Vec<&str> = line.trim().split(' ').filter(|s| !s.is_empty()).collect();
 
-        all_net_unix.push(net_unix);
+    if fields.len() < 7 {
+        return Err(MetricError::InvalidFieldNumberError(
+            "net unix".to_string(),
+            fields.len(),
+            line.to_string(),
+        ));
     }
 
-    Ok(all_net_unix)
+    let mut net_unix = NetUnix::new();
+    net_unix.kernel_ptr = fields[0].trim_matches(':').to_string();
+    net_unix.ref_count = u64::from_str_radix(fields[1], 16).unwrap_or_default();
+    net_unix.flags = u64::from_str_radix(fields[3], 16).unwrap_or_default();
+    net_unix.ntype = u64::from_str_radix(fields[4], 16).unwrap_or_default();
+    net_unix.state = u64::from_str_radix(fields[5], 16).unwrap_or_default();
+    net_unix.inode = fields[6].parse::<u64>().unwrap_or_default();
+
+    if fields.len() > 7 && !fields[7].is_empty() {
+        net_unix.path = Some(fields[7].to_string())
+    }
+
+    Ok(net_unix)
 }
 
 #[cfg(test)]
@@ -82,7 +111,7 @@ mod tests {
 
     #[test]
     fn net_unix() {
-        let all_net_unix = collect_from("test_data/fixtures/proc/net/unix")
+        let all_net_unix = collect_from(Path::new("test_data/fixtures/proc/net/unix"))
             .expect("collecting network unix information");
 
         assert_eq!(all_net_unix.len(), 5);
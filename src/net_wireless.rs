@@ -1,14 +1,18 @@
 use std::path::Path;
 
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    error::{CollectResult, MetricError},
+    error::{CollectResult, MetricError, Partial},
+    filesystem,
+    procfs::ProcFs,
     utils,
 };
 
 /// Wireless models the content of /proc/net/wireless
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Wireless {
     pub name: String,
 
@@ -55,20 +59,33 @@ impl Wireless {
 /// use procsys::net_wireless;
 ///
 /// let netwireless = net_wireless::collect().expect("network wireless information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&netwireless).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<Vec<Wireless>> {
-    collect_from("/proc/net/wireless")
+    ProcFs::default().net_wireless()
 }
 
-fn collect_from(filename: &str) -> CollectResult<Vec<Wireless>> {
-    let mut netwireless: Vec<Wireless> = Vec::new();
+/// collects the wireless interfaces, a malformed interface line is reported under its
+/// interface name
+pub fn collect_lenient() -> CollectResult<Partial<Vec<Wireless>>> {
+    ProcFs::default().net_wireless_lenient()
+}
+
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<Vec<Wireless>> {
+    collect_lenient_from(filename)?.into_result()
+}
+
+pub(crate) fn collect_lenient_from(filename: &Path) -> CollectResult<Partial<Vec<Wireless>>> {
+    let mut netwireless: Partial<Vec<Wireless>> = Partial::default();
 
     let mut line_index = 0;
 
-    if !Path::new(filename).exists() {
+    if !filesystem::exists(filename) {
         return Ok(netwireless);
     }
 
@@ -79,39 +96,50 @@ fn collect_from(filename: &str) -> CollectResult<Vec<Wireless>> {
             continue;
         }
 
-        let fields: Vec<&str> = line.trim().split(' ').filter(|s| !s.is_empty()).collect();
-
-        if fields.len() < 11 {
-            return Err(MetricError::InvalidFieldNumberError(
-                "wireless".to_string(),
-                fields.len(),
-                line,
-            ));
+        match parse_wireless(&line) {
+            Ok(wireless) => netwireless.items.push(wireless),
+            Err(err) => netwireless.push_error(
+                &utils::line_item(&line, line_index),
+                filename,
+                err.with_line(filename, line_index),
+            ),
         }
+    }
 
-        let mut wireless = Wireless::new();
-        wireless.name = fields[0].trim_matches(':').to_string();
-        wireless.status = u64::from_str_radix(fields[1], 16).unwrap_or_default();
+    Ok(netwireless)
+}
 
-        let quality_link = fields[2].trim_end_matches(".");
-        wireless.quality_link = quality_link.parse::<i64>().unwrap_or_default();
+fn parse_wireless(line: &str) -> CollectResult<Wireless> {
+    let fields: Vec<&str> = line.trim().split(' ').filter(|s| !s.is_empty()).collect();
 
-        let quality_level = fields[3].trim_end_matches(".");
-        wireless.quality_level = quality_level.parse::<i64>().unwrap_or_default();
+    if fields.len() < 11 {
+        return Err(MetricError::InvalidFieldNumberError(
+            "wireless".to_string(),
+            fields.len(),
+            line.to_string(),
+        ));
+    }
 
-        let quality_noise = fields[4].trim_end_matches(".");
-        wireless.quality_noise = quality_noise.parse::<i64>().unwrap_or_default();
-        wireless.discarded_nwid = fields[5].parse::<i64>().unwrap_or_default();
-        wireless.discarded_crypt = fields[6].parse::<i64>().unwrap_or_default();
-        wireless.discarded_frag = fields[7].parse::<i64>().unwrap_or_default();
-        wireless.discarded_retry = fields[8].parse::<i64>().unwrap_or_default();
-        wireless.discarded_misc = fields[9].parse::<i64>().unwrap_or_default();
-        wireless.missed_beacon = fields[10].parse::<i64>().unwrap_or_default();
+    let mut wireless = Wireless::new();
+    wireless.name = fields[0].trim_matches(':').to_string();
+    wireless.status = u64::from_str_radix(fields[1], 16).unwrap_or_default();
 
-        netwireless.push(wireless);
-    }
+    let quality_link = fields[2].trim_end_matches(".");
+    wireless.quality_link = quality_link.parse::<i64>().unwrap_or_default();
 
-    Ok(netwireless)
+    let quality_level = fields[3].trim_end_matches(".");
+    wireless.quality_level = quality_level.parse::<i64>().unwrap_or_default();
+
+    let quality_noise = fields[4].trim_end_matches(".");
+    wireless.quality_noise = quality_noise.parse::<i64>().unwrap_or_default();
+    wireless.discarded_nwid = fields[5].parse::<i64>().unwrap_or_default();
+    wireless.discarded_crypt = fields[6].parse::<i64>().unwrap_or_default();
+    wireless.discarded_frag = fields[7].parse::<i64>().unwrap_or_default();
+    wireless.discarded_retry = fields[8].parse::<i64>().unwrap_or_default();
+    wireless.discarded_misc = fields[9].parse::<i64>().unwrap_or_default();
+    wireless.missed_beacon = fields[10].parse::<i64>().unwrap_or_default();
+
+    Ok(wireless)
 }
 
 #[cfg(test)]
@@ -120,7 +148,7 @@ mod tests {
 
     #[test]
     fn net_wireless() {
-        let netwireless = collect_from("test_data/fixtures/proc/net/wireless")
+        let netwireless = collect_from(Path::new("test_data/fixtures/proc/net/wireless"))
             .expect("collecting network wireless information");
 
         assert_eq!(netwireless.len(), 2);
@@ -0,0 +1,538 @@
+//! Async variants of the collectors for tokio based applications.
+//!
+//! The collectors read small pseudo files with blocking IO, they run on the
+//! blocking thread pool of the tokio runtime so the async tasks are never
+//! stalled. A semaphore bounds the number of collectors reading at the same
+//! time, which keeps large process scans from flooding the blocking pool.
+//!
+//! # Example
+//! ```
+//! use procsys::{nonblocking::AsyncProcFs, ProcFs};
+//!
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async {
+//! let procfs = AsyncProcFs::with_concurrency(ProcFs::default(), 16);
+//!
+//! let sys_meminfo = procfs.meminfo().await.expect("memory information");
+//! println!("mem_total: {:?}", sys_meminfo.mem_total);
+//!
+//! // reads the status of every process, at most 16 at a time
+//! # #[cfg(feature = "process")]
+//! for (pid, status) in procfs.scan(|proc| proc.status()).await.expect("processes") {
+//!     if let Ok(status) = status {
+//!         println!("{}: {}", pid, status.name);
+//!     }
+//! }
+//! # });
+//! ```
+
+#[cfg(any(
+    feature = "process",
+    feature = "sysfs-storage",
+    feature = "sysfs-power",
+    feature = "sysfs-hw"
+))]
+use std::collections::HashMap;
+#[cfg(feature = "process")]
+use std::path::PathBuf;
+use std::{panic, sync::Arc};
+
+#[cfg(feature = "process")]
+use tokio::task::JoinSet;
+use tokio::{sync::Semaphore, task};
+
+use crate::{
+    buddyinfo::BuddyInfo,
+    cpuinfo::CpuInfo,
+    crypto::Crypto,
+    diskstats::DiskStats,
+    error::{CollectResult, MetricError, Partial},
+    kernel_random::KernelRandom,
+    loadavg::LoadAvg,
+    meminfo::Meminfo,
+    pagetypeinfo::PageTypeInfo,
+    partitions::Partition,
+    procfs::ProcFs,
+    softirqs::Softirqs,
+    stat::Stat,
+    swaps::Swaps,
+    sysfs::SysFs,
+    vmstat::Vmstat,
+    zoneinfo::ZoneInfo,
+};
+
+#[cfg(feature = "net")]
+use crate::{
+    net_arp::ARPEntry, net_dev::NetDev, net_protocols::NetProtocol, net_sockstat::NetSockStat,
+    net_unix::NetUnix, net_wireless::Wireless,
+};
+
+#[cfg(feature = "process")]
+use crate::{
+    process::Process,
+    process_cgroup::ProcessCgroup,
+    process_fd::{ProcessFd, ProcessFdInfo},
+    process_io::ProcessIO,
+    process_limits::ProcessLimits,
+    process_maps::{ProcessMap, ProcessSmap},
+    process_net_snmp::ProcessNetSnmp,
+    process_net_snmp6::ProcessNetSnmp6,
+    process_netstat::ProcessNetstat,
+    process_ns::ProcessNamespace,
+    process_schedstat::ProcessSchedstat,
+    process_stat::ProcessStat,
+    process_status::ProcessStatus,
+    process_tree::ProcessTree,
+};
+
+#[cfg(feature = "sysfs-storage")]
+use crate::sysfs::{
+    block, block_topology, class_fibrechannel, class_nvme, class_sas_device, class_sas_host,
+    class_sas_phy, class_sas_port, class_scsi_tape,
+};
+
+#[cfg(feature = "sysfs-power")]
+use crate::sysfs::{class_cooling, class_power_supply, class_thermal};
+
+#[cfg(feature = "sysfs-hw")]
+use crate::sysfs::{class_dmi, class_infiniband, class_watchdog, clocksource};
+
+/// default number of collectors which read at the same time
+pub const DEFAULT_CONCURRENCY: usize = 32;
+
+// runs a blocking collector on the blocking pool once a permit is available
+async fn run_blocking<T, F>(limiter: &Semaphore, f: F) -> CollectResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> CollectResult<T> + Send + 'static,
+{
+    let _permit = limiter
+        .acquire()
+        .await
+        .map_err(|err| MetricError::TaskError(err.to_string()))?;
+
+    match task::spawn_blocking(f).await {
+        Ok(result) => result,
+        Err(err) if err.is_panic() => panic::resume_unwind(err.into_panic()),
+        Err(err) => Err(MetricError::TaskError(err.to_string())),
+    }
+}
+
+fn new_limiter(limit: usize) -> Arc<Semaphore> {
+    Arc::new(Semaphore::new(limit.max(1)))
+}
+
+// generates the async variant of the listed collector methods
+macro_rules! async_collectors {
+    ($fs:ident, $($(#[$attr:meta])* $name:ident -> $ty:ty),* $(,)?) => {
+        $(
+            $(#[$attr])*
+            #[doc = concat!("async variant of [`", stringify!($fs), "::", stringify!($name), "`]")]
+            pub async fn $name(&self) -> CollectResult<$ty> {
+                self.run(|fs| fs.$name()).await
+            }
+        )*
+    };
+}
+
+/// AsyncProcFs runs the collectors of a [`ProcFs`] on the blocking pool of the tokio runtime
+#[derive(Debug, Clone)]
+pub struct AsyncProcFs {
+    procfs: ProcFs,
+    limiter: Arc<Semaphore>,
+}
+
+impl Default for AsyncProcFs {
+    fn default() -> Self {
+        Self::new(ProcFs::default())
+    }
+}
+
+impl AsyncProcFs {
+    /// returns a new handle running at most [`DEFAULT_CONCURRENCY`] collectors at the same time
+    pub fn new(procfs: ProcFs) -> Self {
+        Self::with_concurrency(procfs, DEFAULT_CONCURRENCY)
+    }
+
+    /// returns a new handle running at most `limit` collectors at the same time,
+    /// the processes returned by the handle share the same limit
+    pub fn with_concurrency(procfs: ProcFs, limit: usize) -> Self {
+        Self {
+            procfs,
+            limiter: new_limiter(limit),
+        }
+    }
+
+    /// returns the blocking handle
+    pub fn procfs(&self) -> &ProcFs {
+        &self.procfs
+    }
+
+    /// runs a blocking function of the procfs handle on the blocking pool
+    pub async fn run<T, F>(&self, f: F) -> CollectResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&ProcFs) -> CollectResult<T> + Send + 'static,
+    {
+        let procfs = self.procfs.clone();
+        run_blocking(&self.limiter, move || f(&procfs)).await
+    }
+
+    async_collectors!(
+        ProcFs,
+        buddyinfo -> Vec<BuddyInfo>,
+        buddyinfo_lenient -> Partial<Vec<BuddyInfo>>,
+        cmdline -> Vec<String>,
+        cpuinfo -> Vec<CpuInfo>,
+        crypto -> Vec<Crypto>,
+        diskstats -> Vec<DiskStats>,
+        diskstats_lenient -> Partial<Vec<DiskStats>>,
+        kernel_random -> KernelRandom,
+        loadavg -> LoadAvg,
+        meminfo -> Meminfo,
+        #[cfg(feature = "net")]
+        net_arp -> Vec<ARPEntry>,
+        #[cfg(feature = "net")]
+        net_arp_lenient -> Partial<Vec<ARPEntry>>,
+        #[cfg(feature = "net")]
+        net_dev -> Vec<NetDev>,
+        #[cfg(feature = "net")]
+        net_dev_lenient -> Partial<Vec<NetDev>>,
+        #[cfg(feature = "net")]
+        net_protocols -> Vec<NetProtocol>,
+        #[cfg(feature = "net")]
+        net_protocols_lenient -> Partial<Vec<NetProtocol>>,
+        #[cfg(feature = "net")]
+        net_sockstat -> NetSockStat,
+        #[cfg(feature = "net")]
+        net_sockstat6 -> NetSockStat,
+        #[cfg(feature = "net")]
+        net_unix -> Vec<NetUnix>,
+        #[cfg(feature = "net")]
+        net_unix_lenient -> Partial<Vec<NetUnix>>,
+        #[cfg(feature = "net")]
+        net_wireless -> Vec<Wireless>,
+        #[cfg(feature = "net")]
+        net_wireless_lenient -> Partial<Vec<Wireless>>,
+        pagetypeinfo -> PageTypeInfo,
+        partitions -> Vec<Partition>,
+        partitions_lenient -> Partial<Vec<Partition>>,
+        softirqs -> Softirqs,
+        stat -> Stat,
+        swaps -> Vec<Swaps>,
+        swaps_lenient -> Partial<Vec<Swaps>>,
+        vmstat -> Vmstat,
+        zoneinfo -> Vec<ZoneInfo>,
+        #[cfg(feature = "process")]
+        process_tree -> ProcessTree,
+    );
+
+    /// async variant of [`ProcFs::process`]
+    #[cfg(feature = "process")]
+    pub async fn process(&self, pid: usize) -> CollectResult<AsyncProcess> {
+        let process = self.run(move |fs| fs.process(pid)).await?;
+        Ok(AsyncProcess::new(process, self.limiter.clone()))
+    }
+
+    /// async variant of [`ProcFs::processes`]
+    #[cfg(feature = "process")]
+    pub async fn processes(&self) -> CollectResult<Vec<AsyncProcess>> {
+        let processes = self.run(|fs| fs.processes()).await?;
+
+        Ok(processes
+            .into_iter()
+            .map(|process| AsyncProcess::new(process, self.limiter.clone()))
+            .collect())
+    }
+
+    /// runs `f` for every process concurrently, within the concurrency limit of the handle,
+    /// and returns the results sorted by pid
+    #[cfg(feature = "process")]
+    pub async fn scan<T, F>(&self, f: F) -> CollectResult<Vec<(usize, CollectResult<T>)>>
+    where
+        T: Send + 'static,
+        F: Fn(&Process) -> CollectResult<T> + Send + Sync + 'static,
+    {
+        let f = Arc::new(f);
+        let mut tasks = JoinSet::new();
+
+        for process in self.processes().await? {
+            let f = f.clone();
+            tasks.spawn(async move { (process.pid(), process.run(move |proc| f(proc)).await) });
+        }
+
+        let mut results = Vec::new();
+
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok(result) => results.push(result),
+                Err(err) if err.is_panic() => panic::resume_unwind(err.into_panic()),
+                Err(err) => return Err(MetricError::TaskError(err.to_string())),
+            }
+        }
+
+        results.sort_by_key(|(pid, _)| *pid);
+
+        Ok(results)
+    }
+}
+
+/// AsyncSysFs runs the collectors of a [`SysFs`] on the blocking pool of the tokio runtime
+#[derive(Debug, Clone)]
+pub struct AsyncSysFs {
+    sysfs: SysFs,
+    limiter: Arc<Semaphore>,
+}
+
+impl Default for AsyncSysFs {
+    fn default() -> Self {
+        Self::new(SysFs::default())
+    }
+}
+
+impl AsyncSysFs {
+    /// returns a new handle running at most [`DEFAULT_CONCURRENCY`] collectors at the same time
+    pub fn new(sysfs: SysFs) -> Self {
+        Self::with_concurrency(sysfs, DEFAULT_CONCURRENCY)
+    }
+
+    /// returns a new handle running at most `limit` collectors at the same time
+    pub fn with_concurrency(sysfs: SysFs, limit: usize) -> Self {
+        Self {
+            sysfs,
+            limiter: new_limiter(limit),
+        }
+    }
+
+    /// returns the blocking handle
+    pub fn sysfs(&self) -> &SysFs {
+        &self.sysfs
+    }
+
+    /// runs a blocking function of the sysfs handle on the blocking pool
+    pub async fn run<T, F>(&self, f: F) -> CollectResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&SysFs) -> CollectResult<T> + Send + 'static,
+    {
+        let sysfs = self.sysfs.clone();
+        run_blocking(&self.limiter, move || f(&sysfs)).await
+    }
+
+    async_collectors!(
+        SysFs,
+        #[cfg(feature = "sysfs-storage")]
+        block -> HashMap<String, block::BlockDevice>,
+        #[cfg(feature = "sysfs-storage")]
+        block_lenient -> Partial<HashMap<String, block::BlockDevice>>,
+        #[cfg(feature = "sysfs-storage")]
+        block_topology -> block_topology::BlockTopology,
+        #[cfg(feature = "sysfs-power")]
+        class_cooling -> Vec<class_cooling::Cooling>,
+        #[cfg(feature = "sysfs-power")]
+        class_cooling_lenient -> Partial<Vec<class_cooling::Cooling>>,
+        #[cfg(feature = "sysfs-hw")]
+        class_dmi -> class_dmi::DMI,
+        #[cfg(feature = "sysfs-storage")]
+        class_fibrechannel -> HashMap<String, class_fibrechannel::FibreChannelHost>,
+        #[cfg(feature = "sysfs-storage")]
+        class_fibrechannel_lenient -> Partial<HashMap<String, class_fibrechannel::FibreChannelHost>>,
+        #[cfg(feature = "sysfs-hw")]
+        class_infiniband -> HashMap<String, class_infiniband::InfiniBandDevice>,
+        #[cfg(feature = "sysfs-hw")]
+        class_infiniband_lenient -> Partial<HashMap<String, class_infiniband::InfiniBandDevice>>,
+        #[cfg(feature = "sysfs-storage")]
+        class_nvme -> HashMap<String, class_nvme::NVMeDevice>,
+        #[cfg(feature = "sysfs-storage")]
+        class_nvme_lenient -> Partial<HashMap<String, class_nvme::NVMeDevice>>,
+        #[cfg(feature = "sysfs-power")]
+        class_power_supply -> HashMap<String, class_power_supply::PowerSupply>,
+        #[cfg(feature = "sysfs-power")]
+        class_power_supply_lenient -> Partial<HashMap<String, class_power_supply::PowerSupply>>,
+        #[cfg(feature = "sysfs-storage")]
+        class_sas_device -> HashMap<String, class_sas_device::SASDevice>,
+        #[cfg(feature = "sysfs-storage")]
+        class_sas_device_lenient -> Partial<HashMap<String, class_sas_device::SASDevice>>,
+        #[cfg(feature = "sysfs-storage")]
+        class_sas_host -> HashMap<String, class_sas_host::SASHost>,
+        #[cfg(feature = "sysfs-storage")]
+        class_sas_phy -> HashMap<String, class_sas_phy::SASPhy>,
+        #[cfg(feature = "sysfs-storage")]
+        class_sas_phy_lenient -> Partial<HashMap<String, class_sas_phy::SASPhy>>,
+        #[cfg(feature = "sysfs-storage")]
+        class_sas_port -> HashMap<String, class_sas_port::SASPort>,
+        #[cfg(feature = "sysfs-storage")]
+        class_scsi_tape -> HashMap<String, class_scsi_tape::ScsiTapeCounters>,
+        #[cfg(feature = "sysfs-storage")]
+        class_scsi_tape_lenient -> Partial<HashMap<String, class_scsi_tape::ScsiTapeCounters>>,
+        #[cfg(feature = "sysfs-power")]
+        class_thermal -> Vec<class_thermal::ThermalZone>,
+        #[cfg(feature = "sysfs-power")]
+        class_thermal_lenient -> Partial<Vec<class_thermal::ThermalZone>>,
+        #[cfg(feature = "sysfs-hw")]
+        class_watchdog -> Vec<class_watchdog::Watchdog>,
+        #[cfg(feature = "sysfs-hw")]
+        class_watchdog_lenient -> Partial<Vec<class_watchdog::Watchdog>>,
+        #[cfg(feature = "sysfs-hw")]
+        clocksource -> Vec<clocksource::Clocksource>,
+        #[cfg(feature = "sysfs-hw")]
+        clocksource_lenient -> Partial<Vec<clocksource::Clocksource>>,
+    );
+}
+
+/// AsyncProcess runs the accessors of a [`Process`] on the blocking pool of the tokio runtime
+#[cfg(feature = "process")]
+#[derive(Debug, Clone)]
+pub struct AsyncProcess {
+    process: Arc<Process>,
+    limiter: Arc<Semaphore>,
+}
+
+#[cfg(feature = "process")]
+impl AsyncProcess {
+    fn new(process: Process, limiter: Arc<Semaphore>) -> Self {
+        Self {
+            process: Arc::new(process),
+            limiter,
+        }
+    }
+
+    pub fn pid(&self) -> usize {
+        self.process.pid()
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.process.path()
+    }
+
+    /// returns the blocking process
+    pub fn process(&self) -> &Process {
+        &self.process
+    }
+
+    /// runs a blocking function of the process on the blocking pool
+    pub async fn run<T, F>(&self, f: F) -> CollectResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Process) -> CollectResult<T> + Send + 'static,
+    {
+        let process = self.process.clone();
+        run_blocking(&self.limiter, move || f(&process)).await
+    }
+
+    async_collectors!(
+        Process,
+        comm -> String,
+        wchan -> String,
+        cmdline -> Vec<String>,
+        environ -> Vec<String>,
+        executable -> PathBuf,
+        cwd -> PathBuf,
+        root_dir -> PathBuf,
+        cgroup -> Vec<ProcessCgroup>,
+        fd_count -> usize,
+        fds -> Vec<ProcessFd>,
+        io -> ProcessIO,
+        limits -> ProcessLimits,
+        maps -> Vec<ProcessMap>,
+        smaps -> Vec<ProcessSmap>,
+        smaps_rollup -> ProcessSmap,
+        net_snmp -> ProcessNetSnmp,
+        net_snmp6 -> ProcessNetSnmp6,
+        netstat -> ProcessNetstat,
+        namespaces -> HashMap<String, ProcessNamespace>,
+        schedstat -> ProcessSchedstat,
+        stat -> ProcessStat,
+        status -> ProcessStatus,
+    );
+
+    /// async variant of [`Process::fdinfo`]
+    pub async fn fdinfo(&self, fd: u64) -> CollectResult<ProcessFdInfo> {
+        self.run(move |proc| proc.fdinfo(fd)).await
+    }
+
+    /// async variant of [`Process::tasks`]
+    pub async fn tasks(&self) -> CollectResult<Vec<AsyncProcess>> {
+        let tasks = self.run(|proc| proc.tasks()).await?;
+
+        Ok(tasks
+            .into_iter()
+            .map(|task| AsyncProcess::new(task, self.limiter.clone()))
+            .collect())
+    }
+
+    /// async variant of [`Process::task`]
+    pub async fn task(&self, tid: usize) -> CollectResult<AsyncProcess> {
+        let task = self.run(move |proc| proc.task(tid)).await?;
+        Ok(AsyncProcess::new(task, self.limiter.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "process")]
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .build()
+            .expect("tokio runtime")
+    }
+
+    #[test]
+    fn async_procfs() {
+        let procfs = AsyncProcFs::new(ProcFs::new("test_data/fixtures/proc"));
+
+        runtime().block_on(async {
+            let sys_meminfo = procfs.meminfo().await.expect("memory information");
+            assert_eq!(
+                sys_meminfo.mem_total,
+                procfs.procfs().meminfo().unwrap().mem_total
+            );
+
+            #[cfg(feature = "process")]
+            {
+                let proc = procfs.process(26231).await.expect("process 26231");
+                assert_eq!(proc.comm().await.unwrap(), "vim");
+                assert!(procfs.process(1).await.is_err());
+            }
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "sysfs-storage")]
+    fn async_sysfs() {
+        let sysfs = AsyncSysFs::new(SysFs::new("test_data/fixtures/sys"));
+
+        runtime().block_on(async {
+            let nvme_devices = sysfs.class_nvme().await.expect("nvme devices");
+            assert_eq!(nvme_devices.len(), 1);
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "process")]
+    fn async_scan_concurrency() {
+        let procfs = AsyncProcFs::with_concurrency(ProcFs::new("test_data/fixtures/proc"), 2);
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+
+        let (running_scan, max_running_scan) = (running.clone(), max_running.clone());
+        let results = runtime().block_on(procfs.scan(move |proc| {
+            let current = running_scan.fetch_add(1, Ordering::SeqCst) + 1;
+            max_running_scan.fetch_max(current, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            running_scan.fetch_sub(1, Ordering::SeqCst);
+
+            proc.comm()
+        }));
+
+        let results = results.expect("process scan");
+        let blocking = procfs.procfs().processes().expect("processes");
+        assert_eq!(results.len(), blocking.len());
+        assert!(results.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(max_running.load(Ordering::SeqCst) <= 2);
+    }
+}
@@ -0,0 +1,264 @@
+use std::{collections::HashMap, path::Path};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{CollectResult, MetricError},
+    procfs::ProcFs,
+    utils,
+};
+
+/// PageTypeZone holds the free blocks and the page blocks of a zone per migrate type
+/// (e.g. `Unmovable`, `Movable`, `Reclaimable`)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PageTypeZone {
+    pub node: String,
+    pub zone: String,
+
+    /// free blocks of 2^order pages, indexed by order and keyed by migrate type
+    pub free_blocks: HashMap<String, Vec<u64>>,
+
+    /// page blocks keyed by migrate type
+    pub blocks: HashMap<String, u64>,
+
+    /// page blocks holding pages of another migrate type, only reported by the
+    /// kernels built with page owner tracking
+    pub mixed_blocks: HashMap<String, u64>,
+}
+
+impl PageTypeZone {
+    fn new(node: &str, zone: &str) -> Self {
+        Self {
+            node: node.to_string(),
+            zone: zone.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// PageTypeInfo is the free memory per migrate type parsed from /proc/pagetypeinfo
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PageTypeInfo {
+    pub page_block_order: u64,
+    pub pages_per_block: u64,
+    pub zones: Vec<PageTypeZone>,
+}
+
+impl PageTypeInfo {
+    fn new() -> Self {
+        Default::default()
+    }
+
+    /// returns the zone of the given node and name
+    pub fn zone(&self, node: &str, zone: &str) -> Option<&PageTypeZone> {
+        self.zones.iter().find(|z| z.node == node && z.zone == zone)
+    }
+
+    fn zone_mut(&mut self, node: &str, zone: &str) -> &mut PageTypeZone {
+        let index = match self
+            .zones
+            .iter()
+            .position(|z| z.node == node && z.zone == zone)
+        {
+            Some(index) => index,
+            None => {
+                self.zones.push(PageTypeZone::new(node, zone));
+                self.zones.len() - 1
+            }
+        };
+
+        &mut self.zones[index]
+    }
+}
+
+// table of the file the current line belongs to
+enum Table {
+    FreePages,
+    Blocks(Vec<String>),
+    MixedBlocks(Vec<String>),
+}
+
+/// collects the free memory per migrate type, the file is only readable by root
+/// # Example
+/// ```
+/// use procsys::pagetypeinfo;
+///
+/// match pagetypeinfo::collect() {
+///     Ok(info) => {
+///         for zone in &info.zones {
+///             println!("node {} {}: {:?}", zone.node, zone.zone, zone.blocks);
+///         }
+///     }
+///     Err(err) => println!("pagetypeinfo: {}", err),
+/// }
+///
+/// ```
+pub fn collect() -> CollectResult<PageTypeInfo> {
+    ProcFs::default().pagetypeinfo()
+}
+
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<PageTypeInfo> {
+    let mut info = PageTypeInfo::new();
+    let mut table = Table::FreePages;
+
+    for (index, line) in utils::read_file_lines(filename)?.iter().enumerate() {
+        parse_pagetypeinfo_line(&mut info, &mut table, line)
+            .map_err(|err| err.with_line(filename, index + 1))?;
+    }
+
+    Ok(info)
+}
+
+fn parse_pagetypeinfo_line(
+    info: &mut PageTypeInfo,
+    table: &mut Table,
+    line: &str,
+) -> CollectResult<()> {
+    if line.trim().is_empty() {
+        return Ok(());
+    }
+
+    if let Some(value) = line.strip_prefix("Page block order:") {
+        info.page_block_order = utils::convert_str_to_u64(value.trim())?;
+    } else if let Some(value) = line.strip_prefix("Pages per block:") {
+        info.pages_per_block = utils::convert_str_to_u64(value.trim())?;
+    } else if line.starts_with("Free pages count per migrate type") {
+        *table = Table::FreePages;
+    } else if let Some(types) = line.strip_prefix("Number of blocks type") {
+        *table = Table::Blocks(types.split_whitespace().map(String::from).collect());
+    } else if let Some(types) = line.strip_prefix("Number of mixed blocks") {
+        *table = Table::MixedBlocks(types.split_whitespace().map(String::from).collect());
+    } else if line.starts_with("Node") {
+        parse_zone_row(info, table, line)?;
+    }
+
+    Ok(())
+}
+
+// parses "Node    0, zone   Normal, type      Movable   3182    612 ..." rows of the
+// free pages table and "Node 0, zone   Normal   340   5196 ..." rows of the blocks tables
+fn parse_zone_row(info: &mut PageTypeInfo, table: &Table, line: &str) -> CollectResult<()> {
+    let parts: Vec<&str> = line.split(',').collect();
+    let invalid_row = || {
+        MetricError::InvalidFieldNumberError(
+            "pagetypeinfo".to_string(),
+            parts.len(),
+            line.to_string(),
+        )
+    };
+
+    if parts.len() < 2 {
+        return Err(invalid_row());
+    }
+
+    let node = parts[0].trim_start_matches("Node").trim();
+    let zone_fields: Vec<&str> = parts[1].split_whitespace().collect();
+    if zone_fields.len() < 2 || zone_fields[0] != "zone" {
+        return Err(invalid_row());
+    }
+
+    match table {
+        Table::FreePages => {
+            let type_fields: Vec<&str> = parts
+                .get(2)
+                .ok_or_else(invalid_row)?
+                .split_whitespace()
+                .collect();
+            if type_fields.len() < 2 || type_fields[0] != "type" {
+                return Err(invalid_row());
+            }
+
+            let mut counts = Vec::new();
+            for value in &type_fields[2..] {
+                // counts above the limit of the kernel are printed as ">100000"
+                counts.push(utils::convert_str_to_u64(value.trim_start_matches('>'))?);
+            }
+
+            info.zone_mut(node, zone_fields[1])
+                .free_blocks
+                .insert(type_fields[1].to_string(), counts);
+        }
+        Table::Blocks(types) | Table::MixedBlocks(types) => {
+            let values = &zone_fields[2..];
+            if values.len() != types.len() {
+                return Err(invalid_row());
+            }
+
+            let zone = info.zone_mut(node, zone_fields[1]);
+            let blocks = match table {
+                Table::Blocks(_) => &mut zone.blocks,
+                _ => &mut zone.mixed_blocks,
+            };
+
+            for (migrate_type, value) in types.iter().zip(values) {
+                blocks.insert(migrate_type.to_string(), utils::convert_str_to_u64(value)?);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pagetypeinfo() {
+        let info = collect_from(Path::new("test_data/fixtures/proc/pagetypeinfo"))
+            .expect("page type information");
+
+        assert_eq!(info.page_block_order, 9);
+        assert_eq!(info.pages_per_block, 512);
+        assert_eq!(info.zones.len(), 2);
+
+        let dma = info.zone("0", "DMA").expect("DMA zone");
+        assert_eq!(dma.free_blocks.len(), 5);
+        assert_eq!(
+            dma.free_blocks["Movable"],
+            [1, 0, 1, 0, 1, 0, 0, 0, 1, 1, 3]
+        );
+        assert_eq!(dma.blocks["Movable"], 7);
+        assert!(dma.mixed_blocks.is_empty());
+
+        let normal = info.zone("0", "Normal").expect("Normal zone");
+        assert_eq!(
+            normal.free_blocks["Unmovable"],
+            [913, 395, 88, 41, 14, 2, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(normal.blocks["Unmovable"], 340);
+        assert_eq!(normal.blocks["Reclaimable"], 480);
+        assert_eq!(normal.blocks.len(), 5);
+
+        assert!(info.zone("1", "Normal").is_none());
+    }
+
+    #[test]
+    fn pagetypeinfo_mixed_blocks() {
+        let mut memfs = crate::filesystem::MemoryFileSystem::new();
+        memfs.insert_file(
+            "/proc/pagetypeinfo",
+            "Page block order: 9\n\
+             Pages per block:  512\n\
+             \n\
+             Free pages count per migrate type at order       0      1\n\
+             Node    0, zone   Normal, type      Movable >100000  61234\n\
+             \n\
+             Number of blocks type     Unmovable      Movable\n\
+             Node 0, zone   Normal          340         5196\n\
+             \n\
+             Number of mixed blocks    Unmovable      Movable\n\
+             Node 0, zone   Normal           12            3\n",
+        );
+        let procfs = ProcFs::with_filesystem("/proc", std::sync::Arc::new(memfs));
+
+        let info = procfs.pagetypeinfo().expect("page type information");
+        let normal = info.zone("0", "Normal").expect("Normal zone");
+        assert_eq!(normal.free_blocks["Movable"], [100000, 61234]);
+        assert_eq!(normal.blocks["Movable"], 5196);
+        assert_eq!(normal.mixed_blocks["Unmovable"], 12);
+    }
+}
@@ -0,0 +1,143 @@
+use std::path::Path;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{CollectResult, MetricError, Partial},
+    procfs::ProcFs,
+    utils,
+};
+
+/// Partition is a block device entry of /proc/partitions, disks included
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Partition {
+    pub major: u64,
+    pub minor: u64,
+
+    /// size in 1KiB blocks
+    pub blocks: u64,
+
+    pub name: String,
+}
+
+impl Partition {
+    fn new() -> Self {
+        Default::default()
+    }
+
+    /// returns the size of the device in bytes
+    pub fn size(&self) -> u64 {
+        self.blocks * 1024
+    }
+}
+
+/// collects the block devices and their partitions
+/// # Example
+/// ```
+/// use procsys::partitions;
+///
+/// let sys_partitions = partitions::collect().expect("partitions information");
+///
+/// for partition in &sys_partitions {
+///     println!(
+///         "{}:{} {} {} bytes",
+///         partition.major,
+///         partition.minor,
+///         partition.name,
+///         partition.size(),
+///     );
+/// }
+///
+/// ```
+pub fn collect() -> CollectResult<Vec<Partition>> {
+    ProcFs::default().partitions()
+}
+
+/// collects the partitions, a malformed line of /proc/partitions is reported under its
+/// device name
+pub fn collect_lenient() -> CollectResult<Partial<Vec<Partition>>> {
+    ProcFs::default().partitions_lenient()
+}
+
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<Vec<Partition>> {
+    collect_lenient_from(filename)?.into_result()
+}
+
+pub(crate) fn collect_lenient_from(filename: &Path) -> CollectResult<Partial<Vec<Partition>>> {
+    let mut sys_partitions: Partial<Vec<Partition>> = Partial::default();
+
+    // the first line is the header followed by an empty line
+    for (line_index, line) in utils::read_file_lines(filename)?.iter().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match parse_partition(line) {
+            Ok(partition) => sys_partitions.items.push(partition),
+            Err(err) => sys_partitions.push_error(
+                &partition_item(line, line_index + 1),
+                filename,
+                err.with_line(filename, line_index + 1),
+            ),
+        }
+    }
+
+    Ok(sys_partitions)
+}
+
+// returns the device name of a broken line, its last field
+fn partition_item(line: &str, line_number: usize) -> String {
+    match line.split_whitespace().nth(3) {
+        Some(name) => name.to_string(),
+        None => utils::line_item(line, line_number),
+    }
+}
+
+fn parse_partition(line: &str) -> CollectResult<Partition> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+
+    if fields.len() != 4 {
+        return Err(MetricError::InvalidFieldNumberError(
+            "partitions".to_string(),
+            fields.len(),
+            line.to_string(),
+        ));
+    }
+
+    let mut partition = Partition::new();
+    partition.major = utils::convert_str_to_u64(fields[0])?;
+    partition.minor = utils::convert_str_to_u64(fields[1])?;
+    partition.blocks = utils::convert_str_to_u64(fields[2])?;
+    partition.name = fields[3].to_string();
+
+    Ok(partition)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partitions() {
+        let sys_partitions = collect_from(Path::new("test_data/fixtures/proc/partitions"))
+            .expect("partitions information");
+
+        assert_eq!(sys_partitions.len(), 12);
+
+        assert_eq!(
+            sys_partitions[1],
+            Partition {
+                major: 8,
+                minor: 0,
+                blocks: 488386584,
+                name: "sda".to_string(),
+            }
+        );
+        assert_eq!(sys_partitions[1].size(), 488386584 * 1024);
+
+        let swap = sys_partitions.iter().find(|p| p.name == "dm-2").unwrap();
+        assert_eq!((swap.major, swap.minor, swap.blocks), (253, 2, 131072));
+    }
+}
@@ -1,25 +1,37 @@
 use std::{
-    fs::read_link,
+    io,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::{
     error::{CollectResult, MetricError},
+    filesystem::{self, FileSystem},
+    procfs::ProcFs,
     utils,
 };
 
 /// Proc represent a single process
-#[derive(Debug, Serialize)]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Process {
     id: usize,
     path: PathBuf,
+    #[cfg_attr(feature = "serde", serde(skip, default = "filesystem::real"))]
+    fs: Arc<dyn FileSystem>,
 }
 
 impl Process {
-    fn new(id: usize, path: PathBuf) -> Self {
-        Self { id, path }
+    // the process keeps reading from the filesystem it has been found on
+    pub(crate) fn new(id: usize, path: PathBuf) -> Self {
+        Self {
+            id,
+            path,
+            fs: filesystem::current(),
+        }
     }
 
     pub fn pid(&self) -> usize {
@@ -30,71 +42,64 @@ impl Process {
         self.path.clone()
     }
 
+    // runs a parser reading from the filesystem of the process, the files
+    // which vanish because the process has exited meanwhile are reported
+    // as ProcessGone
+    pub(crate) fn scope<T>(&self, f: impl FnOnce() -> CollectResult<T>) -> CollectResult<T> {
+        filesystem::scope(&self.fs, || f().map_err(|err| self.map_error(err)))
+    }
+
+    fn map_error(&self, err: MetricError) -> MetricError {
+        match err {
+            MetricError::IOError(ref path, ref e) if is_gone(self.fs.as_ref(), &self.path, e) => {
+                MetricError::ProcessGone(self.id, path.clone())
+            }
+            err => err.with_path(&self.path),
+        }
+    }
+
     /// returns the command name of a process
     pub fn comm(&self) -> CollectResult<String> {
-        match utils::collect_info_string("comm", &self.path) {
+        self.scope(|| match utils::collect_info_string("comm", &self.path) {
             Ok(c) => Ok(c.unwrap_or_default().trim().to_string()),
             Err(err) => Err(err),
-        }
+        })
     }
 
     /// returns the wchan (wait channel) of a process
     pub fn wchan(&self) -> CollectResult<String> {
-        match utils::collect_info_string("wchan", &self.path) {
-            Ok(c) => {
-                let mut wchan = String::new();
-                let wchan_data = c.unwrap_or_default().trim().to_string();
-
-                if wchan_data != "0" {
-                    wchan = wchan_data;
-                }
-
-                Ok(wchan)
-            }
+        self.scope(|| match utils::collect_info_string("wchan", &self.path) {
+            Ok(c) => Ok(parse_wchan(&c.unwrap_or_default())),
             Err(err) => Err(err),
-        }
+        })
     }
 
     /// returns the command line of a process
     pub fn cmdline(&self) -> CollectResult<Vec<String>> {
-        match utils::collect_info_string("cmdline", &self.path) {
-            Ok(c) => {
-                let proc_cmdline = c
-                    .unwrap_or_default()
-                    .trim_end_matches("\x00")
-                    .split("\x00")
-                    .map(|v| v.to_string())
-                    .collect::<Vec<String>>();
-
-                Ok(proc_cmdline)
-            }
+        self.scope(|| match utils::collect_info_string("cmdline", &self.path) {
+            Ok(c) => Ok(parse_nul_separated(&c.unwrap_or_default())),
             Err(err) => Err(err),
-        }
+        })
     }
 
     /// returns the process environments from `/proc/<pid>/environ`
     pub fn environ(&self) -> CollectResult<Vec<String>> {
-        match utils::collect_info_string("environ", &self.path) {
-            Ok(c) => {
-                let proc_cmdline = c
-                    .unwrap_or_default()
-                    .trim_end_matches("\x00")
-                    .split("\x00")
-                    .map(|v| v.to_string())
-                    .collect::<Vec<String>>();
-
-                Ok(proc_cmdline)
-            }
+        self.scope(|| match utils::collect_info_string("environ", &self.path) {
+            Ok(c) => Ok(parse_nul_separated(&c.unwrap_or_default())),
             Err(err) => Err(err),
-        }
+        })
     }
 
     /// returns the absolute path of the executable command of a process
     pub fn executable(&self) -> CollectResult<PathBuf> {
+        self.scope(|| self.executable_from())
+    }
+
+    fn executable_from(&self) -> CollectResult<PathBuf> {
         let mut proc_path = self.path.clone();
         proc_path.push("exe");
 
-        match read_link(&proc_path) {
+        match filesystem::read_link(&proc_path) {
             Ok(c) => {
                 if c.exists() {
                     return Ok(c);
@@ -108,10 +113,14 @@ impl Process {
 
     /// returns the absolute path to the current working directory of the process
     pub fn cwd(&self) -> CollectResult<PathBuf> {
+        self.scope(|| self.cwd_from())
+    }
+
+    fn cwd_from(&self) -> CollectResult<PathBuf> {
         let mut proc_path = self.path.clone();
         proc_path.push("cwd");
 
-        match read_link(&proc_path) {
+        match filesystem::read_link(&proc_path) {
             Ok(c) => {
                 if c.exists() {
                     return Ok(c);
@@ -125,10 +134,14 @@ impl Process {
 
     /// returns the absolute path to the process's root directory (as set by chroot)
     pub fn root_dir(&self) -> CollectResult<PathBuf> {
+        self.scope(|| self.root_dir_from())
+    }
+
+    fn root_dir_from(&self) -> CollectResult<PathBuf> {
         let mut proc_path = self.path.clone();
         proc_path.push("root");
 
-        match read_link(&proc_path) {
+        match filesystem::read_link(&proc_path) {
             Ok(c) => {
                 if c.exists() {
                     return Ok(c);
@@ -141,6 +154,34 @@ impl Process {
     }
 }
 
+// the files of an exited process vanish along with its directory, or their
+// reads fail with ESRCH
+pub(crate) fn is_gone(fs: &dyn FileSystem, proc_path: &Path, err: &io::Error) -> bool {
+    match err.raw_os_error() {
+        Some(libc::ESRCH) => true,
+        _ => err.kind() == io::ErrorKind::NotFound && !fs.is_dir(proc_path),
+    }
+}
+
+// splits the NUL separated content of the cmdline and environ files
+pub(crate) fn parse_nul_separated(content: &str) -> Vec<String> {
+    content
+        .trim_end_matches("\x00")
+        .split("\x00")
+        .map(|v| v.to_string())
+        .collect::<Vec<String>>()
+}
+
+// the wait channel is "0" if the process is running
+pub(crate) fn parse_wchan(content: &str) -> String {
+    let wchan_data = content.trim();
+
+    match wchan_data {
+        "0" => String::new(),
+        _ => wchan_data.to_string(),
+    }
+}
+
 /// Collects all available processes running on system.
 /// # Example
 /// ```
@@ -158,8 +199,7 @@ impl Process {
 ///
 /// ```
 pub fn collect_all() -> CollectResult<Vec<Process>> {
-    let proc_path = Path::new("/proc");
-    collect_all_from(proc_path)
+    ProcFs::default().processes()
 }
 
 /// collect and return a specific process
@@ -173,11 +213,10 @@ pub fn collect_all() -> CollectResult<Vec<Process>> {
 ///
 /// ```
 pub fn collect(pid: usize) -> CollectResult<Process> {
-    let proc_path = Path::new("/proc");
-    collect_from(proc_path, pid)
+    ProcFs::default().process(pid)
 }
 
-fn collect_all_from(base_path: &Path) -> CollectResult<Vec<Process>> {
+pub(crate) fn collect_all_from(base_path: &Path) -> CollectResult<Vec<Process>> {
     let mut sysprocs = Vec::new();
 
     for file_info in utils::list_dir_content(base_path, "", "proc") {
@@ -185,7 +224,7 @@ fn collect_all_from(base_path: &Path) -> CollectResult<Vec<Process>> {
             let mut proc_dir_path = PathBuf::from(base_path);
             proc_dir_path.push(format!("{}", pid));
 
-            if proc_dir_path.as_path().is_dir() {
+            if filesystem::is_dir(&proc_dir_path) {
                 sysprocs.push(Process::new(pid, proc_dir_path));
             }
         }
@@ -198,7 +237,7 @@ pub fn collect_from(base_path: &Path, pid: usize) -> CollectResult<Process> {
     let mut proc_dir_path = PathBuf::from(base_path);
     proc_dir_path.push(format!("{}", pid));
 
-    if proc_dir_path.as_path().is_dir() {
+    if filesystem::is_dir(&proc_dir_path) {
         return Ok(Process::new(pid, proc_dir_path));
     }
 
@@ -207,8 +246,33 @@ pub fn collect_from(base_path: &Path, pid: usize) -> CollectResult<Process> {
 
 #[cfg(test)]
 mod tests {
+    use crate::{error::ErrorKind, filesystem::MemoryFileSystem};
+
     use super::*;
 
+    #[test]
+    fn process_gone() {
+        let mut memfs = MemoryFileSystem::new();
+        memfs.insert_file("proc/42/comm", "bash\n");
+        let memfs: Arc<dyn FileSystem> = Arc::new(memfs);
+
+        // a missing file of a running process is a plain io error
+        let proc = filesystem::scope(&memfs, || Process::new(42, PathBuf::from("/proc/42")));
+        let err = proc.status().expect_err("missing status file");
+        assert!(matches!(err, MetricError::IOError(_, _)));
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        // the files of an exited process vanish along with its directory
+        let proc = filesystem::scope(&memfs, || Process::new(43, PathBuf::from("/proc/43")));
+        match proc.status() {
+            Err(MetricError::ProcessGone(pid, path)) => {
+                assert_eq!(pid, 43);
+                assert_eq!(path, PathBuf::from("/proc/43/status"));
+            }
+            other => panic!("expected process gone error: {:?}", other),
+        }
+    }
+
     #[test]
     fn proc_collect_all() {
         let sysprocs =
@@ -1,13 +1,15 @@
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::{
     error::{CollectResult, MetricError},
+    filesystem,
     process::Process,
-    utils,
 };
 
 /// ProcessCgroup models one line from /proc/\[pid\]/cgroup
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProcessCgroup {
     pub hierarchy_id: usize,
     pub controllers: Vec<String>,
@@ -23,41 +25,48 @@ impl ProcessCgroup {
 impl Process {
     /// cgroup reads from /proc/\<pid\>/cgroup and returns cgroup information of the process
     pub fn cgroup(&self) -> CollectResult<Vec<ProcessCgroup>> {
-        let mut proc_cgroups = Vec::new();
-        let proc_cgroup_path_str = format!("{:?}", self.path());
-        let proc_cgroup_file = format!("{}/cgroup", proc_cgroup_path_str.replace("\"", ""));
-
-        for line in utils::read_file_lines(&proc_cgroup_file)? {
-            let item_fields: Vec<&str> = line.trim().split(':').collect();
-            if item_fields.len() != 3 {
-                return Err(MetricError::InvalidFieldNumberError(
-                    "process cgroup".to_string(),
-                    item_fields.len(),
-                    line,
-                ));
-            }
-
-            let mut proc_cgroup = ProcessCgroup::new();
-            proc_cgroup.path = item_fields[2].trim().to_string();
-
-            match item_fields[0].parse::<usize>() {
-                Ok(v) => proc_cgroup.hierarchy_id = v,
-                Err(e) => return Err(MetricError::ParseIntError(item_fields[0].to_string(), e)),
-            }
-
-            if item_fields[1].trim() != "" {
-                proc_cgroup.controllers = item_fields[1]
-                    .trim()
-                    .split(",")
-                    .map(|c| c.to_string())
-                    .collect::<Vec<String>>()
-            }
-
-            proc_cgroups.push(proc_cgroup);
+        self.scope(|| self.cgroup_from())
+    }
+
+    fn cgroup_from(&self) -> CollectResult<Vec<ProcessCgroup>> {
+        let content = filesystem::read_to_string(&self.path().join("cgroup"))?;
+        parse_cgroup(&content)
+    }
+}
+
+pub(crate) fn parse_cgroup(content: &str) -> CollectResult<Vec<ProcessCgroup>> {
+    let mut proc_cgroups = Vec::new();
+
+    for line in content.lines() {
+        let item_fields: Vec<&str> = line.trim().split(':').collect();
+        if item_fields.len() != 3 {
+            return Err(MetricError::InvalidFieldNumberError(
+                "process cgroup".to_string(),
+                item_fields.len(),
+                line.to_string(),
+            ));
         }
 
-        Ok(proc_cgroups)
+        let mut proc_cgroup = ProcessCgroup::new();
+        proc_cgroup.path = item_fields[2].trim().to_string();
+
+        match item_fields[0].parse::<usize>() {
+            Ok(v) => proc_cgroup.hierarchy_id = v,
+            Err(e) => return Err(MetricError::ParseIntError(item_fields[0].to_string(), e)),
+        }
+
+        if item_fields[1].trim() != "" {
+            proc_cgroup.controllers = item_fields[1]
+                .trim()
+                .split(",")
+                .map(|c| c.to_string())
+                .collect::<Vec<String>>()
+        }
+
+        proc_cgroups.push(proc_cgroup);
     }
+
+    Ok(proc_cgroups)
 }
 
 #[cfg(test)]
@@ -0,0 +1,436 @@
+use std::path::PathBuf;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{CollectResult, MetricError},
+    filesystem,
+    process::Process,
+    utils,
+};
+
+/// ProcessFdTarget is the classified link target of an open file descriptor
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ProcessFdTarget {
+    /// regular file, directory or device
+    Path(PathBuf),
+
+    /// socket:\[inode\]
+    Socket(u64),
+
+    /// pipe:\[inode\]
+    Pipe(u64),
+
+    /// anon_inode:\<type\> (e.g. eventfd, inotify, \[eventpoll\])
+    AnonInode(String),
+
+    /// memfd:\<name\>
+    Memfd(String),
+
+    /// any other pseudo file target (e.g. net:\[inode\], mnt:\[inode\])
+    Other(String),
+}
+
+impl Default for ProcessFdTarget {
+    fn default() -> Self {
+        ProcessFdTarget::Other(String::new())
+    }
+}
+
+impl ProcessFdTarget {
+    fn from_link(link: PathBuf) -> Self {
+        let target = match link.to_str() {
+            Some(t) => t,
+            None => return ProcessFdTarget::Path(link),
+        };
+
+        if let Some(inode) = parse_bracket_inode(target, "socket:") {
+            return ProcessFdTarget::Socket(inode);
+        }
+
+        if let Some(inode) = parse_bracket_inode(target, "pipe:") {
+            return ProcessFdTarget::Pipe(inode);
+        }
+
+        if let Some(anon_type) = target.strip_prefix("anon_inode:") {
+            return ProcessFdTarget::AnonInode(anon_type.to_string());
+        }
+
+        if let Some(name) = target.strip_prefix("/memfd:") {
+            return ProcessFdTarget::Memfd(name.trim_end_matches(" (deleted)").to_string());
+        }
+
+        if target.starts_with('/') || target.starts_with('.') {
+            return ProcessFdTarget::Path(link);
+        }
+
+        ProcessFdTarget::Other(target.to_string())
+    }
+}
+
+/// ProcessFd is a single open file descriptor from /proc/\<pid\>/fd
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessFd {
+    pub fd: u64,
+    pub target: ProcessFdTarget,
+}
+
+/// ProcessFdInotify is an inotify watch of an inotify file descriptor
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessFdInotify {
+    pub wd: i64,
+    pub ino: u64,
+    pub sdev: u64,
+    pub mask: u64,
+    pub ignored_mask: u64,
+}
+
+/// ProcessFdEpoll is a target file descriptor watched by an epoll file descriptor
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessFdEpoll {
+    pub tfd: u64,
+    pub events: u64,
+    pub data: u64,
+    pub pos: Option<u64>,
+    pub ino: Option<u64>,
+    pub sdev: Option<u64>,
+}
+
+/// ProcessFdInfo models the content of /proc/\<pid\>/fdinfo/\<fd\>
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessFdInfo {
+    pub fd: u64,
+
+    /// current file offset
+    pub pos: u64,
+
+    /// file access mode and status flags (octal in the source file)
+    pub flags: u64,
+
+    /// mount id of the file
+    pub mnt_id: u64,
+
+    /// inode number of the file
+    pub ino: Option<u64>,
+
+    /// eventfd counter value
+    pub eventfd_count: Option<u64>,
+
+    /// inotify watches
+    pub inotify: Vec<ProcessFdInotify>,
+
+    /// epoll target file descriptors
+    pub epoll: Vec<ProcessFdEpoll>,
+}
+
+impl ProcessFdInfo {
+    fn new(fd: u64) -> Self {
+        Self {
+            fd,
+            ..Default::default()
+        }
+    }
+}
+
+impl Process {
+    /// returns the number of open file descriptors of the process
+    pub fn fd_count(&self) -> CollectResult<usize> {
+        self.scope(|| self.fd_count_from())
+    }
+
+    fn fd_count_from(&self) -> CollectResult<usize> {
+        let mut proc_fd_path = self.path();
+        proc_fd_path.push("fd");
+
+        if !filesystem::is_dir(&proc_fd_path) {
+            return Err(MetricError::PathNotFound(proc_fd_path));
+        }
+
+        Ok(utils::list_dir_content(&proc_fd_path, "", "fd")
+            .iter()
+            .filter(|f| f.parse::<u64>().is_ok())
+            .count())
+    }
+
+    /// returns the open file descriptors of the process from /proc/\<pid\>/fd
+    /// # Example
+    /// ```
+    /// use procsys::process;
+    ///
+    /// let proc = process::collect(1).expect("process pid 1");
+    /// for fd in proc.fds().unwrap_or_default() {
+    ///     println!("{}: {:?}", fd.fd, fd.target);
+    /// }
+    ///
+    /// ```
+    pub fn fds(&self) -> CollectResult<Vec<ProcessFd>> {
+        self.scope(|| self.fds_from())
+    }
+
+    fn fds_from(&self) -> CollectResult<Vec<ProcessFd>> {
+        let mut proc_fds = Vec::new();
+
+        let mut proc_fd_path = self.path();
+        proc_fd_path.push("fd");
+
+        if !filesystem::is_dir(&proc_fd_path) {
+            return Err(MetricError::PathNotFound(proc_fd_path));
+        }
+
+        for fd_name in utils::list_dir_content(&proc_fd_path, "", "fd") {
+            let fd = match fd_name.parse::<u64>() {
+                Ok(fd) => fd,
+                Err(_) => continue,
+            };
+
+            let fd_link_path = proc_fd_path.join(&fd_name);
+            let target = match filesystem::read_link(&fd_link_path) {
+                Ok(link) => ProcessFdTarget::from_link(link),
+                // the file descriptor has been closed in the meantime
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(MetricError::IOError(fd_link_path, err)),
+            };
+
+            proc_fds.push(ProcessFd { fd, target });
+        }
+
+        proc_fds.sort_by_key(|f| f.fd);
+
+        Ok(proc_fds)
+    }
+
+    /// returns information of an open file descriptor from /proc/\<pid\>/fdinfo/\<fd\>
+    pub fn fdinfo(&self, fd: u64) -> CollectResult<ProcessFdInfo> {
+        self.scope(|| self.fdinfo_from(fd))
+    }
+
+    fn fdinfo_from(&self, fd: u64) -> CollectResult<ProcessFdInfo> {
+        let mut proc_fdinfo = ProcessFdInfo::new(fd);
+
+        let mut proc_fdinfo_file = self.path();
+        proc_fdinfo_file.push("fdinfo");
+        proc_fdinfo_file.push(fd.to_string());
+
+        for line in utils::read_file_lines(&proc_fdinfo_file)? {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(watch) = line.strip_prefix("inotify ") {
+                proc_fdinfo.inotify.push(parse_inotify(watch)?);
+                continue;
+            }
+
+            if line.starts_with("tfd:") {
+                proc_fdinfo.epoll.push(parse_epoll(line)?);
+                continue;
+            }
+
+            let (item, value) = match line.split_once(':') {
+                Some((item, value)) => (item.trim(), value.trim()),
+                None => continue,
+            };
+
+            match item {
+                "pos" => proc_fdinfo.pos = utils::convert_str_to_u64(value)?,
+                "flags" => proc_fdinfo.flags = utils::convert_radix_str_to_u64(value, 8)?,
+                "mnt_id" => proc_fdinfo.mnt_id = utils::convert_str_to_u64(value)?,
+                "ino" => proc_fdinfo.ino = Some(utils::convert_str_to_u64(value)?),
+                "eventfd-count" => {
+                    proc_fdinfo.eventfd_count = Some(utils::convert_radix_str_to_u64(value, 16)?)
+                }
+                _ => {}
+            }
+        }
+
+        Ok(proc_fdinfo)
+    }
+}
+
+fn parse_bracket_inode(target: &str, prefix: &str) -> Option<u64> {
+    target
+        .strip_prefix(prefix)?
+        .strip_prefix('[')?
+        .strip_suffix(']')?
+        .parse::<u64>()
+        .ok()
+}
+
+// splits fdinfo lines such as "tfd:        5 events:       1d data: ffffffffffffffff"
+// or "wd:3 ino:1 sdev:34" into key value pairs
+fn parse_key_values(line: &str) -> Vec<(&str, &str)> {
+    let mut key_values = Vec::new();
+    let mut fields = line.split_whitespace();
+
+    while let Some(field) = fields.next() {
+        if let Some(key) = field.strip_suffix(':') {
+            if let Some(value) = fields.next() {
+                key_values.push((key, value));
+            }
+
+            continue;
+        }
+
+        if let Some((key, value)) = field.split_once(':') {
+            key_values.push((key, value));
+        }
+    }
+
+    key_values
+}
+
+fn parse_inotify(line: &str) -> CollectResult<ProcessFdInotify> {
+    let mut inotify = ProcessFdInotify::default();
+
+    for (key, value) in parse_key_values(line) {
+        match key {
+            "wd" => inotify.wd = utils::convert_str_to_i64(value)?,
+            "ino" => inotify.ino = utils::convert_radix_str_to_u64(value, 16)?,
+            "sdev" => inotify.sdev = utils::convert_radix_str_to_u64(value, 16)?,
+            "mask" => inotify.mask = utils::convert_radix_str_to_u64(value, 16)?,
+            "ignored_mask" => inotify.ignored_mask = utils::convert_radix_str_to_u64(value, 16)?,
+            _ => {}
+        }
+    }
+
+    Ok(inotify)
+}
+
+fn parse_epoll(line: &str) -> CollectResult<ProcessFdEpoll> {
+    let mut epoll = ProcessFdEpoll::default();
+
+    for (key, value) in parse_key_values(line) {
+        match key {
+            "tfd" => epoll.tfd = utils::convert_str_to_u64(value)?,
+            "events" => epoll.events = utils::convert_radix_str_to_u64(value, 16)?,
+            "data" => epoll.data = utils::convert_radix_str_to_u64(value, 16)?,
+            "pos" => epoll.pos = Some(utils::convert_str_to_u64(value)?),
+            "ino" => epoll.ino = Some(utils::convert_radix_str_to_u64(value, 16)?),
+            "sdev" => epoll.sdev = Some(utils::convert_radix_str_to_u64(value, 16)?),
+            _ => {}
+        }
+    }
+
+    Ok(epoll)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::*;
+    use crate::process::*;
+
+    #[test]
+    fn proc_fds() {
+        let proc_path = Path::new("test_data/fixtures/proc");
+        let sys_proc = collect_from(proc_path, 26231).expect("running proc 26231");
+        let sys_proc_fds = sys_proc.fds().expect("running proc 26231 fds");
+
+        assert_eq!(sys_proc.fd_count().unwrap(), 5);
+        assert_eq!(sys_proc_fds.len(), 5);
+        assert_eq!(
+            sys_proc_fds.iter().map(|f| f.fd).collect::<Vec<u64>>(),
+            [0, 1, 2, 3, 10],
+        );
+        assert_eq!(
+            sys_proc_fds[0].target,
+            ProcessFdTarget::Path(PathBuf::from("../../symlinktargets/abc")),
+        );
+        assert_eq!(
+            sys_proc_fds[4].target,
+            ProcessFdTarget::Path(PathBuf::from("../../symlinktargets/xyz")),
+        );
+
+        let sys_proc = collect_from(proc_path, 26232).expect("running proc 26232");
+        let sys_proc_fds = sys_proc.fds().expect("running proc 26232 fds");
+        assert_eq!(sys_proc_fds.len(), 5);
+        assert_eq!(sys_proc_fds[4].fd, 4);
+        assert!(sys_proc.fdinfo(4).is_err());
+
+        let sys_proc = collect_from(proc_path, 26233).expect("running proc 26233");
+        assert!(sys_proc.fds().is_err());
+        assert!(sys_proc.fd_count().is_err());
+    }
+
+    #[test]
+    fn proc_fd_target() {
+        assert_eq!(
+            ProcessFdTarget::from_link(PathBuf::from("socket:[1234]")),
+            ProcessFdTarget::Socket(1234),
+        );
+        assert_eq!(
+            ProcessFdTarget::from_link(PathBuf::from("pipe:[5678]")),
+            ProcessFdTarget::Pipe(5678),
+        );
+        assert_eq!(
+            ProcessFdTarget::from_link(PathBuf::from("anon_inode:[eventfd]")),
+            ProcessFdTarget::AnonInode("[eventfd]".to_string()),
+        );
+        assert_eq!(
+            ProcessFdTarget::from_link(PathBuf::from("anon_inode:inotify")),
+            ProcessFdTarget::AnonInode("inotify".to_string()),
+        );
+        assert_eq!(
+            ProcessFdTarget::from_link(PathBuf::from("/memfd:shm (deleted)")),
+            ProcessFdTarget::Memfd("shm".to_string()),
+        );
+        assert_eq!(
+            ProcessFdTarget::from_link(PathBuf::from("/dev/null")),
+            ProcessFdTarget::Path(PathBuf::from("/dev/null")),
+        );
+        assert_eq!(
+            ProcessFdTarget::from_link(PathBuf::from("net:[4026531840]")),
+            ProcessFdTarget::Other("net:[4026531840]".to_string()),
+        );
+    }
+
+    #[test]
+    fn proc_fdinfo() {
+        let proc_path = Path::new("test_data/fixtures/proc");
+        let sys_proc = collect_from(proc_path, 26231).expect("running proc 26231");
+
+        let fdinfo = sys_proc.fdinfo(0).expect("running proc 26231 fdinfo 0");
+        assert_eq!(fdinfo.fd, 0);
+        assert_eq!(fdinfo.pos, 0);
+        assert_eq!(fdinfo.flags, 0o2004000);
+        assert_eq!(fdinfo.mnt_id, 13);
+        assert_eq!(fdinfo.eventfd_count, None);
+        assert_eq!(fdinfo.inotify.len(), 3);
+        assert_eq!(fdinfo.inotify[0].wd, 3);
+        assert_eq!(fdinfo.inotify[0].ino, 1);
+        assert_eq!(fdinfo.inotify[0].sdev, 0x34);
+        assert_eq!(fdinfo.inotify[0].mask, 0xfce);
+        assert_eq!(fdinfo.inotify[0].ignored_mask, 0);
+        assert_eq!(fdinfo.inotify[1].ino, 0x1300016);
+        assert_eq!(fdinfo.inotify[1].sdev, 0xfd00002);
+
+        let fdinfo = sys_proc.fdinfo(1).expect("running proc 26231 fdinfo 1");
+        assert_eq!(fdinfo.flags, 0o2004002);
+        assert_eq!(fdinfo.eventfd_count, Some(0));
+        assert!(fdinfo.inotify.is_empty());
+
+        let fdinfo = sys_proc.fdinfo(10).expect("running proc 26231 fdinfo 10");
+        assert_eq!(fdinfo.mnt_id, 9);
+
+        assert!(sys_proc.fdinfo(4).is_err());
+
+        let epoll = parse_epoll(
+            "tfd:        5 events:       1d data: ffffffffffffffff  pos:0 ino:61af sdev:7",
+        )
+        .expect("epoll tfd");
+        assert_eq!(epoll.tfd, 5);
+        assert_eq!(epoll.events, 0x1d);
+        assert_eq!(epoll.data, u64::MAX);
+        assert_eq!(epoll.pos, Some(0));
+        assert_eq!(epoll.ino, Some(0x61af));
+        assert_eq!(epoll.sdev, Some(7));
+    }
+}
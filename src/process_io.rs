@@ -1,9 +1,10 @@
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::{
     error::{CollectResult, MetricError},
+    filesystem,
     process::Process,
-    utils,
 };
 
 enum ProcessIOType {
@@ -33,7 +34,8 @@ impl ProcessIOType {
 }
 
 /// ProcessIO models the content of /proc/\<pid\>/io
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProcessIO {
     pub rchar: u64,
     pub wchar: u64,
@@ -53,49 +55,48 @@ impl ProcessIO {
 impl Process {
     /// returns proc IO stats
     pub fn io(&self) -> CollectResult<ProcessIO> {
-        let mut proc_io = ProcessIO::new();
-        let proc_io_path_str = format!("{:?}", self.path());
-        let proc_io_file = format!("{}/io", proc_io_path_str.replace("\"", ""));
-        for line in utils::read_file_lines(&proc_io_file)? {
-            let item_fields: Vec<&str> = line.trim().split(':').filter(|s| !s.is_empty()).collect();
+        self.scope(|| self.io_from())
+    }
 
-            if item_fields.len() != 2 {
-                return Err(MetricError::InvalidFieldNumberError(
-                    "process io".to_string(),
-                    item_fields.len(),
-                    line,
-                ));
-            }
+    fn io_from(&self) -> CollectResult<ProcessIO> {
+        let content = filesystem::read_to_string(&self.path().join("io"))?;
+        parse_io(&content)
+    }
+}
 
-            let item_value = item_fields[1].trim();
-            match ProcessIOType::from(item_fields[0]) {
-                ProcessIOType::RChar => {
-                    proc_io.rchar = item_value.parse::<u64>().unwrap_or_default()
-                }
-                ProcessIOType::WChar => {
-                    proc_io.wchar = item_value.parse::<u64>().unwrap_or_default()
-                }
-                ProcessIOType::SyscR => {
-                    proc_io.syscr = item_value.parse::<u64>().unwrap_or_default()
-                }
-                ProcessIOType::SyscW => {
-                    proc_io.syscw = item_value.parse::<u64>().unwrap_or_default()
-                }
-                ProcessIOType::ReadBytes => {
-                    proc_io.read_bytes = item_value.parse::<u64>().unwrap_or_default()
-                }
-                ProcessIOType::WriteBytes => {
-                    proc_io.write_bytes = item_value.parse::<u64>().unwrap_or_default()
-                }
-                ProcessIOType::CancelledWriteBytes => {
-                    proc_io.cancelled_write_bytes = item_value.parse::<i64>().unwrap_or_default()
-                }
-                ProcessIOType::Unknown => {}
-            }
+pub(crate) fn parse_io(content: &str) -> CollectResult<ProcessIO> {
+    let mut proc_io = ProcessIO::new();
+    for line in content.lines() {
+        let item_fields: Vec<&str> = line.trim().split(':').filter(|s| !s.is_empty()).collect();
+
+        if item_fields.len() != 2 {
+            return Err(MetricError::InvalidFieldNumberError(
+                "process io".to_string(),
+                item_fields.len(),
+                line.to_string(),
+            ));
         }
 
-        Ok(proc_io)
+        let item_value = item_fields[1].trim();
+        match ProcessIOType::from(item_fields[0]) {
+            ProcessIOType::RChar => proc_io.rchar = item_value.parse::<u64>().unwrap_or_default(),
+            ProcessIOType::WChar => proc_io.wchar = item_value.parse::<u64>().unwrap_or_default(),
+            ProcessIOType::SyscR => proc_io.syscr = item_value.parse::<u64>().unwrap_or_default(),
+            ProcessIOType::SyscW => proc_io.syscw = item_value.parse::<u64>().unwrap_or_default(),
+            ProcessIOType::ReadBytes => {
+                proc_io.read_bytes = item_value.parse::<u64>().unwrap_or_default()
+            }
+            ProcessIOType::WriteBytes => {
+                proc_io.write_bytes = item_value.parse::<u64>().unwrap_or_default()
+            }
+            ProcessIOType::CancelledWriteBytes => {
+                proc_io.cancelled_write_bytes = item_value.parse::<i64>().unwrap_or_default()
+            }
+            ProcessIOType::Unknown => {}
+        }
     }
+
+    Ok(proc_io)
 }
 
 #[cfg(test)]
@@ -1,10 +1,7 @@
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-use crate::{
-    error::{CollectResult, MetricError},
-    process::Process,
-    utils,
-};
+use crate::{error::CollectResult, process::Process, utils};
 
 const MAX_LIMIT_SIZE: u64 = 18446744073709551615;
 
@@ -53,7 +50,8 @@ impl ProcessLimitsType {
 }
 
 /// ProcessLimits represents the soft limits for each of the process's resource limits
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProcessLimits {
     pub cpu_time: u64,
     pub file_size: u64,
@@ -82,48 +80,52 @@ impl ProcessLimits {
 impl Process {
     /// limits returns the current soft limits of the process
     pub fn limits(&self) -> CollectResult<ProcessLimits> {
-        let mut proc_limits = ProcessLimits::new();
-        let proc_limits_path_str = format!("{:?}", self.path());
-        let proc_limits_file = format!("{}/limits", proc_limits_path_str.replace("\"", ""));
+        self.scope(|| self.limits_from())
+    }
 
-        let re = match regex::Regex::new(r"(Max \w+\s{0,1}?\w*\s{0,1}\w*)\s{2,}(\w+)\s+(\w+)") {
-            Ok(r) => r,
-            Err(e) => return Err(MetricError::RegexError(e)),
-        };
+    fn limits_from(&self) -> CollectResult<ProcessLimits> {
+        let mut proc_limits = ProcessLimits::new();
+        let proc_limits_file = self.path().join("limits");
 
         for line in utils::read_file_lines(&proc_limits_file)? {
-            if line.starts_with("Limit") {
+            // the limit name is padded with at least two spaces, the soft
+            // limit is the first of the following columns
+            let Some((item, values)) = line.split_once("  ") else {
+                continue;
+            };
+
+            if !item.starts_with("Max ") {
                 continue;
             }
 
-            for (_, [item, soft_limit, _]) in re.captures_iter(&line).map(|c| c.extract()) {
-                let mut soft_value: u64 = MAX_LIMIT_SIZE;
-
-                if soft_limit != "unlimited" {
-                    soft_value = soft_limit.parse::<u64>().unwrap_or_default();
-                }
-
-                match ProcessLimitsType::from(item.trim()) {
-                    ProcessLimitsType::CPUTime => proc_limits.cpu_time = soft_value,
-                    ProcessLimitsType::FileSize => proc_limits.file_size = soft_value,
-                    ProcessLimitsType::DataSize => proc_limits.data_size = soft_value,
-                    ProcessLimitsType::StackSize => proc_limits.stack_size = soft_value,
-                    ProcessLimitsType::CoreFileSize => proc_limits.core_file_size = soft_value,
-                    ProcessLimitsType::ResidentSet => proc_limits.resident_set = soft_value,
-                    ProcessLimitsType::Processes => proc_limits.processes = soft_value,
-                    ProcessLimitsType::OpenFiles => proc_limits.open_files = soft_value,
-                    ProcessLimitsType::LockedMemery => proc_limits.locked_memory = soft_value,
-                    ProcessLimitsType::AddressSpace => proc_limits.address_space = soft_value,
-                    ProcessLimitsType::FileLocks => proc_limits.file_locks = soft_value,
-                    ProcessLimitsType::PendingSignals => proc_limits.pending_signals = soft_value,
-                    ProcessLimitsType::MsqqueueSize => proc_limits.msgqueue_size = soft_value,
-                    ProcessLimitsType::NicePriority => proc_limits.nice_priority = soft_value,
-                    ProcessLimitsType::RealtimePriority => {
-                        proc_limits.realtime_priority = soft_value
-                    }
-                    ProcessLimitsType::RealtimeTimeout => proc_limits.realtime_timeout = soft_value,
-                    ProcessLimitsType::Unknown => {}
-                }
+            let Some(soft_limit) = values.split_whitespace().next() else {
+                continue;
+            };
+
+            let mut soft_value: u64 = MAX_LIMIT_SIZE;
+
+            if soft_limit != "unlimited" {
+                soft_value = soft_limit.parse::<u64>().unwrap_or_default();
+            }
+
+            match ProcessLimitsType::from(item.trim()) {
+                ProcessLimitsType::CPUTime => proc_limits.cpu_time = soft_value,
+                ProcessLimitsType::FileSize => proc_limits.file_size = soft_value,
+                ProcessLimitsType::DataSize => proc_limits.data_size = soft_value,
+                ProcessLimitsType::StackSize => proc_limits.stack_size = soft_value,
+                ProcessLimitsType::CoreFileSize => proc_limits.core_file_size = soft_value,
+                ProcessLimitsType::ResidentSet => proc_limits.resident_set = soft_value,
+                ProcessLimitsType::Processes => proc_limits.processes = soft_value,
+                ProcessLimitsType::OpenFiles => proc_limits.open_files = soft_value,
+                ProcessLimitsType::LockedMemery => proc_limits.locked_memory = soft_value,
+                ProcessLimitsType::AddressSpace => proc_limits.address_space = soft_value,
+                ProcessLimitsType::FileLocks => proc_limits.file_locks = soft_value,
+                ProcessLimitsType::PendingSignals => proc_limits.pending_signals = soft_value,
+                ProcessLimitsType::MsqqueueSize => proc_limits.msgqueue_size = soft_value,
+                ProcessLimitsType::NicePriority => proc_limits.nice_priority = soft_value,
+                ProcessLimitsType::RealtimePriority => proc_limits.realtime_priority = soft_value,
+                ProcessLimitsType::RealtimeTimeout => proc_limits.realtime_timeout = soft_value,
+                ProcessLimitsType::Unknown => {}
             }
         }
 
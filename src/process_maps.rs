@@ -0,0 +1,438 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{CollectResult, MetricError},
+    process::Process,
+    utils,
+};
+
+/// ProcessMapPermissions holds the access permissions of a memory mapping
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessMapPermissions {
+    pub read: bool,
+    pub write: bool,
+    pub execute: bool,
+    pub shared: bool,
+    pub private: bool,
+}
+
+/// ProcessMap models a single memory mapping line from /proc/\<pid\>/maps
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessMap {
+    pub start_address: u64,
+    pub end_address: u64,
+    pub perms: ProcessMapPermissions,
+    pub offset: u64,
+    pub dev_major: u64,
+    pub dev_minor: u64,
+    pub inode: u64,
+    pub pathname: Option<String>,
+}
+
+impl ProcessMap {
+    fn new() -> Self {
+        Default::default()
+    }
+
+    /// returns the size of the mapping in bytes
+    pub fn size(&self) -> u64 {
+        self.end_address.saturating_sub(self.start_address)
+    }
+}
+
+/// ProcessSmap models a single memory mapping from /proc/\<pid\>/smaps
+/// (or the accumulated mappings from /proc/\<pid\>/smaps_rollup), memory values are in bytes
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessSmap {
+    pub map: ProcessMap,
+    pub size: Option<u64>,
+    pub kernel_page_size: Option<u64>,
+    pub mmu_page_size: Option<u64>,
+    pub rss: Option<u64>,
+    pub pss: Option<u64>,
+    pub pss_dirty: Option<u64>,
+    pub pss_anon: Option<u64>,
+    pub pss_file: Option<u64>,
+    pub pss_shmem: Option<u64>,
+    pub shared_clean: Option<u64>,
+    pub shared_dirty: Option<u64>,
+    pub private_clean: Option<u64>,
+    pub private_dirty: Option<u64>,
+    pub referenced: Option<u64>,
+    pub anonymous: Option<u64>,
+    pub ksm: Option<u64>,
+    pub lazy_free: Option<u64>,
+    pub anon_huge_pages: Option<u64>,
+    pub shmem_pmd_mapped: Option<u64>,
+    pub file_pmd_mapped: Option<u64>,
+    pub shared_hugetlb: Option<u64>,
+    pub private_hugetlb: Option<u64>,
+    pub swap: Option<u64>,
+    pub swap_pss: Option<u64>,
+    pub locked: Option<u64>,
+
+    /// 1 if the mapping is eligible for transparent huge pages
+    pub thp_eligible: Option<u64>,
+
+    /// memory protection key of the mapping
+    pub protection_key: Option<u64>,
+
+    pub vm_flags: Vec<String>,
+}
+
+impl ProcessSmap {
+    fn new(map: ProcessMap) -> Self {
+        Self {
+            map,
+            ..Default::default()
+        }
+    }
+
+    /// returns the shared (clean and dirty) memory in bytes
+    pub fn shared(&self) -> u64 {
+        self.shared_clean.unwrap_or_default() + self.shared_dirty.unwrap_or_default()
+    }
+
+    /// returns the private (clean and dirty) memory in bytes
+    pub fn private(&self) -> u64 {
+        self.private_clean.unwrap_or_default() + self.private_dirty.unwrap_or_default()
+    }
+}
+
+impl Process {
+    /// returns the memory mappings of the process from /proc/\<pid\>/maps
+    pub fn maps(&self) -> CollectResult<Vec<ProcessMap>> {
+        self.scope(|| self.maps_from())
+    }
+
+    fn maps_from(&self) -> CollectResult<Vec<ProcessMap>> {
+        let mut proc_maps = Vec::new();
+
+        let mut proc_maps_file = self.path();
+        proc_maps_file.push("maps");
+
+        for line in utils::read_file_lines(&proc_maps_file)? {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            proc_maps.push(parse_map(&line)?);
+        }
+
+        Ok(proc_maps)
+    }
+
+    /// returns the memory mappings of the process with their memory
+    /// consumption from /proc/\<pid\>/smaps
+    pub fn smaps(&self) -> CollectResult<Vec<ProcessSmap>> {
+        self.scope(|| self.smaps_from())
+    }
+
+    fn smaps_from(&self) -> CollectResult<Vec<ProcessSmap>> {
+        let mut proc_smaps_file = self.path();
+        proc_smaps_file.push("smaps");
+
+        parse_smaps(utils::read_file_lines(&proc_smaps_file)?)
+    }
+
+    /// returns the accumulated memory consumption of all the process mappings
+    /// from /proc/\<pid\>/smaps_rollup
+    pub fn smaps_rollup(&self) -> CollectResult<ProcessSmap> {
+        self.scope(|| self.smaps_rollup_from())
+    }
+
+    fn smaps_rollup_from(&self) -> CollectResult<ProcessSmap> {
+        let mut proc_smaps_file = self.path();
+        proc_smaps_file.push("smaps_rollup");
+
+        match parse_smaps(utils::read_file_lines(&proc_smaps_file)?)?.pop() {
+            Some(rollup) => Ok(rollup),
+            None => Err(MetricError::ParseError(format!(
+                "empty process smaps rollup {:?}",
+                proc_smaps_file
+            ))),
+        }
+    }
+}
+
+fn parse_map(line: &str) -> CollectResult<ProcessMap> {
+    let mut map_fields: Vec<&str> = Vec::new();
+    let mut remaining = line;
+
+    // the pathname is the remaining of the line and may contain spaces
+    while map_fields.len() < 5 {
+        remaining = remaining.trim_start();
+        if remaining.is_empty() {
+            break;
+        }
+
+        let field_end = remaining
+            .find(char::is_whitespace)
+            .unwrap_or(remaining.len());
+        map_fields.push(&remaining[..field_end]);
+        remaining = &remaining[field_end..];
+    }
+
+    if map_fields.len() != 5 {
+        return Err(MetricError::InvalidFieldNumberError(
+            "process map".to_string(),
+            map_fields.len(),
+            line.to_string(),
+        ));
+    }
+
+    let mut proc_map = ProcessMap::new();
+
+    let address_fields: Vec<&str> = map_fields[0].split('-').collect();
+    if address_fields.len() != 2 {
+        return Err(MetricError::InvalidFieldNumberError(
+            "process map address".to_string(),
+            address_fields.len(),
+            line.to_string(),
+        ));
+    }
+
+    proc_map.start_address = utils::convert_radix_str_to_u64(address_fields[0], 16)?;
+    proc_map.end_address = utils::convert_radix_str_to_u64(address_fields[1], 16)?;
+
+    let perms: Vec<char> = map_fields[1].chars().collect();
+    if perms.len() != 4 {
+        return Err(MetricError::ParseError(format!(
+            "invalid process map permissions: {}",
+            line
+        )));
+    }
+
+    proc_map.perms.read = perms[0] == 'r';
+    proc_map.perms.write = perms[1] == 'w';
+    proc_map.perms.execute = perms[2] == 'x';
+    proc_map.perms.shared = perms[3] == 's';
+    proc_map.perms.private = perms[3] == 'p';
+
+    proc_map.offset = utils::convert_radix_str_to_u64(map_fields[2], 16)?;
+
+    let device_fields: Vec<&str> = map_fields[3].split(':').collect();
+    if device_fields.len() != 2 {
+        return Err(MetricError::InvalidFieldNumberError(
+            "process map device".to_string(),
+            device_fields.len(),
+            line.to_string(),
+        ));
+    }
+
+    proc_map.dev_major = utils::convert_radix_str_to_u64(device_fields[0], 16)?;
+    proc_map.dev_minor = utils::convert_radix_str_to_u64(device_fields[1], 16)?;
+    proc_map.inode = utils::convert_str_to_u64(map_fields[4])?;
+
+    let pathname = remaining.trim();
+    if !pathname.is_empty() {
+        proc_map.pathname = Some(pathname.to_string());
+    }
+
+    Ok(proc_map)
+}
+
+fn parse_smaps(lines: Vec<String>) -> CollectResult<Vec<ProcessSmap>> {
+    let mut proc_smaps: Vec<ProcessSmap> = Vec::new();
+
+    for line in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let first_field = line.split_whitespace().next().unwrap_or_default();
+
+        // mapping header lines start with the address range, attribute lines with "Name:"
+        if !first_field.ends_with(':') {
+            proc_smaps.push(ProcessSmap::new(parse_map(&line)?));
+            continue;
+        }
+
+        let proc_smap = match proc_smaps.last_mut() {
+            Some(s) => s,
+            None => {
+                return Err(MetricError::ParseError(format!(
+                    "process smaps attribute without mapping: {}",
+                    line
+                )))
+            }
+        };
+
+        let item = first_field.trim_end_matches(':');
+        let item_value = line[first_field.len()..].trim();
+
+        if item == "VmFlags" {
+            proc_smap.vm_flags = item_value
+                .split_whitespace()
+                .map(|f| f.to_string())
+                .collect();
+            continue;
+        }
+
+        let value_fields: Vec<&str> = item_value.split_whitespace().collect();
+
+        // unitless attributes (e.g. "THPeligible: 0") hold a plain integer
+        if value_fields.len() == 1 {
+            match item {
+                "THPeligible" => {
+                    proc_smap.thp_eligible = Some(utils::convert_str_to_u64(value_fields[0])?)
+                }
+                "ProtectionKey" => {
+                    proc_smap.protection_key = Some(utils::convert_str_to_u64(value_fields[0])?)
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        if value_fields.len() != 2 {
+            return Err(MetricError::InvalidFieldNumberError(
+                "process smaps".to_string(),
+                value_fields.len(),
+                line,
+            ));
+        }
+
+        let value =
+            utils::convert_to_bytes(utils::convert_str_to_u64(value_fields[0])?, value_fields[1])?;
+
+        match item {
+            "Size" => proc_smap.size = value,
+            "KernelPageSize" => proc_smap.kernel_page_size = value,
+            "MMUPageSize" => proc_smap.mmu_page_size = value,
+            "Rss" => proc_smap.rss = value,
+            "Pss" => proc_smap.pss = value,
+            "Pss_Dirty" => proc_smap.pss_dirty = value,
+            "Pss_Anon" => proc_smap.pss_anon = value,
+            "Pss_File" => proc_smap.pss_file = value,
+            "Pss_Shmem" => proc_smap.pss_shmem = value,
+            "Shared_Clean" => proc_smap.shared_clean = value,
+            "Shared_Dirty" => proc_smap.shared_dirty = value,
+            "Private_Clean" => proc_smap.private_clean = value,
+            "Private_Dirty" => proc_smap.private_dirty = value,
+            "Referenced" => proc_smap.referenced = value,
+            "Anonymous" => proc_smap.anonymous = value,
+            "KSM" => proc_smap.ksm = value,
+            "LazyFree" => proc_smap.lazy_free = value,
+            "AnonHugePages" => proc_smap.anon_huge_pages = value,
+            "ShmemPmdMapped" => proc_smap.shmem_pmd_mapped = value,
+            "FilePmdMapped" => proc_smap.file_pmd_mapped = value,
+            "Shared_Hugetlb" => proc_smap.shared_hugetlb = value,
+            "Private_Hugetlb" => proc_smap.private_hugetlb = value,
+            "Swap" => proc_smap.swap = value,
+            "SwapPss" => proc_smap.swap_pss = value,
+            "Locked" => proc_smap.locked = value,
+            _ => {}
+        }
+    }
+
+    Ok(proc_smaps)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::process::*;
+
+    #[test]
+    fn proc_maps() {
+        let proc_path = Path::new("test_data/fixtures/proc");
+        let sys_proc = collect_from(proc_path, 26232).expect("running proc 26232");
+        let sys_proc_maps = sys_proc.maps().expect("running proc 26232 maps");
+
+        assert_eq!(sys_proc_maps.len(), 9);
+        assert_eq!(sys_proc_maps[0].start_address, 0x55680ae1e000);
+        assert_eq!(sys_proc_maps[0].end_address, 0x55680ae20000);
+        assert_eq!(sys_proc_maps[0].size(), 0x2000);
+        assert!(sys_proc_maps[0].perms.read);
+        assert!(!sys_proc_maps[0].perms.write);
+        assert!(!sys_proc_maps[0].perms.execute);
+        assert!(sys_proc_maps[0].perms.private);
+        assert_eq!(sys_proc_maps[0].offset, 0);
+        assert_eq!(sys_proc_maps[0].dev_major, 0xfd);
+        assert_eq!(sys_proc_maps[0].dev_minor, 0x01);
+        assert_eq!(sys_proc_maps[0].inode, 47316994);
+        assert_eq!(sys_proc_maps[0].pathname.as_deref(), Some("/bin/cat"));
+
+        assert!(sys_proc_maps[1].perms.execute);
+        assert!(sys_proc_maps[1].perms.shared);
+        assert_eq!(sys_proc_maps[1].offset, 0xa000);
+        assert_eq!(sys_proc_maps[2].pathname.as_deref(), Some("[heap]"));
+        assert_eq!(sys_proc_maps[8].start_address, 0xffffffffff600000);
+
+        let sys_proc = collect_from(proc_path, 26234).expect("running proc 26234");
+        let sys_proc_maps = sys_proc.maps().expect("running proc 26234 maps");
+
+        assert_eq!(sys_proc_maps.len(), 4);
+        assert_eq!(sys_proc_maps[0].dev_major, 3);
+        assert_eq!(sys_proc_maps[0].pathname.as_deref(), Some("/bin/tcsh"));
+        assert_eq!(sys_proc_maps[2].pathname, None);
+
+        let sys_proc = collect_from(proc_path, 26231).expect("running proc 26231");
+        assert!(sys_proc.maps().is_err());
+    }
+
+    #[test]
+    fn proc_smaps() {
+        let proc_path = Path::new("test_data/fixtures/proc");
+        let sys_proc = collect_from(proc_path, 26231).expect("running proc 26231");
+        let sys_proc_smaps = sys_proc.smaps().expect("running proc 26231 smaps");
+
+        assert_eq!(sys_proc_smaps.len(), 12);
+        assert_eq!(
+            sys_proc_smaps[0].map.pathname.as_deref(),
+            Some("/bin/alertmanager"),
+        );
+        assert_eq!(sys_proc_smaps[0].size, Some(8900 * 1024));
+        assert_eq!(sys_proc_smaps[0].rss, Some(2952 * 1024));
+        assert_eq!(sys_proc_smaps[0].pss, Some(2952 * 1024));
+        assert_eq!(sys_proc_smaps[0].private(), 2952 * 1024);
+        assert_eq!(sys_proc_smaps[0].shared(), 0);
+        assert_eq!(
+            sys_proc_smaps[0].vm_flags,
+            ["rd", "ex", "mr", "mw", "me", "dw", "sd"]
+        );
+        assert_eq!(sys_proc_smaps[2].swap, Some(12 * 1024));
+        assert_eq!(sys_proc_smaps[2].anonymous, Some(92 * 1024));
+        assert_eq!(sys_proc_smaps[3].map.pathname, None);
+        assert_eq!(sys_proc_smaps[0].thp_eligible, Some(0));
+        assert_eq!(sys_proc_smaps[2].thp_eligible, Some(1));
+        assert_eq!(sys_proc_smaps[2].protection_key, Some(0));
+        assert_eq!(sys_proc_smaps[2].locked, Some(0));
+
+        let total_pss: u64 = sys_proc_smaps
+            .iter()
+            .map(|s| s.pss.unwrap_or_default())
+            .sum();
+        assert_eq!(total_pss, 29944 * 1024);
+    }
+
+    #[test]
+    fn proc_smaps_rollup() {
+        let proc_path = Path::new("test_data/fixtures/proc");
+        let sys_proc = collect_from(proc_path, 26231).expect("running proc 26231");
+        let rollup = sys_proc
+            .smaps_rollup()
+            .expect("running proc 26231 smaps_rollup");
+
+        assert_eq!(rollup.map.pathname.as_deref(), Some("[rollup]"));
+        assert_eq!(rollup.rss, Some(29948 * 1024));
+        assert_eq!(rollup.pss, Some(29944 * 1024));
+        assert_eq!(rollup.shared_clean, Some(4 * 1024));
+        assert_eq!(rollup.private_dirty, Some(14396 * 1024));
+        assert_eq!(rollup.anonymous, Some(20756 * 1024));
+        assert_eq!(rollup.lazy_free, Some(5848 * 1024));
+        assert_eq!(rollup.swap, Some(1940 * 1024));
+        assert_eq!(rollup.swap_pss, Some(1940 * 1024));
+        assert_eq!(rollup.locked, Some(0));
+        assert!(rollup.vm_flags.is_empty());
+
+        let sys_proc = collect_from(proc_path, 26232).expect("running proc 26232");
+        assert!(sys_proc.smaps_rollup().is_err());
+    }
+}
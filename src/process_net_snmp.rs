@@ -1,4 +1,5 @@
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::{
     error::{CollectResult, MetricError},
@@ -7,7 +8,8 @@ use crate::{
 };
 
 /// ProcessNetSnmp models the content of /proc/\<pid\>/net/snmp
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProcessNetSnmp {
     pub ip: Ip,
     pub icmp: Icmp,
@@ -17,7 +19,8 @@ pub struct ProcessNetSnmp {
     pub upd_lite: UdpLite,
 }
 
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ip {
     pub forwarding: Option<i64>,
     pub default_ttl: Option<i64>,
@@ -41,7 +44,8 @@ pub struct Ip {
     pub out_transmits: Option<i64>,
 }
 
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Icmp {
     pub in_msgs: Option<i64>,
     pub in_errors: Option<i64>,
@@ -74,13 +78,15 @@ pub struct Icmp {
     pub out_addr_mask_reps: Option<i64>,
 }
 
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IcmpMsg {
     pub in_type3: Option<i64>,
     pub out_type3: Option<i64>,
 }
 
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Tcp {
     pub rto_algorithm: Option<i64>,
     pub rto_min: Option<i64>,
@@ -99,7 +105,8 @@ pub struct Tcp {
     pub in_csum_errors: Option<i64>,
 }
 
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Udp {
     pub in_datagrams: Option<i64>,
     pub no_ports: Option<i64>,
@@ -112,7 +119,8 @@ pub struct Udp {
     pub mem_errors: Option<i64>,
 }
 
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UdpLite {
     pub in_datagrams: Option<i64>,
     pub no_ports: Option<i64>,
@@ -177,6 +185,10 @@ impl UdpLite {
 impl Process {
     /// net_snmp returns the current net/snmp stats of the process
     pub fn net_snmp(&self) -> CollectResult<ProcessNetSnmp> {
+        self.scope(|| self.net_snmp_from())
+    }
+
+    fn net_snmp_from(&self) -> CollectResult<ProcessNetSnmp> {
         let mut proc_netsnmp = ProcessNetSnmp::new();
 
         let proc_netsnmp_path_str = format!("{:?}/net", self.path());
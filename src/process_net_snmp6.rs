@@ -1,4 +1,5 @@
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::{
     error::{CollectResult, MetricError},
@@ -7,7 +8,8 @@ use crate::{
 };
 
 /// ProcessNetSnmp6 models the content of /proc/\<pid\>/net/snmp6
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProcessNetSnmp6 {
     pub ip6_in_receives: Option<i64>,
     pub ip6_in_hdr_errors: Option<i64>,
@@ -115,6 +117,10 @@ impl ProcessNetSnmp6 {
 impl Process {
     /// net_snmp6 returns the current net/snmp6 stats of the process
     pub fn net_snmp6(&self) -> CollectResult<ProcessNetSnmp6> {
+        self.scope(|| self.net_snmp6_from())
+    }
+
+    fn net_snmp6_from(&self) -> CollectResult<ProcessNetSnmp6> {
         let mut proc_netsnmp6 = ProcessNetSnmp6::new();
 
         let proc_netsnmp6_path_str = format!("{:?}/net", self.path());
@@ -1,4 +1,5 @@
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::{
     error::{CollectResult, MetricError},
@@ -7,14 +8,15 @@ use crate::{
 };
 
 /// ProcessNetstat models the content of /proc/\<pid\>/net/netstat
-#[derive(Debug, Serialize, Clone, Default)]
-
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProcessNetstat {
     pub tcp_ext: TcpExt,
     pub ip_ext: IpExt,
 }
 
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TcpExt {
     pub syn_cookies_sent: Option<i64>,
     pub syn_cookies_recv: Option<i64>,
@@ -149,7 +151,8 @@ pub struct TcpExt {
     pub tcp_ao_dropped_icmps: Option<i64>,
 }
 
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IpExt {
     pub in_no_routes: Option<i64>,
     pub in_truncated_pkts: Option<i64>,
@@ -195,6 +198,10 @@ impl IpExt {
 impl Process {
     /// netstat returns the current netstat of the process
     pub fn netstat(&self) -> CollectResult<ProcessNetstat> {
+        self.scope(|| self.netstat_from())
+    }
+
+    fn netstat_from(&self) -> CollectResult<ProcessNetstat> {
         let mut proc_netstat = ProcessNetstat::new();
         let proc_netstat_path_str = format!("{:?}/net", self.path());
         let proc_netstat_file = format!("{}/netstat", proc_netstat_path_str.replace("\"", ""));
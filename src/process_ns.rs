@@ -1,15 +1,18 @@
-use std::{collections::HashMap, fs::read_link};
+use std::collections::HashMap;
 
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::{
     error::{CollectResult, MetricError},
+    filesystem,
     process::Process,
     utils,
 };
 
 /// ProcessNamespace represents a single namespace of a process
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProcessNamespace {
     pub ns_type: String,
     pub inode: u32,
@@ -24,6 +27,10 @@ impl ProcessNamespace {
 impl Process {
     /// Namespaces reads from /proc/\<pid\>/ns/* to get the namespaces of which the process is a member
     pub fn namespaces(&self) -> CollectResult<HashMap<String, ProcessNamespace>> {
+        self.scope(|| self.namespaces_from())
+    }
+
+    fn namespaces_from(&self) -> CollectResult<HashMap<String, ProcessNamespace>> {
         let mut proc_namespaces: HashMap<String, ProcessNamespace> = HashMap::new();
 
         let mut proc_ns_path = self.path();
@@ -33,7 +40,7 @@ impl Process {
             let mut ns_item_path = proc_ns_path.clone();
             ns_item_path.push(&ns_item);
 
-            match read_link(&ns_item_path) {
+            match filesystem::read_link(&ns_item_path) {
                 Ok(c) => {
                     let item_fields: Vec<&str> = c
                         .to_str()
@@ -0,0 +1,90 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{CollectResult, MetricError},
+    filesystem,
+    process::Process,
+    utils,
+};
+
+/// ProcessSchedstat models the content of /proc/\<pid\>/schedstat
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessSchedstat {
+    /// time spent on the cpu in nanoseconds
+    pub running_nanoseconds: u64,
+
+    /// time spent waiting on a runqueue in nanoseconds
+    pub waiting_nanoseconds: u64,
+
+    /// number of timeslices run on this cpu
+    pub run_timeslices: u64,
+}
+
+impl ProcessSchedstat {
+    fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Process {
+    /// returns the process scheduler statistics from /proc/\<pid\>/schedstat
+    pub fn schedstat(&self) -> CollectResult<ProcessSchedstat> {
+        self.scope(|| self.schedstat_from())
+    }
+
+    fn schedstat_from(&self) -> CollectResult<ProcessSchedstat> {
+        let content = filesystem::read_to_string(&self.path().join("schedstat"))?;
+
+        parse_schedstat(&content)
+    }
+}
+
+pub(crate) fn parse_schedstat(content: &str) -> CollectResult<ProcessSchedstat> {
+    let mut proc_schedstat = ProcessSchedstat::new();
+
+    let line = content.lines().next().unwrap_or_default();
+    let fields: Vec<&str> = line.split_whitespace().collect();
+
+    if fields.len() != 3 {
+        return Err(MetricError::InvalidFieldNumberError(
+            "process schedstat".to_string(),
+            fields.len(),
+            line.to_string(),
+        ));
+    }
+
+    proc_schedstat.running_nanoseconds = utils::convert_str_to_u64(fields[0])?;
+    proc_schedstat.waiting_nanoseconds = utils::convert_str_to_u64(fields[1])?;
+    proc_schedstat.run_timeslices = utils::convert_str_to_u64(fields[2])?;
+
+    Ok(proc_schedstat)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{error::ErrorKind, process::*};
+
+    #[test]
+    fn proc_schedstat() {
+        let proc_path = Path::new("test_data/fixtures/proc");
+        let sys_proc = collect_from(proc_path, 26231).expect("running proc 26231");
+        let sys_proc_schedstat = sys_proc.schedstat().expect("running proc 26231 schedstat");
+
+        assert_eq!(sys_proc_schedstat.running_nanoseconds, 411605849);
+        assert_eq!(sys_proc_schedstat.waiting_nanoseconds, 93680043);
+        assert_eq!(sys_proc_schedstat.run_timeslices, 79);
+
+        let sys_proc = collect_from(proc_path, 26233).expect("running proc 26233");
+        assert!(sys_proc.schedstat().is_err());
+
+        let sys_proc = collect_from(proc_path, 26232).expect("running proc 26232");
+        assert_eq!(
+            sys_proc.schedstat().unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+    }
+}
@@ -0,0 +1,278 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{CollectResult, MetricError},
+    filesystem,
+    process::Process,
+    utils,
+};
+
+// number of fields following the command name which are available on all supported kernels
+const STAT_MIN_FIELDS: usize = 37;
+
+/// ProcessStat models the content of /proc/\<pid\>/stat
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessStat {
+    /// process id
+    pub pid: usize,
+
+    /// filename of the executable
+    pub comm: String,
+
+    /// process state (R, S, D, Z, T, t, X, I, ...)
+    pub state: String,
+
+    /// pid of the parent process
+    pub ppid: usize,
+
+    /// process group id of the process
+    pub pgrp: i64,
+
+    /// session id of the process
+    pub session: i64,
+
+    /// controlling terminal of the process
+    pub tty: i64,
+
+    /// id of the foreground process group of the controlling terminal
+    pub tpgid: i64,
+
+    /// kernel flags word of the process
+    pub flags: u64,
+
+    /// number of minor faults the process has made
+    pub minflt: u64,
+
+    /// number of minor faults that the process's waited-for children have made
+    pub cminflt: u64,
+
+    /// number of major faults the process has made
+    pub majflt: u64,
+
+    /// number of major faults that the process's waited-for children have made
+    pub cmajflt: u64,
+
+    /// amount of time (clock ticks) the process has been scheduled in user mode
+    pub utime: u64,
+
+    /// amount of time (clock ticks) the process has been scheduled in kernel mode
+    pub stime: u64,
+
+    /// amount of time (clock ticks) the process's waited-for children have been scheduled in user mode
+    pub cutime: i64,
+
+    /// amount of time (clock ticks) the process's waited-for children have been scheduled in kernel mode
+    pub cstime: i64,
+
+    /// scheduling priority of the process
+    pub priority: i64,
+
+    /// nice value of the process
+    pub nice: i64,
+
+    /// number of threads in the process
+    pub num_threads: i64,
+
+    /// time (clock ticks) the process started after system boot
+    pub starttime: u64,
+
+    /// virtual memory size in bytes
+    pub vsize: u64,
+
+    /// resident set size in pages
+    pub rss: i64,
+
+    /// soft limit in bytes on the rss of the process
+    pub rsslim: u64,
+
+    /// signal to be sent to parent when the process dies
+    pub exit_signal: i64,
+
+    /// CPU number the process last executed on
+    pub processor: i64,
+
+    /// real-time scheduling priority
+    pub rt_priority: Option<u64>,
+
+    /// scheduling policy
+    pub policy: Option<u64>,
+
+    /// aggregated block I/O delays (clock ticks)
+    pub delayacct_blkio_ticks: Option<u64>,
+
+    /// guest time (clock ticks) of the process
+    pub guest_time: Option<u64>,
+
+    /// guest time (clock ticks) of the process's children
+    pub cguest_time: Option<i64>,
+}
+
+impl ProcessStat {
+    fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Process {
+    /// returns the process status information from /proc/\<pid\>/stat
+    pub fn stat(&self) -> CollectResult<ProcessStat> {
+        self.scope(|| self.stat_from())
+    }
+
+    fn stat_from(&self) -> CollectResult<ProcessStat> {
+        let content = filesystem::read_to_string(&self.path().join("stat"))?;
+        let line = content.lines().next().unwrap_or_default();
+
+        parse_stat(line)
+    }
+}
+
+pub(crate) fn parse_stat(line: &str) -> CollectResult<ProcessStat> {
+    let mut proc_stat = ProcessStat::new();
+
+    // the command name may contain spaces and parentheses, it is delimited by the
+    // first opening and the last closing parenthesis
+    let (comm_start, comm_end) = match (line.find('('), line.rfind(')')) {
+        (Some(start), Some(end)) if start < end => (start, end),
+        _ => {
+            return Err(MetricError::ParseError(format!(
+                "invalid process stat comm: {}",
+                line
+            )))
+        }
+    };
+
+    proc_stat.pid = utils::convert_str_to_u64(line[..comm_start].trim())? as usize;
+    proc_stat.comm = line[comm_start + 1..comm_end].to_string();
+
+    let fields: Vec<&str> = line[comm_end + 1..].split_whitespace().collect();
+
+    if fields.len() < STAT_MIN_FIELDS {
+        return Err(MetricError::InvalidFieldNumberError(
+            "process stat".to_string(),
+            fields.len(),
+            line.to_string(),
+        ));
+    }
+
+    proc_stat.state = fields[0].to_string();
+    proc_stat.ppid = utils::convert_str_to_u64(fields[1])? as usize;
+    proc_stat.pgrp = utils::convert_str_to_i64(fields[2])?;
+    proc_stat.session = utils::convert_str_to_i64(fields[3])?;
+    proc_stat.tty = utils::convert_str_to_i64(fields[4])?;
+    proc_stat.tpgid = utils::convert_str_to_i64(fields[5])?;
+    proc_stat.flags = utils::convert_str_to_u64(fields[6])?;
+    proc_stat.minflt = utils::convert_str_to_u64(fields[7])?;
+    proc_stat.cminflt = utils::convert_str_to_u64(fields[8])?;
+    proc_stat.majflt = utils::convert_str_to_u64(fields[9])?;
+    proc_stat.cmajflt = utils::convert_str_to_u64(fields[10])?;
+    proc_stat.utime = utils::convert_str_to_u64(fields[11])?;
+    proc_stat.stime = utils::convert_str_to_u64(fields[12])?;
+    proc_stat.cutime = utils::convert_str_to_i64(fields[13])?;
+    proc_stat.cstime = utils::convert_str_to_i64(fields[14])?;
+    proc_stat.priority = utils::convert_str_to_i64(fields[15])?;
+    proc_stat.nice = utils::convert_str_to_i64(fields[16])?;
+    proc_stat.num_threads = utils::convert_str_to_i64(fields[17])?;
+    // fields[18] is itrealvalue which is always 0 since kernel 2.6.17
+    proc_stat.starttime = utils::convert_str_to_u64(fields[19])?;
+    proc_stat.vsize = utils::convert_str_to_u64(fields[20])?;
+    proc_stat.rss = utils::convert_str_to_i64(fields[21])?;
+    proc_stat.rsslim = utils::convert_str_to_u64(fields[22])?;
+    proc_stat.exit_signal = utils::convert_str_to_i64(fields[35])?;
+    proc_stat.processor = utils::convert_str_to_i64(fields[36])?;
+
+    if let Some(value) = fields.get(37) {
+        proc_stat.rt_priority = Some(utils::convert_str_to_u64(value)?);
+    }
+
+    if let Some(value) = fields.get(38) {
+        proc_stat.policy = Some(utils::convert_str_to_u64(value)?);
+    }
+
+    if let Some(value) = fields.get(39) {
+        proc_stat.delayacct_blkio_ticks = Some(utils::convert_str_to_u64(value)?);
+    }
+
+    if let Some(value) = fields.get(40) {
+        proc_stat.guest_time = Some(utils::convert_str_to_u64(value)?);
+    }
+
+    if let Some(value) = fields.get(41) {
+        proc_stat.cguest_time = Some(utils::convert_str_to_i64(value)?);
+    }
+
+    Ok(proc_stat)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{error::ErrorKind, process::*};
+
+    #[test]
+    fn proc_stat() {
+        let proc_path = Path::new("test_data/fixtures/proc");
+        let sys_proc = collect_from(proc_path, 26231).expect("running proc 26231");
+        let sys_proc_stat = sys_proc.stat().expect("running proc 26231 stat");
+
+        assert_eq!(sys_proc_stat.pid, 26231);
+        assert_eq!(sys_proc_stat.comm, "vim");
+        assert_eq!(sys_proc_stat.state, "R");
+        assert_eq!(sys_proc_stat.ppid, 5392);
+        assert_eq!(sys_proc_stat.pgrp, 7446);
+        assert_eq!(sys_proc_stat.session, 5392);
+        assert_eq!(sys_proc_stat.tty, 34835);
+        assert_eq!(sys_proc_stat.tpgid, 7446);
+        assert_eq!(sys_proc_stat.flags, 4218880);
+        assert_eq!(sys_proc_stat.minflt, 32533);
+        assert_eq!(sys_proc_stat.cminflt, 309516);
+        assert_eq!(sys_proc_stat.majflt, 26);
+        assert_eq!(sys_proc_stat.cmajflt, 82);
+        assert_eq!(sys_proc_stat.utime, 1677);
+        assert_eq!(sys_proc_stat.stime, 44);
+        assert_eq!(sys_proc_stat.cutime, 158);
+        assert_eq!(sys_proc_stat.cstime, 99);
+        assert_eq!(sys_proc_stat.priority, 20);
+        assert_eq!(sys_proc_stat.nice, 0);
+        assert_eq!(sys_proc_stat.num_threads, 1);
+        assert_eq!(sys_proc_stat.starttime, 82375);
+        assert_eq!(sys_proc_stat.vsize, 56274944);
+        assert_eq!(sys_proc_stat.rss, 1981);
+        assert_eq!(sys_proc_stat.rsslim, 18446744073709551615);
+        assert_eq!(sys_proc_stat.exit_signal, 17);
+        assert_eq!(sys_proc_stat.processor, 0);
+        assert_eq!(sys_proc_stat.rt_priority, Some(0));
+        assert_eq!(sys_proc_stat.policy, Some(0));
+        assert_eq!(sys_proc_stat.delayacct_blkio_ticks, Some(31));
+        assert_eq!(sys_proc_stat.guest_time, Some(0));
+        assert_eq!(sys_proc_stat.cguest_time, Some(0));
+
+        let sys_proc = collect_from(proc_path, 584).expect("running proc 584");
+        let sys_proc_stat = sys_proc.stat().expect("running proc 584 stat");
+
+        assert_eq!(sys_proc_stat.pid, 1020);
+        assert_eq!(sys_proc_stat.comm, "(a b ) ( c d) ");
+        assert_eq!(sys_proc_stat.state, "R");
+        assert_eq!(sys_proc_stat.ppid, 28378);
+        assert_eq!(sys_proc_stat.starttime, 10839175);
+        assert_eq!(sys_proc_stat.vsize, 10395648);
+        assert_eq!(sys_proc_stat.rss, 155);
+        assert_eq!(sys_proc_stat.processor, 5);
+
+        let sys_proc = collect_from(proc_path, 27079).expect("running proc 27079");
+        let sys_proc_stat = sys_proc.stat().expect("running proc 27079 stat");
+
+        assert_eq!(sys_proc_stat.comm, "pthread_load");
+        assert_eq!(sys_proc_stat.state, "S");
+        assert_eq!(sys_proc_stat.ppid, 1);
+        assert_eq!(sys_proc_stat.utime, 58125);
+        assert_eq!(sys_proc_stat.num_threads, 5);
+        assert_eq!(sys_proc_stat.processor, 2);
+
+        let sys_proc = collect_from(proc_path, 26234).expect("running proc 26234");
+        assert_eq!(sys_proc.stat().unwrap_err().kind(), ErrorKind::NotFound);
+    }
+}
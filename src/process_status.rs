@@ -0,0 +1,402 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{CollectResult, MetricError},
+    filesystem,
+    process::Process,
+    utils,
+};
+
+// linux capability names indexed by capability number (see include/uapi/linux/capability.h)
+const CAPABILITY_NAMES: [&str; 41] = [
+    "CAP_CHOWN",
+    "CAP_DAC_OVERRIDE",
+    "CAP_DAC_READ_SEARCH",
+    "CAP_FOWNER",
+    "CAP_FSETID",
+    "CAP_KILL",
+    "CAP_SETGID",
+    "CAP_SETUID",
+    "CAP_SETPCAP",
+    "CAP_LINUX_IMMUTABLE",
+    "CAP_NET_BIND_SERVICE",
+    "CAP_NET_BROADCAST",
+    "CAP_NET_ADMIN",
+    "CAP_NET_RAW",
+    "CAP_IPC_LOCK",
+    "CAP_IPC_OWNER",
+    "CAP_SYS_MODULE",
+    "CAP_SYS_RAWIO",
+    "CAP_SYS_CHROOT",
+    "CAP_SYS_PTRACE",
+    "CAP_SYS_PACCT",
+    "CAP_SYS_ADMIN",
+    "CAP_SYS_BOOT",
+    "CAP_SYS_NICE",
+    "CAP_SYS_RESOURCE",
+    "CAP_SYS_TIME",
+    "CAP_SYS_TTY_CONFIG",
+    "CAP_MKNOD",
+    "CAP_LEASE",
+    "CAP_AUDIT_WRITE",
+    "CAP_AUDIT_CONTROL",
+    "CAP_SETFCAP",
+    "CAP_MAC_OVERRIDE",
+    "CAP_MAC_ADMIN",
+    "CAP_SYSLOG",
+    "CAP_WAKE_ALARM",
+    "CAP_BLOCK_SUSPEND",
+    "CAP_AUDIT_READ",
+    "CAP_PERFMON",
+    "CAP_BPF",
+    "CAP_CHECKPOINT_RESTORE",
+];
+
+/// ProcessIds holds the real, effective, saved set and filesystem user or group IDs
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessIds {
+    pub real: u64,
+    pub effective: u64,
+    pub saved: u64,
+    pub filesystem: u64,
+}
+
+/// ProcessCapabilities holds a capability set mask and its decoded capability names
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessCapabilities {
+    pub mask: u64,
+    pub names: Vec<String>,
+}
+
+impl ProcessCapabilities {
+    fn from_mask(mask: u64) -> Self {
+        let names = (0..64)
+            .filter(|bit| mask & (1 << bit) != 0)
+            .map(|bit| match CAPABILITY_NAMES.get(bit) {
+                Some(name) => name.to_string(),
+                None => format!("CAP_{}", bit),
+            })
+            .collect();
+
+        Self { mask, names }
+    }
+
+    /// returns true if the set contains the capability (e.g. `CAP_SYS_ADMIN`)
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.iter().any(|n| n == name)
+    }
+}
+
+/// ProcessStatus models the content of /proc/\<pid\>/status, memory values are in bytes
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessStatus {
+    pub name: String,
+    pub umask: Option<u32>,
+    pub state: String,
+    pub tgid: usize,
+    pub ngid: Option<usize>,
+    pub pid: usize,
+    pub ppid: usize,
+    pub tracer_pid: usize,
+    pub uids: ProcessIds,
+    pub gids: ProcessIds,
+    pub fd_size: u64,
+    pub groups: Vec<u64>,
+    pub vm_peak: Option<u64>,
+    pub vm_size: Option<u64>,
+    pub vm_lck: Option<u64>,
+    pub vm_pin: Option<u64>,
+    pub vm_hwm: Option<u64>,
+    pub vm_rss: Option<u64>,
+    pub rss_anon: Option<u64>,
+    pub rss_file: Option<u64>,
+    pub rss_shmem: Option<u64>,
+    pub vm_data: Option<u64>,
+    pub vm_stk: Option<u64>,
+    pub vm_exe: Option<u64>,
+    pub vm_lib: Option<u64>,
+    pub vm_pte: Option<u64>,
+    pub vm_pmd: Option<u64>,
+    pub vm_swap: Option<u64>,
+    pub hugetlb_pages: Option<u64>,
+    pub threads: u64,
+    pub sig_queued: u64,
+    pub sig_queued_limit: u64,
+    pub sig_pnd: u64,
+    pub shd_pnd: u64,
+    pub sig_blk: u64,
+    pub sig_ign: u64,
+    pub sig_cgt: u64,
+    pub cap_inh: ProcessCapabilities,
+    pub cap_prm: ProcessCapabilities,
+    pub cap_eff: ProcessCapabilities,
+    pub cap_bnd: ProcessCapabilities,
+    pub cap_amb: ProcessCapabilities,
+    pub no_new_privs: Option<bool>,
+    pub seccomp: Option<u64>,
+    pub cpus_allowed_list: Vec<u64>,
+    pub mems_allowed_list: Vec<u64>,
+    pub voluntary_ctxt_switches: Option<u64>,
+    pub nonvoluntary_ctxt_switches: Option<u64>,
+}
+
+impl ProcessStatus {
+    fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Process {
+    /// returns the process status information from /proc/\<pid\>/status
+    pub fn status(&self) -> CollectResult<ProcessStatus> {
+        self.scope(|| self.status_from())
+    }
+
+    fn status_from(&self) -> CollectResult<ProcessStatus> {
+        let content = filesystem::read_to_string(&self.path().join("status"))?;
+        parse_status(&content)
+    }
+}
+
+pub(crate) fn parse_status(content: &str) -> CollectResult<ProcessStatus> {
+    let mut proc_status = ProcessStatus::new();
+
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let item_fields: Vec<&str> = line.splitn(2, ':').collect();
+
+        if item_fields.len() != 2 {
+            return Err(MetricError::InvalidFieldNumberError(
+                "process status".to_string(),
+                item_fields.len(),
+                line.to_string(),
+            ));
+        }
+
+        let item_value = item_fields[1].trim();
+
+        match item_fields[0].trim() {
+            "Name" => proc_status.name = item_value.to_string(),
+            "Umask" => {
+                proc_status.umask = Some(utils::convert_radix_str_to_u64(item_value, 8)? as u32);
+            }
+            "State" => proc_status.state = item_value.to_string(),
+            "Tgid" => proc_status.tgid = utils::convert_str_to_u64(item_value)? as usize,
+            "Ngid" => {
+                proc_status.ngid = Some(utils::convert_str_to_u64(item_value)? as usize);
+            }
+            "Pid" => proc_status.pid = utils::convert_str_to_u64(item_value)? as usize,
+            "PPid" => proc_status.ppid = utils::convert_str_to_u64(item_value)? as usize,
+            "TracerPid" => {
+                proc_status.tracer_pid = utils::convert_str_to_u64(item_value)? as usize;
+            }
+            "Uid" => proc_status.uids = parse_ids(item_value, line)?,
+            "Gid" => proc_status.gids = parse_ids(item_value, line)?,
+            "FDSize" => proc_status.fd_size = utils::convert_str_to_u64(item_value)?,
+            "Groups" => {
+                for group in item_value.split_whitespace() {
+                    proc_status.groups.push(utils::convert_str_to_u64(group)?);
+                }
+            }
+            "VmPeak" => proc_status.vm_peak = parse_bytes(item_value)?,
+            "VmSize" => proc_status.vm_size = parse_bytes(item_value)?,
+            "VmLck" => proc_status.vm_lck = parse_bytes(item_value)?,
+            "VmPin" => proc_status.vm_pin = parse_bytes(item_value)?,
+            "VmHWM" => proc_status.vm_hwm = parse_bytes(item_value)?,
+            "VmRSS" => proc_status.vm_rss = parse_bytes(item_value)?,
+            "RssAnon" => proc_status.rss_anon = parse_bytes(item_value)?,
+            "RssFile" => proc_status.rss_file = parse_bytes(item_value)?,
+            "RssShmem" => proc_status.rss_shmem = parse_bytes(item_value)?,
+            "VmData" => proc_status.vm_data = parse_bytes(item_value)?,
+            "VmStk" => proc_status.vm_stk = parse_bytes(item_value)?,
+            "VmExe" => proc_status.vm_exe = parse_bytes(item_value)?,
+            "VmLib" => proc_status.vm_lib = parse_bytes(item_value)?,
+            "VmPTE" => proc_status.vm_pte = parse_bytes(item_value)?,
+            "VmPMD" => proc_status.vm_pmd = parse_bytes(item_value)?,
+            "VmSwap" => proc_status.vm_swap = parse_bytes(item_value)?,
+            "HugetlbPages" => proc_status.hugetlb_pages = parse_bytes(item_value)?,
+            "Threads" => proc_status.threads = utils::convert_str_to_u64(item_value)?,
+            "SigQ" => {
+                let sigq_fields: Vec<&str> = item_value.split('/').collect();
+                if sigq_fields.len() != 2 {
+                    return Err(MetricError::InvalidFieldNumberError(
+                        "process status sigq".to_string(),
+                        sigq_fields.len(),
+                        line.to_string(),
+                    ));
+                }
+
+                proc_status.sig_queued = utils::convert_str_to_u64(sigq_fields[0])?;
+                proc_status.sig_queued_limit = utils::convert_str_to_u64(sigq_fields[1])?;
+            }
+            "SigPnd" => proc_status.sig_pnd = utils::convert_radix_str_to_u64(item_value, 16)?,
+            "ShdPnd" => proc_status.shd_pnd = utils::convert_radix_str_to_u64(item_value, 16)?,
+            "SigBlk" => proc_status.sig_blk = utils::convert_radix_str_to_u64(item_value, 16)?,
+            "SigIgn" => proc_status.sig_ign = utils::convert_radix_str_to_u64(item_value, 16)?,
+            "SigCgt" => proc_status.sig_cgt = utils::convert_radix_str_to_u64(item_value, 16)?,
+            "CapInh" => proc_status.cap_inh = parse_capabilities(item_value)?,
+            "CapPrm" => proc_status.cap_prm = parse_capabilities(item_value)?,
+            "CapEff" => proc_status.cap_eff = parse_capabilities(item_value)?,
+            "CapBnd" => proc_status.cap_bnd = parse_capabilities(item_value)?,
+            "CapAmb" => proc_status.cap_amb = parse_capabilities(item_value)?,
+            "NoNewPrivs" => {
+                proc_status.no_new_privs = Some(utils::convert_str_to_u64(item_value)? != 0);
+            }
+            "Seccomp" => proc_status.seccomp = Some(utils::convert_str_to_u64(item_value)?),
+            "Cpus_allowed_list" => proc_status.cpus_allowed_list = parse_list(item_value)?,
+            "Mems_allowed_list" => proc_status.mems_allowed_list = parse_list(item_value)?,
+            "voluntary_ctxt_switches" => {
+                proc_status.voluntary_ctxt_switches = Some(utils::convert_str_to_u64(item_value)?);
+            }
+            "nonvoluntary_ctxt_switches" => {
+                proc_status.nonvoluntary_ctxt_switches =
+                    Some(utils::convert_str_to_u64(item_value)?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(proc_status)
+}
+
+fn parse_bytes(value: &str) -> CollectResult<Option<u64>> {
+    let value_fields: Vec<&str> = value.split_whitespace().collect();
+
+    match value_fields.len() {
+        1 => utils::convert_to_bytes(utils::convert_str_to_u64(value_fields[0])?, "B"),
+        2 => utils::convert_to_bytes(utils::convert_str_to_u64(value_fields[0])?, value_fields[1]),
+        _ => Err(MetricError::InvalidFieldNumberError(
+            "process status memory".to_string(),
+            value_fields.len(),
+            value.to_string(),
+        )),
+    }
+}
+
+fn parse_ids(value: &str, line: &str) -> CollectResult<ProcessIds> {
+    let id_fields: Vec<&str> = value.split_whitespace().collect();
+
+    if id_fields.len() != 4 {
+        return Err(MetricError::InvalidFieldNumberError(
+            "process status ids".to_string(),
+            id_fields.len(),
+            line.to_string(),
+        ));
+    }
+
+    Ok(ProcessIds {
+        real: utils::convert_str_to_u64(id_fields[0])?,
+        effective: utils::convert_str_to_u64(id_fields[1])?,
+        saved: utils::convert_str_to_u64(id_fields[2])?,
+        filesystem: utils::convert_str_to_u64(id_fields[3])?,
+    })
+}
+
+fn parse_capabilities(value: &str) -> CollectResult<ProcessCapabilities> {
+    Ok(ProcessCapabilities::from_mask(
+        utils::convert_radix_str_to_u64(value, 16)?,
+    ))
+}
+
+// parses a kernel list format value (e.g. 0-3,8,10-11)
+fn parse_list(value: &str) -> CollectResult<Vec<u64>> {
+    let mut items = Vec::new();
+
+    for item in value.split(',').filter(|s| !s.trim().is_empty()) {
+        match item.trim().split_once('-') {
+            Some((first, last)) => {
+                let first = utils::convert_str_to_u64(first)?;
+                let last = utils::convert_str_to_u64(last)?;
+                items.extend(first..=last);
+            }
+            None => items.push(utils::convert_str_to_u64(item.trim())?),
+        }
+    }
+
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::parse_list;
+    use crate::process::*;
+
+    #[test]
+    fn proc_status() {
+        let proc_path = Path::new("test_data/fixtures/proc");
+        let sys_proc = collect_from(proc_path, 26231).expect("running proc 26231");
+        let sys_proc_status = sys_proc.status().expect("running proc 26231 status");
+
+        assert_eq!(sys_proc_status.name, "prometheus");
+        assert_eq!(sys_proc_status.umask, Some(0o022));
+        assert_eq!(sys_proc_status.state, "S (sleeping)");
+        assert_eq!(sys_proc_status.tgid, 26231);
+        assert_eq!(sys_proc_status.ngid, Some(0));
+        assert_eq!(sys_proc_status.pid, 26231);
+        assert_eq!(sys_proc_status.ppid, 1);
+        assert_eq!(sys_proc_status.tracer_pid, 0);
+        assert_eq!(sys_proc_status.uids.real, 1000);
+        assert_eq!(sys_proc_status.uids.filesystem, 0);
+        assert_eq!(sys_proc_status.gids.effective, 1001);
+        assert_eq!(sys_proc_status.fd_size, 128);
+        assert!(sys_proc_status.groups.is_empty());
+        assert_eq!(sys_proc_status.vm_peak, Some(58472 * 1024));
+        assert_eq!(sys_proc_status.vm_size, Some(58440 * 1024));
+        assert_eq!(sys_proc_status.vm_lck, Some(0));
+        assert_eq!(sys_proc_status.vm_hwm, Some(8028 * 1024));
+        assert_eq!(sys_proc_status.vm_rss, Some(6716 * 1024));
+        assert_eq!(sys_proc_status.rss_anon, Some(2092 * 1024));
+        assert_eq!(sys_proc_status.rss_file, Some(4624 * 1024));
+        assert_eq!(sys_proc_status.vm_pmd, Some(12 * 1024));
+        assert_eq!(sys_proc_status.vm_swap, Some(660 * 1024));
+        assert_eq!(sys_proc_status.threads, 1);
+        assert_eq!(sys_proc_status.sig_queued, 8);
+        assert_eq!(sys_proc_status.sig_queued_limit, 63965);
+        assert_eq!(sys_proc_status.sig_pnd, 0);
+        assert_eq!(sys_proc_status.sig_blk, 0x7be3c0fe28014a03);
+        assert_eq!(sys_proc_status.sig_ign, 0x1000);
+        assert_eq!(sys_proc_status.sig_cgt, 0x1800004ec);
+        assert_eq!(sys_proc_status.cap_inh.mask, 0);
+        assert!(sys_proc_status.cap_inh.names.is_empty());
+        assert_eq!(sys_proc_status.cap_eff.mask, 0x3fffffffff);
+        assert_eq!(sys_proc_status.cap_eff.names.len(), 38);
+        assert_eq!(sys_proc_status.cap_eff.names[0], "CAP_CHOWN");
+        assert_eq!(sys_proc_status.cap_eff.names[37], "CAP_AUDIT_READ");
+        assert!(sys_proc_status.cap_eff.contains("CAP_SYS_ADMIN"));
+        assert!(!sys_proc_status.cap_eff.contains("CAP_BPF"));
+        assert_eq!(sys_proc_status.no_new_privs, None);
+        assert_eq!(sys_proc_status.seccomp, Some(0));
+        assert_eq!(sys_proc_status.cpus_allowed_list, [0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(sys_proc_status.mems_allowed_list, [0]);
+        assert_eq!(sys_proc_status.voluntary_ctxt_switches, Some(4742839));
+        assert_eq!(sys_proc_status.nonvoluntary_ctxt_switches, Some(1727500));
+
+        let sys_proc = collect_from(proc_path, 26235).expect("running proc 26235");
+        let sys_proc_status = sys_proc.status().expect("running proc 26235 status");
+
+        assert_eq!(sys_proc_status.name, "kube-proxy");
+        assert_eq!(sys_proc_status.ngid, Some(12345));
+        assert_eq!(sys_proc_status.ppid, 1234);
+        assert_eq!(sys_proc_status.threads, 51);
+        assert_eq!(sys_proc_status.cap_inh.names.len(), 38);
+
+        let sys_proc = collect_from(proc_path, 26232).expect("running proc 26232");
+        assert!(sys_proc.status().is_err());
+    }
+
+    #[test]
+    fn proc_status_list() {
+        assert_eq!(parse_list("0-3,8,10-11").unwrap(), [0, 1, 2, 3, 8, 10, 11]);
+        assert!(parse_list("").unwrap().is_empty());
+        assert!(parse_list("a-b").is_err());
+    }
+}
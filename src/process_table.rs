@@ -0,0 +1,391 @@
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{CollectResult, ItemError, MetricError},
+    filesystem::FileSystem,
+    process,
+    process_cgroup::{self, ProcessCgroup},
+    process_io::{self, ProcessIO},
+    process_schedstat::{self, ProcessSchedstat},
+    process_stat::{self, ProcessStat},
+    process_status::{self, ProcessStatus},
+    procfs::ProcFs,
+};
+
+/// ProcessField is a file of `/proc/<pid>` read by [`ProcessTable::scan`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ProcessField {
+    Comm,
+    Cmdline,
+    Wchan,
+    Stat,
+    Status,
+    Io,
+    Schedstat,
+    Cgroup,
+}
+
+impl ProcessField {
+    /// all fields which can be scanned
+    pub const ALL: [ProcessField; 8] = [
+        ProcessField::Comm,
+        ProcessField::Cmdline,
+        ProcessField::Wchan,
+        ProcessField::Stat,
+        ProcessField::Status,
+        ProcessField::Io,
+        ProcessField::Schedstat,
+        ProcessField::Cgroup,
+    ];
+
+    /// returns the name of the file under `/proc/<pid>`
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            ProcessField::Comm => "comm",
+            ProcessField::Cmdline => "cmdline",
+            ProcessField::Wchan => "wchan",
+            ProcessField::Stat => "stat",
+            ProcessField::Status => "status",
+            ProcessField::Io => "io",
+            ProcessField::Schedstat => "schedstat",
+            ProcessField::Cgroup => "cgroup",
+        }
+    }
+}
+
+/// ProcessRow holds the scanned fields of a process, the fields which have not been
+/// requested or could not be read are None
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessRow {
+    pub pid: usize,
+    pub comm: Option<String>,
+    pub cmdline: Option<Vec<String>>,
+    pub wchan: Option<String>,
+    pub stat: Option<ProcessStat>,
+    pub status: Option<ProcessStatus>,
+    pub io: Option<ProcessIO>,
+    pub schedstat: Option<ProcessSchedstat>,
+    pub cgroup: Option<Vec<ProcessCgroup>>,
+}
+
+impl ProcessRow {
+    fn new(pid: usize) -> Self {
+        Self {
+            pid,
+            ..Default::default()
+        }
+    }
+}
+
+/// ProcessTable holds the requested fields of all processes, read in a single pass
+#[derive(Debug, Default)]
+pub struct ProcessTable {
+    /// rows of the processes sorted by pid
+    pub rows: Vec<ProcessRow>,
+
+    /// errors of the fields which could not be read or parsed (e.g. permission denied)
+    pub errors: Vec<ItemError>,
+
+    /// number of processes which have exited during the scan, they have no row
+    pub vanished: usize,
+}
+
+impl ProcessTable {
+    /// reads the given fields of all processes running on the system. Only the requested
+    /// files are read, the buffers are reused between the processes and, with the `rayon`
+    /// feature, the processes are scanned in parallel.
+    /// # Example
+    /// ```
+    /// use procsys::process_table::{ProcessField, ProcessTable};
+    ///
+    /// let table = ProcessTable::scan(&[ProcessField::Comm, ProcessField::Stat])
+    ///     .expect("process table");
+    ///
+    /// for row in &table.rows {
+    ///     let comm = row.comm.clone().unwrap_or_default();
+    ///     let utime = row.stat.as_ref().map(|stat| stat.utime).unwrap_or_default();
+    ///     println!("{} {} utime={}", row.pid, comm, utime);
+    /// }
+    ///
+    /// ```
+    pub fn scan(fields: &[ProcessField]) -> CollectResult<Self> {
+        ProcFs::default().process_table(fields)
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// returns the row of the given pid
+    pub fn get(&self, pid: usize) -> Option<&ProcessRow> {
+        self.rows
+            .binary_search_by_key(&pid, |row| row.pid)
+            .ok()
+            .map(|index| &self.rows[index])
+    }
+}
+
+// Scanner holds the buffers reused between the processes
+struct Scanner<'a> {
+    fs: &'a dyn FileSystem,
+    path: PathBuf,
+    pid_name: String,
+    content: Vec<u8>,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(fs: &'a dyn FileSystem, root: &Path) -> Self {
+        Self {
+            fs,
+            path: root.to_path_buf(),
+            pid_name: String::new(),
+            content: Vec::new(),
+        }
+    }
+
+    // returns the row and the field errors of a process, None if it has exited
+    fn scan(
+        &mut self,
+        pid: usize,
+        fields: &[ProcessField],
+    ) -> Option<(ProcessRow, Vec<ItemError>)> {
+        let mut row = ProcessRow::new(pid);
+        let mut errors = Vec::new();
+
+        self.pid_name.clear();
+        let _ = write!(self.pid_name, "{}", pid);
+        self.path.push(&self.pid_name);
+
+        for field in fields {
+            self.path.push(field.file_name());
+            let read_result = self.fs.read_file_into(&self.path, &mut self.content);
+
+            let result = match read_result {
+                Ok(()) => parse_field(&mut row, *field, &String::from_utf8_lossy(&self.content)),
+                Err(err) => {
+                    let proc_path = self.path.parent().unwrap_or(&self.path);
+
+                    if process::is_gone(self.fs, proc_path, &err) {
+                        self.path.pop();
+                        self.path.pop();
+                        return None;
+                    }
+
+                    Err(MetricError::IOError(self.path.clone(), err))
+                }
+            };
+
+            if let Err(err) = result {
+                errors.push(ItemError {
+                    item: self.pid_name.clone(),
+                    path: self.path.clone(),
+                    error: err.with_path(&self.path),
+                });
+            }
+
+            self.path.pop();
+        }
+
+        self.path.pop();
+
+        Some((row, errors))
+    }
+}
+
+fn parse_field(row: &mut ProcessRow, field: ProcessField, content: &str) -> CollectResult<()> {
+    match field {
+        ProcessField::Comm => row.comm = Some(content.trim().to_string()),
+        ProcessField::Cmdline => row.cmdline = Some(process::parse_nul_separated(content)),
+        ProcessField::Wchan => row.wchan = Some(process::parse_wchan(content)),
+        ProcessField::Stat => {
+            let line = content.lines().next().unwrap_or_default();
+            row.stat = Some(process_stat::parse_stat(line)?);
+        }
+        ProcessField::Status => row.status = Some(process_status::parse_status(content)?),
+        ProcessField::Io => row.io = Some(process_io::parse_io(content)?),
+        ProcessField::Schedstat => {
+            row.schedstat = Some(process_schedstat::parse_schedstat(content)?)
+        }
+        ProcessField::Cgroup => row.cgroup = Some(process_cgroup::parse_cgroup(content)?),
+    }
+
+    Ok(())
+}
+
+pub(crate) fn scan_from(
+    fs: &dyn FileSystem,
+    root: &Path,
+    fields: &[ProcessField],
+) -> CollectResult<ProcessTable> {
+    let mut pids: Vec<usize> = match fs.list_dir(root) {
+        Ok(names) => names
+            .iter()
+            .filter_map(|name| name.parse::<usize>().ok())
+            .collect(),
+        Err(err) => return Err(MetricError::IOError(root.to_path_buf(), err)),
+    };
+    pids.sort_unstable();
+
+    #[cfg(feature = "rayon")]
+    let results: Vec<Option<(ProcessRow, Vec<ItemError>)>> = pids
+        .par_iter()
+        .map_init(
+            || Scanner::new(fs, root),
+            |scanner, pid| scanner.scan(*pid, fields),
+        )
+        .collect();
+
+    #[cfg(not(feature = "rayon"))]
+    let results: Vec<Option<(ProcessRow, Vec<ItemError>)>> = {
+        let mut scanner = Scanner::new(fs, root);
+        pids.iter().map(|pid| scanner.scan(*pid, fields)).collect()
+    };
+
+    let mut table = ProcessTable::default();
+    table.rows.reserve(results.len());
+
+    for result in results {
+        match result {
+            Some((row, mut errors)) => {
+                table.rows.push(row);
+                table.errors.append(&mut errors);
+            }
+            None => table.vanished += 1,
+        }
+    }
+
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use crate::filesystem::MemoryFileSystem;
+
+    use super::*;
+
+    // process 43 exits after the pids have been listed
+    #[derive(Debug)]
+    struct ExitingFileSystem {
+        memfs: MemoryFileSystem,
+    }
+
+    impl ExitingFileSystem {
+        fn exited(path: &Path) -> bool {
+            path.starts_with("/proc/43")
+        }
+    }
+
+    impl FileSystem for ExitingFileSystem {
+        fn read_file(&self, path: &Path) -> io::Result<Vec<u8>> {
+            match Self::exited(path) {
+                true => Err(io::Error::from(io::ErrorKind::NotFound)),
+                false => self.memfs.read_file(path),
+            }
+        }
+
+        fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+            self.memfs.read_link(path)
+        }
+
+        fn list_dir(&self, path: &Path) -> io::Result<Vec<String>> {
+            self.memfs.list_dir(path)
+        }
+
+        fn exists(&self, path: &Path) -> bool {
+            !Self::exited(path) && self.memfs.exists(path)
+        }
+
+        fn is_dir(&self, path: &Path) -> bool {
+            !Self::exited(path) && self.memfs.is_dir(path)
+        }
+    }
+
+    #[test]
+    fn process_table() {
+        let procfs = ProcFs::new("test_data/fixtures/proc");
+        let table = procfs
+            .process_table(&[ProcessField::Comm, ProcessField::Stat])
+            .expect("process table");
+
+        assert_eq!(table.len(), procfs.processes().unwrap().len());
+        assert!(table.rows.windows(2).all(|pair| pair[0].pid < pair[1].pid));
+        assert_eq!(table.vanished, 0);
+
+        let row = table.get(26231).expect("process 26231");
+        let proc = procfs.process(26231).unwrap();
+        assert_eq!(row.comm.as_deref(), Some("vim"));
+        assert_eq!(row.stat.as_ref().unwrap().utime, proc.stat().unwrap().utime);
+        assert!(row.status.is_none());
+
+        // 26233 has no stat file but it is still running
+        let row = table.get(26233).expect("process 26233");
+        assert!(row.stat.is_none());
+        assert!(table
+            .errors
+            .iter()
+            .any(|err| err.item == "26233" && err.path.ends_with("26233/stat")));
+    }
+
+    #[test]
+    fn process_table_all_fields() {
+        let procfs = ProcFs::new("test_data/fixtures/proc");
+        let table = procfs
+            .process_table(&ProcessField::ALL)
+            .expect("process table");
+
+        let row = table.get(26231).expect("process 26231");
+        let proc = procfs.process(26231).unwrap();
+        assert_eq!(row.cmdline, Some(proc.cmdline().unwrap()));
+        assert_eq!(row.wchan, Some(proc.wchan().unwrap()));
+        assert_eq!(
+            row.status.as_ref().unwrap().name,
+            proc.status().unwrap().name
+        );
+        assert_eq!(row.io.as_ref().unwrap().rchar, proc.io().unwrap().rchar);
+        assert_eq!(
+            row.schedstat.as_ref().unwrap().run_timeslices,
+            proc.schedstat().unwrap().run_timeslices
+        );
+        assert_eq!(
+            row.cgroup.as_ref().unwrap().len(),
+            proc.cgroup().unwrap().len()
+        );
+    }
+
+    #[test]
+    fn process_table_vanished() {
+        let mut memfs = MemoryFileSystem::new();
+        memfs.insert_file("proc/42/comm", "bash\n");
+        memfs.insert_file("proc/43/comm", "sleep\n");
+        memfs.insert_file("proc/100/comm", "sshd\n");
+        let procfs =
+            ProcFs::with_filesystem("/proc", std::sync::Arc::new(ExitingFileSystem { memfs }));
+
+        let table = procfs
+            .process_table(&[ProcessField::Comm])
+            .expect("process table");
+
+        assert_eq!(table.vanished, 1);
+        assert!(table.errors.is_empty());
+
+        let pids: Vec<usize> = table.rows.iter().map(|row| row.pid).collect();
+        assert_eq!(pids, vec![42, 100]);
+        assert_eq!(table.get(100).unwrap().comm.as_deref(), Some("sshd"));
+    }
+}
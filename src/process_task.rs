@@ -0,0 +1,119 @@
+use std::path::PathBuf;
+
+use crate::{
+    error::{CollectResult, MetricError},
+    filesystem,
+    process::Process,
+    utils,
+};
+
+impl Process {
+    /// returns the tasks (threads) of the process from /proc/\<pid\>/task,
+    /// the returned handles support the same accessors as the process
+    /// (comm, stat, status, io, schedstat, wchan, ...) and their pid is the thread id
+    /// # Example
+    /// ```
+    /// use procsys::process;
+    ///
+    /// let proc = process::collect(1).expect("process pid 1");
+    /// for task in proc.tasks().expect("process pid 1 tasks") {
+    ///     println!("tid: {}", task.pid());
+    ///     println!("\t comm: {}", task.comm().unwrap_or_default());
+    ///     println!("\t utime: {}", task.stat().map(|s| s.utime).unwrap_or_default());
+    /// }
+    ///
+    /// ```
+    pub fn tasks(&self) -> CollectResult<Vec<Process>> {
+        self.scope(|| self.tasks_from())
+    }
+
+    fn tasks_from(&self) -> CollectResult<Vec<Process>> {
+        let mut proc_tasks = Vec::new();
+
+        let proc_task_path = self.task_path();
+        if !filesystem::is_dir(&proc_task_path) {
+            return Err(MetricError::PathNotFound(proc_task_path));
+        }
+
+        for task_name in utils::list_dir_content(&proc_task_path, "", "task") {
+            if let Ok(tid) = task_name.parse::<usize>() {
+                let task_dir_path = proc_task_path.join(&task_name);
+
+                // the thread may have exited since the directory has been listed
+                if filesystem::is_dir(&task_dir_path) {
+                    proc_tasks.push(Process::new(tid, task_dir_path));
+                }
+            }
+        }
+
+        proc_tasks.sort_by_key(|t| t.pid());
+
+        Ok(proc_tasks)
+    }
+
+    /// returns a specific task (thread) of the process from /proc/\<pid\>/task/\<tid\>
+    pub fn task(&self, tid: usize) -> CollectResult<Process> {
+        self.scope(|| self.task_from(tid))
+    }
+
+    fn task_from(&self, tid: usize) -> CollectResult<Process> {
+        let mut task_dir_path = self.task_path();
+        task_dir_path.push(format!("{}", tid));
+
+        if filesystem::is_dir(&task_dir_path) {
+            return Ok(Process::new(tid, task_dir_path));
+        }
+
+        Err(MetricError::ProcessNotFound(tid))
+    }
+
+    fn task_path(&self) -> PathBuf {
+        let mut proc_task_path = self.path();
+        proc_task_path.push("task");
+
+        proc_task_path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::process::*;
+
+    #[test]
+    fn proc_tasks() {
+        let proc_path = Path::new("test_data/fixtures/proc");
+        let sys_proc = collect_from(proc_path, 27079).expect("running proc 27079");
+        let sys_proc_tasks = sys_proc.tasks().expect("running proc 27079 tasks");
+
+        assert_eq!(
+            sys_proc_tasks
+                .iter()
+                .map(|t| t.pid())
+                .collect::<Vec<usize>>(),
+            [27079, 27080, 27081, 27082, 27083],
+        );
+
+        let hottest_task = sys_proc_tasks
+            .iter()
+            .max_by_key(|t| t.stat().map(|s| s.utime).unwrap_or_default())
+            .expect("hottest task");
+        let hottest_task_stat = hottest_task.stat().expect("running task stat");
+        assert_eq!(hottest_task.pid(), 27080);
+        assert_eq!(hottest_task_stat.pid, hottest_task.pid());
+        assert_eq!(hottest_task_stat.comm, "pthread_load");
+
+        let sys_task = sys_proc.task(27080).expect("running task 27080");
+        let sys_task_stat = sys_task.stat().expect("running task 27080 stat");
+        assert_eq!(sys_task_stat.pid, 27080);
+        assert_eq!(sys_task_stat.state, "R");
+        assert_eq!(sys_task_stat.utime, 34136);
+        assert!(sys_task.status().is_err());
+
+        assert!(sys_proc.task(1).is_err());
+
+        let sys_proc = collect_from(proc_path, 26231).expect("running proc 26231");
+        assert!(sys_proc.tasks().is_err());
+    }
+}
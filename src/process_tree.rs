@@ -0,0 +1,382 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    path::Path,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::CollectResult,
+    process::{self, Process},
+    process_stat::ProcessStat,
+    procfs::ProcFs,
+};
+
+/// ProcessTreeNode is a single process of a process tree
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessTreeNode {
+    pub pid: usize,
+    pub ppid: usize,
+    pub comm: String,
+    pub stat: ProcessStat,
+}
+
+/// ProcessTreeUsage holds the accumulated resource usage of a process subtree
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessTreeUsage {
+    /// number of processes in the subtree
+    pub processes: usize,
+
+    /// number of threads in the subtree
+    pub num_threads: i64,
+
+    /// amount of time (clock ticks) scheduled in user mode
+    pub utime: u64,
+
+    /// amount of time (clock ticks) scheduled in kernel mode
+    pub stime: u64,
+
+    /// virtual memory size in bytes
+    pub vsize: u64,
+
+    /// resident set size in pages
+    pub rss: i64,
+}
+
+impl ProcessTreeUsage {
+    fn new() -> Self {
+        Default::default()
+    }
+
+    fn add(&mut self, stat: &ProcessStat) {
+        self.processes += 1;
+        self.num_threads += stat.num_threads;
+        self.utime += stat.utime;
+        self.stime += stat.stime;
+        self.vsize += stat.vsize;
+        self.rss += stat.rss;
+    }
+}
+
+/// ProcessTree is the parent/child hierarchy of processes built from
+/// the parent pids of /proc/\<pid\>/stat
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessTree {
+    nodes: BTreeMap<usize, ProcessTreeNode>,
+    children: BTreeMap<usize, Vec<usize>>,
+}
+
+impl ProcessTree {
+    fn new() -> Self {
+        Default::default()
+    }
+
+    /// builds a process tree from a list of processes, processes which
+    /// have exited in the meantime (unreadable stat) are skipped
+    pub fn from_processes(procs: &[Process]) -> Self {
+        let mut proc_tree = ProcessTree::new();
+
+        for proc in procs {
+            if let Ok(stat) = proc.stat() {
+                proc_tree.insert(proc.pid(), stat);
+            }
+        }
+
+        proc_tree
+    }
+
+    /// builds a process tree from a list of process stats
+    pub fn from_stats<I: IntoIterator<Item = ProcessStat>>(stats: I) -> Self {
+        let mut proc_tree = ProcessTree::new();
+
+        for stat in stats {
+            proc_tree.insert(stat.pid, stat);
+        }
+
+        proc_tree
+    }
+
+    fn insert(&mut self, pid: usize, stat: ProcessStat) {
+        let ppid = stat.ppid;
+
+        // a process is never its own parent (e.g. the idle task pid 0)
+        if ppid != pid {
+            let siblings = self.children.entry(ppid).or_default();
+            if let Err(pos) = siblings.binary_search(&pid) {
+                siblings.insert(pos, pid);
+            }
+        }
+
+        self.nodes.insert(
+            pid,
+            ProcessTreeNode {
+                pid,
+                ppid,
+                comm: stat.comm.clone(),
+                stat,
+            },
+        );
+    }
+
+    /// returns the number of processes in the tree
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// returns true if the tree has no processes
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// returns the process with the given pid
+    pub fn get(&self, pid: usize) -> Option<&ProcessTreeNode> {
+        self.nodes.get(&pid)
+    }
+
+    /// returns all processes of the tree ordered by pid
+    pub fn nodes(&self) -> impl Iterator<Item = &ProcessTreeNode> {
+        self.nodes.values()
+    }
+
+    /// returns the pids of the processes whose parent is not part of the tree
+    /// (e.g. init and kthreadd)
+    pub fn roots(&self) -> Vec<usize> {
+        self.nodes
+            .values()
+            .filter(|n| n.pid == n.ppid || !self.nodes.contains_key(&n.ppid))
+            .map(|n| n.pid)
+            .collect()
+    }
+
+    /// returns the pids of the direct children of a process
+    pub fn children(&self, pid: usize) -> Vec<usize> {
+        self.children
+            .get(&pid)
+            .map(|c| {
+                c.iter()
+                    .copied()
+                    .filter(|p| self.nodes.contains_key(p))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// returns the pids of all the processes under a process (depth first, excluding the process itself)
+    pub fn descendants(&self, pid: usize) -> Vec<usize> {
+        let mut proc_descendants = Vec::new();
+        let mut visited = BTreeSet::from([pid]);
+        let mut stack: Vec<usize> = self.children(pid).into_iter().rev().collect();
+
+        while let Some(child) = stack.pop() {
+            if !visited.insert(child) {
+                continue;
+            }
+
+            proc_descendants.push(child);
+            stack.extend(self.children(child).into_iter().rev());
+        }
+
+        proc_descendants
+    }
+
+    /// returns the pids of the ancestors of a process, from its parent up to the root of the tree (e.g. init)
+    pub fn ancestors(&self, pid: usize) -> Vec<usize> {
+        let mut proc_ancestors = Vec::new();
+        let mut visited = BTreeSet::from([pid]);
+        let mut current = pid;
+
+        while let Some(node) = self.nodes.get(&current) {
+            if !self.nodes.contains_key(&node.ppid) || !visited.insert(node.ppid) {
+                break;
+            }
+
+            proc_ancestors.push(node.ppid);
+            current = node.ppid;
+        }
+
+        proc_ancestors
+    }
+
+    /// returns the accumulated resource usage of a process and all its descendants
+    pub fn subtree_usage(&self, pid: usize) -> ProcessTreeUsage {
+        let mut usage = ProcessTreeUsage::new();
+
+        if let Some(node) = self.nodes.get(&pid) {
+            usage.add(&node.stat);
+
+            for child in self.descendants(pid) {
+                if let Some(child_node) = self.nodes.get(&child) {
+                    usage.add(&child_node.stat);
+                }
+            }
+        }
+
+        usage
+    }
+
+    /// returns a pstree like representation of a process and its descendants
+    pub fn render(&self, pid: usize) -> String {
+        let mut output = String::new();
+
+        if let Some(node) = self.nodes.get(&pid) {
+            output.push_str(&format!("{}({})\n", node.comm, node.pid));
+            let mut visited = BTreeSet::from([pid]);
+            self.render_children(pid, "", &mut visited, &mut output);
+        }
+
+        output
+    }
+
+    fn render_children(
+        &self,
+        pid: usize,
+        prefix: &str,
+        visited: &mut BTreeSet<usize>,
+        output: &mut String,
+    ) {
+        let children = self.children(pid);
+
+        for (index, child) in children.iter().enumerate() {
+            if !visited.insert(*child) {
+                continue;
+            }
+
+            let last = index == children.len() - 1;
+            let (branch, indent) = match last {
+                true => ("└─", "  "),
+                false => ("├─", "│ "),
+            };
+
+            let node = &self.nodes[child];
+            output.push_str(&format!(
+                "{}{}{}({})\n",
+                prefix, branch, node.comm, node.pid
+            ));
+
+            self.render_children(*child, &format!("{}{}", prefix, indent), visited, output);
+        }
+    }
+}
+
+impl fmt::Display for ProcessTree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for root in self.roots() {
+            write!(f, "{}", self.render(root))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// builds the process tree of all processes running on the system
+/// # Example
+/// ```
+/// use procsys::process_tree;
+///
+/// let proc_tree = process_tree::collect().expect("process tree");
+///
+/// println!("{}", proc_tree);
+/// for pid in proc_tree.descendants(1) {
+///     println!("{:?}", proc_tree.get(pid).map(|n| &n.comm));
+/// }
+///
+/// ```
+pub fn collect() -> CollectResult<ProcessTree> {
+    ProcFs::default().process_tree()
+}
+
+pub(crate) fn collect_from(base_path: &Path) -> CollectResult<ProcessTree> {
+    let procs = process::collect_all_from(base_path)?;
+
+    Ok(ProcessTree::from_processes(&procs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_stat(pid: usize, ppid: usize, comm: &str) -> ProcessStat {
+        ProcessStat {
+            pid,
+            ppid,
+            comm: comm.to_string(),
+            num_threads: 1,
+            utime: 10,
+            stime: 5,
+            vsize: 4096,
+            rss: 2,
+            ..Default::default()
+        }
+    }
+
+    fn test_tree() -> ProcessTree {
+        ProcessTree::from_stats([
+            test_stat(1, 0, "systemd"),
+            test_stat(2, 0, "kthreadd"),
+            test_stat(100, 1, "sshd"),
+            test_stat(200, 100, "sshd"),
+            test_stat(201, 200, "bash"),
+            test_stat(202, 201, "vim"),
+            test_stat(300, 1, "cron"),
+            test_stat(400, 2, "kworker/0:1"),
+        ])
+    }
+
+    #[test]
+    fn process_tree_queries() {
+        let proc_tree = test_tree();
+
+        assert_eq!(proc_tree.len(), 8);
+        assert_eq!(proc_tree.roots(), [1, 2]);
+        assert_eq!(proc_tree.children(1), [100, 300]);
+        assert_eq!(proc_tree.children(202), Vec::<usize>::new());
+        assert_eq!(proc_tree.descendants(1), [100, 200, 201, 202, 300]);
+        assert_eq!(proc_tree.descendants(999), Vec::<usize>::new());
+        assert_eq!(proc_tree.ancestors(202), [201, 200, 100, 1]);
+        assert_eq!(proc_tree.ancestors(1), Vec::<usize>::new());
+        assert_eq!(proc_tree.get(201).unwrap().comm, "bash");
+
+        let usage = proc_tree.subtree_usage(100);
+        assert_eq!(usage.processes, 4);
+        assert_eq!(usage.num_threads, 4);
+        assert_eq!(usage.utime, 40);
+        assert_eq!(usage.stime, 20);
+        assert_eq!(usage.vsize, 4 * 4096);
+        assert_eq!(usage.rss, 8);
+        assert_eq!(proc_tree.subtree_usage(999), ProcessTreeUsage::default());
+    }
+
+    #[test]
+    fn process_tree_render() {
+        let proc_tree = test_tree();
+
+        assert_eq!(
+            proc_tree.to_string(),
+            "systemd(1)\n\
+             ├─sshd(100)\n\
+             │ └─sshd(200)\n\
+             │   └─bash(201)\n\
+             │     └─vim(202)\n\
+             └─cron(300)\n\
+             kthreadd(2)\n\
+             └─kworker/0:1(400)\n",
+        );
+        assert_eq!(proc_tree.render(201), "bash(201)\n└─vim(202)\n");
+    }
+
+    #[test]
+    fn process_tree_collect() {
+        let proc_tree =
+            collect_from(Path::new("test_data/fixtures/proc")).expect("running process tree");
+
+        assert_eq!(proc_tree.len(), 4);
+        assert_eq!(proc_tree.get(26231).unwrap().ppid, 5392);
+        assert_eq!(proc_tree.get(27079).unwrap().comm, "pthread_load");
+        assert_eq!(proc_tree.roots(), [584, 26231, 26232, 27079]);
+    }
+}
@@ -0,0 +1,345 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{
+    buddyinfo::{self, BuddyInfo},
+    cmdline,
+    cpuinfo::{self, CpuInfo},
+    crypto::{self, Crypto},
+    diskstats::{self, DiskStats},
+    error::{CollectResult, Partial},
+    filesystem::{self, FileSystem},
+    kernel_random::{self, KernelRandom},
+    loadavg::{self, LoadAvg},
+    meminfo::{self, Meminfo},
+    pagetypeinfo::{self, PageTypeInfo},
+    partitions::{self, Partition},
+    softirqs::{self, Softirqs},
+    stat::{self, Stat},
+    swaps::{self, Swaps},
+    vmstat::{self, Vmstat},
+    zoneinfo::{self, ZoneInfo},
+};
+
+#[cfg(feature = "net")]
+use crate::{
+    net_arp::{self, ARPEntry},
+    net_dev::{self, NetDev},
+    net_protocols::{self, NetProtocol},
+    net_sockstat::{self, NetSockStat},
+    net_unix::{self, NetUnix},
+    net_wireless::{self, Wireless},
+};
+
+#[cfg(feature = "process")]
+use crate::{
+    process::{self, Process},
+    process_table::{self, ProcessField, ProcessTable},
+    process_tree::{self, ProcessTree},
+};
+
+/// default mount point of the proc pseudo-filesystem
+pub const DEFAULT_MOUNT_POINT: &str = "/proc";
+
+/// ProcFs is a handle to a proc filesystem mounted at an arbitrary root
+/// (e.g. a host's /proc bind mounted at /host/proc inside a container)
+#[derive(Debug, Clone)]
+pub struct ProcFs {
+    root: PathBuf,
+    fs: Arc<dyn FileSystem>,
+}
+
+impl PartialEq for ProcFs {
+    fn eq(&self, other: &Self) -> bool {
+        self.root == other.root && Arc::ptr_eq(&self.fs, &other.fs)
+    }
+}
+
+impl Eq for ProcFs {}
+
+impl Default for ProcFs {
+    fn default() -> Self {
+        Self::new(DEFAULT_MOUNT_POINT)
+    }
+}
+
+impl ProcFs {
+    /// returns a new proc filesystem handle for the given mount point
+    /// # Example
+    /// ```
+    /// use procsys::ProcFs;
+    ///
+    /// let procfs = ProcFs::new("/proc");
+    /// let sys_meminfo = procfs.meminfo().expect("memory information");
+    /// println!("{:?}", sys_meminfo.mem_total);
+    ///
+    /// ```
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self::with_filesystem(root, filesystem::real())
+    }
+
+    /// returns a new proc filesystem handle for the given mount point reading from `fs`
+    /// instead of the real filesystem (e.g. a [`crate::filesystem::MemoryFileSystem`])
+    pub fn with_filesystem<P: AsRef<Path>>(root: P, fs: Arc<dyn FileSystem>) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            fs,
+        }
+    }
+
+    /// returns the mount point of the proc filesystem
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// returns the absolute path of an entry relative to the mount point
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.root.join(name)
+    }
+
+    /// returns the filesystem the handle reads from
+    pub fn filesystem(&self) -> &Arc<dyn FileSystem> {
+        &self.fs
+    }
+
+    // runs a parser reading from the filesystem of the handle
+    pub(crate) fn scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        filesystem::scope(&self.fs, f)
+    }
+
+    // runs the parser of `<root>/<name>` reading from the filesystem of the
+    // handle, and adds the path to the errors which do not carry one
+    pub(crate) fn collect_at<T>(
+        &self,
+        name: &str,
+        f: impl FnOnce(&Path) -> CollectResult<T>,
+    ) -> CollectResult<T> {
+        let path = self.path(name);
+        self.scope(|| f(&path)).map_err(|err| err.with_path(&path))
+    }
+
+    /// collects buddyinfo statistics from `<root>/buddyinfo`
+    pub fn buddyinfo(&self) -> CollectResult<Vec<BuddyInfo>> {
+        self.collect_at("buddyinfo", buddyinfo::collect_from)
+    }
+
+    /// like [`ProcFs::buddyinfo`], but keeps the entries that parsed and reports the others
+    pub fn buddyinfo_lenient(&self) -> CollectResult<Partial<Vec<BuddyInfo>>> {
+        self.collect_at("buddyinfo", buddyinfo::collect_lenient_from)
+    }
+
+    /// collects system boot cmdline from `<root>/cmdline`
+    pub fn cmdline(&self) -> CollectResult<Vec<String>> {
+        self.collect_at("cmdline", |_| cmdline::collect_from("cmdline", &self.root))
+    }
+
+    /// collects cpu information from `<root>/cpuinfo`
+    pub fn cpuinfo(&self) -> CollectResult<Vec<CpuInfo>> {
+        self.collect_at("cpuinfo", cpuinfo::collect_from)
+    }
+
+    /// collects crypto information from `<root>/crypto`
+    pub fn crypto(&self) -> CollectResult<Vec<Crypto>> {
+        self.collect_at("crypto", crypto::collect_from)
+    }
+
+    /// collects block devices I/O statistics from `<root>/diskstats`
+    pub fn diskstats(&self) -> CollectResult<Vec<DiskStats>> {
+        self.collect_at("diskstats", diskstats::collect_from)
+    }
+
+    /// like [`ProcFs::diskstats`], but keeps the entries that parsed and reports the others
+    pub fn diskstats_lenient(&self) -> CollectResult<Partial<Vec<DiskStats>>> {
+        self.collect_at("diskstats", diskstats::collect_lenient_from)
+    }
+
+    /// collects kernel's random number generator information from `<root>/sys/kernel/random`
+    pub fn kernel_random(&self) -> CollectResult<KernelRandom> {
+        self.collect_at("sys/kernel/random", kernel_random::collect_from)
+    }
+
+    /// collects load average information from `<root>/loadavg`
+    pub fn loadavg(&self) -> CollectResult<LoadAvg> {
+        self.collect_at("loadavg", |_| loadavg::collect_from("loadavg", &self.root))
+    }
+
+    /// collects memory statistics from `<root>/meminfo`
+    pub fn meminfo(&self) -> CollectResult<Meminfo> {
+        self.collect_at("meminfo", meminfo::collect_from)
+    }
+
+    /// collects ARP entries from `<root>/net/arp`
+    #[cfg(feature = "net")]
+    pub fn net_arp(&self) -> CollectResult<Vec<ARPEntry>> {
+        self.collect_at("net/arp", net_arp::collect_from)
+    }
+
+    /// like [`ProcFs::net_arp`], but keeps the entries that parsed and reports the others
+    #[cfg(feature = "net")]
+    pub fn net_arp_lenient(&self) -> CollectResult<Partial<Vec<ARPEntry>>> {
+        self.collect_at("net/arp", net_arp::collect_lenient_from)
+    }
+
+    /// collects network devices information from `<root>/net/dev`
+    #[cfg(feature = "net")]
+    pub fn net_dev(&self) -> CollectResult<Vec<NetDev>> {
+        self.collect_at("net/dev", net_dev::collect_from)
+    }
+
+    /// like [`ProcFs::net_dev`], but keeps the entries that parsed and reports the others
+    #[cfg(feature = "net")]
+    pub fn net_dev_lenient(&self) -> CollectResult<Partial<Vec<NetDev>>> {
+        self.collect_at("net/dev", net_dev::collect_lenient_from)
+    }
+
+    /// collects network protocols information from `<root>/net/protocols`
+    #[cfg(feature = "net")]
+    pub fn net_protocols(&self) -> CollectResult<Vec<NetProtocol>> {
+        self.collect_at("net/protocols", net_protocols::collect_from)
+    }
+
+    /// like [`ProcFs::net_protocols`], but keeps the entries that parsed and reports the others
+    #[cfg(feature = "net")]
+    pub fn net_protocols_lenient(&self) -> CollectResult<Partial<Vec<NetProtocol>>> {
+        self.collect_at("net/protocols", net_protocols::collect_lenient_from)
+    }
+
+    /// collects sockets statistics from `<root>/net/sockstat`
+    #[cfg(feature = "net")]
+    pub fn net_sockstat(&self) -> CollectResult<NetSockStat> {
+        self.collect_at("net/sockstat", net_sockstat::collect_from)
+    }
+
+    /// collects IPv6 sockets statistics from `<root>/net/sockstat6`
+    #[cfg(feature = "net")]
+    pub fn net_sockstat6(&self) -> CollectResult<NetSockStat> {
+        self.collect_at("net/sockstat6", net_sockstat::collect_from)
+    }
+
+    /// collects unix domain sockets from `<root>/net/unix`
+    #[cfg(feature = "net")]
+    pub fn net_unix(&self) -> CollectResult<Vec<NetUnix>> {
+        self.collect_at("net/unix", net_unix::collect_from)
+    }
+
+    /// like [`ProcFs::net_unix`], but keeps the entries that parsed and reports the others
+    #[cfg(feature = "net")]
+    pub fn net_unix_lenient(&self) -> CollectResult<Partial<Vec<NetUnix>>> {
+        self.collect_at("net/unix", net_unix::collect_lenient_from)
+    }
+
+    /// collects wireless interfaces statistics from `<root>/net/wireless`
+    #[cfg(feature = "net")]
+    pub fn net_wireless(&self) -> CollectResult<Vec<Wireless>> {
+        self.collect_at("net/wireless", net_wireless::collect_from)
+    }
+
+    /// like [`ProcFs::net_wireless`], but keeps the entries that parsed and reports the others
+    #[cfg(feature = "net")]
+    pub fn net_wireless_lenient(&self) -> CollectResult<Partial<Vec<Wireless>>> {
+        self.collect_at("net/wireless", net_wireless::collect_lenient_from)
+    }
+
+    /// collects free memory per migrate type from `<root>/pagetypeinfo`
+    pub fn pagetypeinfo(&self) -> CollectResult<PageTypeInfo> {
+        self.collect_at("pagetypeinfo", pagetypeinfo::collect_from)
+    }
+
+    /// collects block devices and partitions from `<root>/partitions`
+    pub fn partitions(&self) -> CollectResult<Vec<Partition>> {
+        self.collect_at("partitions", partitions::collect_from)
+    }
+
+    /// like [`ProcFs::partitions`], but keeps the entries that parsed and reports the others
+    pub fn partitions_lenient(&self) -> CollectResult<Partial<Vec<Partition>>> {
+        self.collect_at("partitions", partitions::collect_lenient_from)
+    }
+
+    /// collects softirq statistics from `<root>/softirqs`
+    pub fn softirqs(&self) -> CollectResult<Softirqs> {
+        self.collect_at("softirqs", softirqs::collect_from)
+    }
+
+    /// collects kernel/system statistics from `<root>/stat`
+    pub fn stat(&self) -> CollectResult<Stat> {
+        self.collect_at("stat", stat::collect_from)
+    }
+
+    /// collects swap devices information from `<root>/swaps`
+    pub fn swaps(&self) -> CollectResult<Vec<Swaps>> {
+        self.collect_at("swaps", swaps::collect_from)
+    }
+
+    /// like [`ProcFs::swaps`], but keeps the entries that parsed and reports the others
+    pub fn swaps_lenient(&self) -> CollectResult<Partial<Vec<Swaps>>> {
+        self.collect_at("swaps", swaps::collect_lenient_from)
+    }
+
+    /// collects virtual memory statistics from `<root>/vmstat`
+    pub fn vmstat(&self) -> CollectResult<Vmstat> {
+        self.collect_at("vmstat", vmstat::collect_from)
+    }
+
+    /// collects memory zones information from `<root>/zoneinfo`
+    pub fn zoneinfo(&self) -> CollectResult<Vec<ZoneInfo>> {
+        self.collect_at("zoneinfo", zoneinfo::collect_from)
+    }
+
+    /// returns the process with the given pid from `<root>/<pid>`
+    #[cfg(feature = "process")]
+    pub fn process(&self, pid: usize) -> CollectResult<Process> {
+        self.scope(|| process::collect_from(&self.root, pid))
+    }
+
+    /// returns all processes available under the mount point
+    #[cfg(feature = "process")]
+    pub fn processes(&self) -> CollectResult<Vec<Process>> {
+        self.scope(|| process::collect_all_from(&self.root))
+    }
+
+    /// reads the given fields of all processes available under the mount point
+    #[cfg(feature = "process")]
+    pub fn process_table(&self, fields: &[ProcessField]) -> CollectResult<ProcessTable> {
+        process_table::scan_from(self.fs.as_ref(), &self.root, fields)
+    }
+
+    /// builds the process tree of all processes available under the mount point
+    #[cfg(feature = "process")]
+    pub fn process_tree(&self) -> CollectResult<ProcessTree> {
+        self.scope(|| process_tree::collect_from(&self.root))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn procfs_alternate_root() {
+        let procfs = ProcFs::new("test_data/fixtures/proc");
+        assert_eq!(procfs.root(), Path::new("test_data/fixtures/proc"));
+        assert_eq!(
+            procfs.path("net/dev"),
+            PathBuf::from("test_data/fixtures/proc/net/dev"),
+        );
+
+        let meminfo = procfs.meminfo().expect("collecting memory information");
+        assert_eq!(meminfo.mem_total.unwrap(), 16042172416);
+
+        let sysload = procfs.loadavg().expect("collecting system load average");
+        assert_eq!(sysload.load1, 0.02);
+
+        #[cfg(feature = "net")]
+        assert_eq!(procfs.net_dev().expect("network devices").len(), 2);
+
+        #[cfg(feature = "process")]
+        {
+            assert_eq!(procfs.processes().expect("running procs").len(), 7);
+            assert_eq!(procfs.process(26231).unwrap().comm().unwrap(), "vim");
+        }
+
+        assert_eq!(ProcFs::default().root(), Path::new(DEFAULT_MOUNT_POINT));
+    }
+}
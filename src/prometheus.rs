@@ -0,0 +1,861 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    path::PathBuf,
+    thread,
+    time::Duration,
+};
+
+use serde_json::Value;
+
+use crate::{
+    collector::Registry,
+    error::{CollectResult, MetricError},
+};
+
+/// default address of the /metrics HTTP listener
+pub const DEFAULT_LISTEN_ADDRESS: &str = "127.0.0.1:9256";
+
+const METRIC_PREFIX: &str = "procsys";
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+// time a client has to send its request (and to read the response) before it is dropped
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+// longest request line read from a client, the rest of the request is ignored
+const MAX_REQUEST_LINE: u64 = 8192;
+
+/// MetricType is the Prometheus type of a metric
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricType {
+    Counter,
+    Gauge,
+}
+
+impl MetricType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MetricType::Counter => "counter",
+            MetricType::Gauge => "gauge",
+        }
+    }
+}
+
+/// Metric is a single Prometheus sample
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metric {
+    pub name: String,
+    pub help: String,
+    pub metric_type: MetricType,
+    pub labels: Vec<(String, String)>,
+    pub value: f64,
+}
+
+// a map (or list) in a collector output whose keys (or items) become label values
+struct LabelLevel {
+    // dot separated field path of the map, "" is the collector output itself and "*" any top level field
+    path: &'static str,
+    label: &'static str,
+    // list items field used as label value, the item index is used if not set
+    key: Option<&'static str>,
+    // list items fields added as extra labels
+    extra: &'static [&'static str],
+}
+
+struct MetricSpec {
+    collector: &'static str,
+    help: &'static str,
+    levels: &'static [LabelLevel],
+    // dot separated field paths which are counters, "*" matches everything and "name.*" a prefix
+    counters: &'static [&'static str],
+}
+
+macro_rules! level {
+    ($path:expr, $label:expr) => {
+        LabelLevel {
+            path: $path,
+            label: $label,
+            key: None,
+            extra: &[],
+        }
+    };
+    ($path:expr, $label:expr, $key:expr) => {
+        LabelLevel {
+            path: $path,
+            label: $label,
+            key: Some($key),
+            extra: &[],
+        }
+    };
+    ($path:expr, $label:expr, $key:expr, $extra:expr) => {
+        LabelLevel {
+            path: $path,
+            label: $label,
+            key: Some($key),
+            extra: $extra,
+        }
+    };
+}
+
+const METRIC_SPECS: &[MetricSpec] = &[
+    MetricSpec {
+        collector: "buddyinfo",
+        help: "free memory fragments from /proc/buddyinfo",
+        levels: &[
+            level!("", "zone", "zone", &["node"]),
+            level!("sizes", "order"),
+        ],
+        counters: &[],
+    },
+    MetricSpec {
+        collector: "block",
+        help: "block device from /sys/block",
+        levels: &[level!("", "device")],
+        counters: &[
+            "stat.discard_time",
+            "stat.discards_*",
+            "stat.flush_time",
+            "stat.flushes_*",
+            "stat.io_time",
+            "stat.read_time",
+            "stat.reads_*",
+            "stat.sectors_*",
+            "stat.weighted_io_time",
+            "stat.write_time",
+            "stat.writes_*",
+        ],
+    },
+    MetricSpec {
+        collector: "class_cooling",
+        help: "cooling device from /sys/class/thermal/cooling_device<X>",
+        levels: &[level!("", "name", "name", &["cooling_type"])],
+        counters: &[],
+    },
+    MetricSpec {
+        collector: "class_fibrechannel",
+        help: "fibre channel host from /sys/class/fc_host",
+        levels: &[level!("", "host")],
+        counters: &["statistics.*"],
+    },
+    MetricSpec {
+        collector: "class_infiniband",
+        help: "infiniband device from /sys/class/infiniband",
+        levels: &[level!("", "device"), level!("ports", "port")],
+        counters: &["ports.counters.*", "ports.hw_counters.*"],
+    },
+    MetricSpec {
+        collector: "class_power_supply",
+        help: "power supply from /sys/class/power_supply",
+        levels: &[level!("", "power_supply")],
+        counters: &[],
+    },
+    MetricSpec {
+        collector: "class_sas_phy",
+        help: "SAS phy from /sys/class/sas_phy",
+        levels: &[level!("", "phy")],
+        counters: &[
+            "invalid_dword_count",
+            "loss_of_dword_sync_count",
+            "phy_reset_problem_count",
+            "running_disparity_error_count",
+        ],
+    },
+    MetricSpec {
+        collector: "class_scsi_tape",
+        help: "SCSI tape statistics from /sys/class/scsi_tape",
+        levels: &[level!("", "device")],
+        counters: &[
+            "io_ns",
+            "other_cnt",
+            "read_byte_cnt",
+            "read_cnt",
+            "read_ns",
+            "resid_cnt",
+            "write_byte_cnt",
+            "write_cnt",
+            "write_ns",
+        ],
+    },
+    MetricSpec {
+        collector: "class_thermal",
+        help: "thermal zone from /sys/class/thermal/thermal_zone<X>",
+        levels: &[level!("", "zone", "name", &["zone_type"])],
+        counters: &[],
+    },
+    MetricSpec {
+        collector: "class_watchdog",
+        help: "watchdog device from /sys/class/watchdog",
+        levels: &[level!("", "device", "name")],
+        counters: &[],
+    },
+    MetricSpec {
+        collector: "cpuinfo",
+        help: "cpu information from /proc/cpuinfo",
+        levels: &[level!("", "cpu", "processor")],
+        counters: &[],
+    },
+    MetricSpec {
+        collector: "crypto",
+        help: "cryptographic cipher from /proc/crypto",
+        levels: &[level!("", "name", "name", &["driver"])],
+        counters: &[],
+    },
+    MetricSpec {
+        collector: "diskstats",
+        help: "block device I/O statistics from /proc/diskstats",
+        levels: &[level!("", "device", "name", &["major", "minor"])],
+        counters: &[
+            "discard_time",
+            "discards_*",
+            "flush_time",
+            "flushes_*",
+            "io_time",
+            "read_time",
+            "reads_*",
+            "sectors_*",
+            "weighted_io_time",
+            "write_time",
+            "writes_*",
+        ],
+    },
+    MetricSpec {
+        collector: "net_arp",
+        help: "ARP entry from /proc/net/arp",
+        levels: &[level!("", "ip_address", "ip_address", &["device"])],
+        counters: &[],
+    },
+    MetricSpec {
+        collector: "net_dev",
+        help: "network interface statistics from /proc/net/dev",
+        levels: &[level!("", "interface", "name")],
+        counters: &["*"],
+    },
+    MetricSpec {
+        collector: "net_protocols",
+        help: "network protocol from /proc/net/protocols",
+        levels: &[level!("", "protocol", "name")],
+        counters: &[],
+    },
+    MetricSpec {
+        collector: "net_sockstat",
+        help: "socket statistics from /proc/net/sockstat",
+        levels: &[level!("protocols", "protocol", "protocol")],
+        counters: &[],
+    },
+    MetricSpec {
+        collector: "net_sockstat6",
+        help: "socket statistics from /proc/net/sockstat6",
+        levels: &[level!("protocols", "protocol", "protocol")],
+        counters: &[],
+    },
+    MetricSpec {
+        collector: "net_wireless",
+        help: "wireless interface statistics from /proc/net/wireless",
+        levels: &[level!("", "interface", "name")],
+        counters: &["discarded_*", "missed_beacon"],
+    },
+    MetricSpec {
+        collector: "pagetypeinfo",
+        help: "free memory per migrate type from /proc/pagetypeinfo",
+        levels: &[
+            level!("zones", "zone", "zone", &["node"]),
+            level!("zones.free_blocks", "migrate_type"),
+            level!("zones.free_blocks", "order"),
+            level!("zones.blocks", "migrate_type"),
+            level!("zones.mixed_blocks", "migrate_type"),
+        ],
+        counters: &[],
+    },
+    MetricSpec {
+        collector: "partitions",
+        help: "block device from /proc/partitions",
+        levels: &[level!("", "device", "name", &["major", "minor"])],
+        counters: &[],
+    },
+    MetricSpec {
+        collector: "softirqs",
+        help: "softirq statistics from /proc/softirqs",
+        levels: &[level!("*", "cpu")],
+        counters: &["*"],
+    },
+    MetricSpec {
+        collector: "stat",
+        help: "kernel/system statistics from /proc/stat",
+        levels: &[level!("cpus", "cpu"), level!("intr", "irq")],
+        counters: &[
+            "cpu_total.*",
+            "cpus.*",
+            "ctxt",
+            "intr",
+            "intr_total",
+            "processes",
+            "softirq.*",
+        ],
+    },
+    MetricSpec {
+        collector: "swaps",
+        help: "swap device from /proc/swaps",
+        levels: &[level!("", "filename", "filename", &["swap_type"])],
+        counters: &[],
+    },
+    MetricSpec {
+        collector: "vmstat",
+        help: "virtual memory statistics from /proc/vmstat",
+        levels: &[level!("unknown", "name")],
+        counters: &[
+            "allocstall_*",
+            "compact_*",
+            "drop_*",
+            "kswapd_*",
+            "nr_dirtied",
+            "nr_written",
+            "numa_*",
+            "oom_kill",
+            "pageoutrun",
+            "pg*",
+            "pswp*",
+            "slabs_scanned",
+            "thp_*",
+            "workingset_*",
+        ],
+    },
+    MetricSpec {
+        collector: "zoneinfo",
+        help: "memory zone from /proc/zoneinfo",
+        levels: &[
+            level!("", "zone", "zone", &["node"]),
+            level!("pagesets", "cpu", "cpu"),
+        ],
+        counters: &[
+            "node_stats.nr_dirtied",
+            "node_stats.nr_written",
+            "node_stats.workingset_*",
+            "stats.numa_*",
+        ],
+    },
+];
+
+// collectors which do not provide metrics (e.g. the list of running processes), or
+// whose series would change with every scrape (e.g. the unix sockets by inode)
+const EXCLUDED_COLLECTORS: &[&str] = &["processes", "net_unix"];
+
+const DEFAULT_SPEC: MetricSpec = MetricSpec {
+    collector: "",
+    help: "",
+    levels: &[],
+    counters: &[],
+};
+
+/// returns the Prometheus metrics of a collector output
+/// # Example
+/// ```
+/// use procsys::{collector::Registry, prometheus};
+///
+/// let registry = Registry::default();
+/// let meminfo = registry.collect("meminfo").expect("memory information");
+///
+/// for metric in prometheus::metrics("meminfo", &meminfo) {
+///     println!("{} {}", metric.name, metric.value);
+/// }
+///
+/// ```
+pub fn metrics(collector: &str, value: &Value) -> Vec<Metric> {
+    let mut collector_metrics = Vec::new();
+
+    if EXCLUDED_COLLECTORS.contains(&collector) {
+        return collector_metrics;
+    }
+
+    let spec = METRIC_SPECS
+        .iter()
+        .find(|s| s.collector == collector)
+        .unwrap_or(&DEFAULT_SPEC);
+
+    let walker = MetricWalker {
+        collector,
+        spec,
+        metrics: &mut collector_metrics,
+    };
+    walker.walk(value, &mut Vec::new(), &mut Vec::new());
+
+    collector_metrics
+}
+
+/// renders metrics into the Prometheus text exposition format,
+/// metrics with the same name are grouped under a single HELP/TYPE header
+pub fn render(metrics: &[Metric]) -> String {
+    let mut families: BTreeMap<&str, Vec<&Metric>> = BTreeMap::new();
+    for metric in metrics {
+        families.entry(&metric.name).or_default().push(metric);
+    }
+
+    let mut output = String::new();
+
+    for (name, family) in families {
+        let _ = writeln!(output, "# HELP {} {}", name, escape_help(&family[0].help));
+        let _ = writeln!(output, "# TYPE {} {}", name, family[0].metric_type.as_str());
+
+        for metric in family {
+            output.push_str(name);
+
+            if !metric.labels.is_empty() {
+                let labels: Vec<String> = metric
+                    .labels
+                    .iter()
+                    .map(|(k, v)| format!("{}=\"{}\"", k, escape_label_value(v)))
+                    .collect();
+                let _ = write!(output, "{{{}}}", labels.join(","));
+            }
+
+            let _ = writeln!(output, " {}", format_value(metric.value));
+        }
+    }
+
+    output
+}
+
+/// runs all enabled collectors of the registry and renders their metrics into the
+/// Prometheus text exposition format, the status of each collector is reported by
+/// the `procsys_collector_success` gauge
+/// # Example
+/// ```
+/// use procsys::{collector::Registry, prometheus};
+///
+/// let registry = Registry::default();
+/// print!("{}", prometheus::render_registry(&registry));
+///
+/// ```
+pub fn render_registry(registry: &Registry) -> String {
+    let mut registry_metrics = Vec::new();
+
+    for (name, result) in registry.collect_all() {
+        let success = match result {
+            Ok(value) => {
+                registry_metrics.append(&mut metrics(&name, &value));
+                1.0
+            }
+            Err(err) => {
+                logging!(debug, "collector {} failed: {}", name, err);
+                0.0
+            }
+        };
+
+        registry_metrics.push(Metric {
+            name: format!("{}_collector_success", METRIC_PREFIX),
+            help: "whether the collector succeeded".to_string(),
+            metric_type: MetricType::Gauge,
+            labels: vec![("collector".to_string(), name)],
+            value: success,
+        });
+    }
+
+    render(&registry_metrics)
+}
+
+/// serves the registry metrics on `http://<addr>/metrics` until an error occurs,
+/// each scrape runs all enabled collectors. The connections are handled concurrently,
+/// a client which does not send its request in time is dropped.
+/// # Example
+/// ```no_run
+/// use procsys::{collector::Registry, prometheus};
+///
+/// let registry = Registry::default();
+/// prometheus::serve(prometheus::DEFAULT_LISTEN_ADDRESS, &registry).expect("metrics listener");
+///
+/// ```
+pub fn serve<A: ToSocketAddrs>(addr: A, registry: &Registry) -> CollectResult<()> {
+    serve_listener(TcpListener::bind(addr).map_err(listener_error)?, registry)
+}
+
+fn serve_listener(listener: TcpListener, registry: &Registry) -> CollectResult<()> {
+    thread::scope(|scope| {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    scope.spawn(move || {
+                        if let Err(err) = handle_connection(stream, registry) {
+                            logging!(warn, "metrics request failed: {}", err);
+                        }
+                    });
+                }
+                Err(err) => return Err(listener_error(err)),
+            }
+        }
+
+        Ok(())
+    })
+}
+
+fn handle_connection(mut stream: TcpStream, registry: &Registry) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+
+    let mut request_line = String::new();
+    BufReader::new(&stream)
+        .take(MAX_REQUEST_LINE)
+        .read_line(&mut request_line)?;
+
+    let request_fields: Vec<&str> = request_line.split_whitespace().collect();
+    let (status, content_type, body) = match request_fields.as_slice() {
+        _ if !request_line.ends_with('\n') => (
+            "400 Bad Request",
+            "text/plain",
+            "request line too long\n".to_string(),
+        ),
+        ["GET", "/metrics", ..] => ("200 OK", CONTENT_TYPE, render_registry(registry)),
+        ["GET", ..] => ("404 Not Found", "text/plain", "see /metrics\n".to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "method not allowed\n".to_string(),
+        ),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body,
+    )?;
+
+    stream.flush()
+}
+
+fn listener_error(err: std::io::Error) -> MetricError {
+    MetricError::IOError(PathBuf::from("/metrics"), err)
+}
+
+struct MetricWalker<'a> {
+    collector: &'a str,
+    spec: &'a MetricSpec,
+    metrics: &'a mut Vec<Metric>,
+}
+
+impl MetricWalker<'_> {
+    fn walk(mut self, value: &Value, path: &mut Vec<String>, labels: &mut Vec<(String, String)>) {
+        self.walk_value(value, path, labels);
+    }
+
+    fn walk_value(
+        &mut self,
+        value: &Value,
+        path: &mut Vec<String>,
+        labels: &mut Vec<(String, String)>,
+    ) {
+        // a path may have several levels (e.g. a map of lists), each label is used once
+        let level = self.spec.levels.iter().find(|l| {
+            level_matches(l.path, path) && !labels.iter().any(|(name, _)| name == l.label)
+        });
+
+        match (level, value) {
+            (Some(level), Value::Object(entries)) => {
+                for (key, entry) in entries {
+                    labels.push((level.label.to_string(), key.to_owned()));
+                    self.walk_item(entry, path, labels);
+                    labels.pop();
+                }
+            }
+            (level, Value::Array(items)) => {
+                let label = level.map(|l| l.label).unwrap_or("index");
+
+                for (index, item) in items.iter().enumerate() {
+                    let label_count = labels.len();
+
+                    let key_value = level
+                        .and_then(|l| l.key)
+                        .and_then(|k| item.get(k))
+                        .and_then(label_value)
+                        .unwrap_or_else(|| index.to_string());
+                    labels.push((label.to_string(), key_value));
+
+                    for extra in level.map(|l| l.extra).unwrap_or_default() {
+                        if let Some(extra_value) = item.get(extra).and_then(label_value) {
+                            labels.push((extra.to_string(), extra_value));
+                        }
+                    }
+
+                    match (level, item) {
+                        // fields used as label values are not exported as metrics
+                        (Some(level), Value::Object(fields)) => {
+                            let mut fields = fields.clone();
+                            for label_field in level.key.iter().chain(level.extra) {
+                                fields.remove(*label_field);
+                            }
+
+                            self.walk_item(&Value::Object(fields), path, labels);
+                        }
+                        _ => self.walk_item(item, path, labels),
+                    }
+
+                    labels.truncate(label_count);
+                }
+            }
+            _ => self.walk_item(value, path, labels),
+        }
+    }
+
+    fn walk_item(
+        &mut self,
+        value: &Value,
+        path: &mut Vec<String>,
+        labels: &mut Vec<(String, String)>,
+    ) {
+        match value {
+            Value::Number(number) => {
+                if let Some(v) = number.as_f64() {
+                    self.push(path, labels, v);
+                }
+            }
+            Value::Bool(b) => self.push(path, labels, if *b { 1.0 } else { 0.0 }),
+            Value::Object(fields) => {
+                for (name, field) in fields {
+                    // fields named after a label (e.g. the port number of a port) are redundant
+                    if labels.iter().any(|(l, _)| l == name) && !field.is_object() {
+                        continue;
+                    }
+
+                    path.push(name.to_owned());
+                    self.walk_value(field, path, labels);
+                    path.pop();
+                }
+            }
+            Value::Array(_) => self.walk_value(value, path, labels),
+            _ => {}
+        }
+    }
+
+    fn push(&mut self, path: &[String], labels: &[(String, String)], value: f64) {
+        let mut name = format!("{}_{}", METRIC_PREFIX, self.collector);
+        for field in path {
+            name.push('_');
+            name.push_str(field);
+        }
+
+        let metric_type = match self.spec.counters.iter().any(|c| counter_matches(c, path)) {
+            true => MetricType::Counter,
+            false => MetricType::Gauge,
+        };
+
+        let help = match self.spec.help.is_empty() {
+            true => format!("{} {}", self.collector, path.join(".")),
+            false => format!("{} {}", self.spec.help, path.join(".")),
+        };
+
+        self.metrics.push(Metric {
+            name: sanitize_name(&name),
+            help: help.trim().to_string(),
+            metric_type,
+            labels: labels.to_vec(),
+            value,
+        });
+    }
+}
+
+// matches a field path against a label level path, "" is the collector output
+// itself and "*" any top level field
+fn level_matches(pattern: &str, path: &[String]) -> bool {
+    match pattern {
+        "" => path.is_empty(),
+        "*" => path.len() == 1,
+        _ => path.join(".") == pattern,
+    }
+}
+
+// matches a field path against a counter pattern, "*" matches every field
+// and a trailing "*" any field starting with the prefix
+fn counter_matches(pattern: &str, path: &[String]) -> bool {
+    let joined = path.join(".");
+
+    match pattern.strip_suffix('*') {
+        Some(prefix) => joined.starts_with(prefix),
+        None => joined == pattern,
+    }
+}
+
+fn label_value(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.to_owned()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn sanitize_name(name: &str) -> String {
+    name.chars()
+        .map(
+            |c| match c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+                true => c,
+                false => '_',
+            },
+        )
+        .collect()
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        return "NaN".to_string();
+    }
+
+    if value.is_infinite() {
+        return match value > 0.0 {
+            true => "+Inf".to_string(),
+            false => "-Inf".to_string(),
+        };
+    }
+
+    format!("{}", value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{procfs::ProcFs, sysfs::SysFs};
+
+    fn test_registry() -> Registry {
+        Registry::with_mounts(
+            &ProcFs::new("test_data/fixtures/proc"),
+            &SysFs::new("test_data/fixtures/sys"),
+        )
+    }
+
+    #[test]
+    fn prometheus_render() {
+        let output = render_registry(&test_registry());
+
+        assert!(output.contains("# HELP procsys_meminfo_mem_total "));
+        assert!(output.contains("# TYPE procsys_meminfo_mem_total gauge\n"));
+        assert!(output.contains("\nprocsys_meminfo_mem_total 16042172416\n"));
+        assert!(output.contains("\nprocsys_loadavg_load1 0.02\n"));
+
+        assert!(output.contains("# TYPE procsys_net_dev_rx_bytes counter\n"));
+        assert!(output.contains("procsys_net_dev_rx_bytes{interface=\"vethf345468\"} 648\n"));
+        assert!(!output.contains("procsys_net_dev_name"));
+
+        assert!(output.contains("# TYPE procsys_stat_cpus_user counter\n"));
+        assert!(output.contains("procsys_stat_cpus_user{cpu=\"1\"} 47869\n"));
+        assert!(output.contains("procsys_stat_intr{irq=\"9\"} 79281\n"));
+        assert!(output.contains("# TYPE procsys_stat_procs_running gauge\n"));
+
+        assert!(output.contains("procsys_softirqs_timer{cpu=\"0\"} 2776180\n"));
+        assert!(output.contains("procsys_buddyinfo_sizes{zone=\"DMA\",node=\"0\",order=\"0\"}"));
+        assert!(output.contains(
+            "procsys_pagetypeinfo_zones_free_blocks{zone=\"DMA\",node=\"0\",migrate_type=\"Movable\",order=\"10\"} 3\n"
+        ));
+        assert!(output.contains("# TYPE procsys_zoneinfo_stats_numa_hit counter\n"));
+        assert!(output.contains(
+            "procsys_diskstats_reads_completed{device=\"sda\",major=\"8\",minor=\"0\"} 25354637\n"
+        ));
+        assert!(output.contains("# TYPE procsys_diskstats_io_in_progress gauge\n"));
+        assert!(output.contains(
+            "procsys_class_thermal_temp{zone=\"thermal_zone0\",zone_type=\"bcm2835_thermal\"} 49925\n"
+        ));
+        assert!(output
+            .contains("# TYPE procsys_class_infiniband_ports_counters_port_rcv_data counter\n"));
+        assert!(output.contains("procsys_class_fibrechannel_statistics_rx_frames{host=\"host0\"}"));
+
+        assert!(output.contains("procsys_collector_success{collector=\"meminfo\"} 1\n"));
+        assert!(!output.contains("procsys_processes"));
+        assert!(!output.contains("procsys_net_unix"));
+
+        let help_lines = output
+            .lines()
+            .filter(|l| l.starts_with("# HELP procsys_net_dev_rx_bytes "))
+            .count();
+        assert_eq!(help_lines, 1);
+    }
+
+    #[test]
+    fn prometheus_escape_and_match() {
+        let metric = Metric {
+            name: "procsys_test".to_string(),
+            help: "line\nbreak".to_string(),
+            metric_type: MetricType::Gauge,
+            labels: vec![("path".to_string(), "a\"b\\c".to_string())],
+            value: 1.5,
+        };
+
+        assert_eq!(
+            render(&[metric]),
+            "# HELP procsys_test line\\nbreak\n# TYPE procsys_test gauge\nprocsys_test{path=\"a\\\"b\\\\c\"} 1.5\n",
+        );
+
+        let path = vec!["statistics".to_string(), "rx_frames".to_string()];
+        assert!(counter_matches("statistics.*", &path));
+        assert!(counter_matches("*", &path));
+        assert!(!counter_matches("statistics", &path));
+        assert!(level_matches("*", &path[..1]));
+        assert!(!level_matches("*", &path));
+        assert!(!level_matches("*", &[]));
+        assert!(level_matches("", &[]));
+        assert_eq!(
+            sanitize_name("procsys_class_sas_phy-11:0"),
+            "procsys_class_sas_phy_11:0"
+        );
+    }
+
+    #[test]
+    fn prometheus_http_listener() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("metrics listener");
+        let addr = listener.local_addr().expect("metrics listener address");
+
+        let client = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).expect("metrics connection");
+            stream
+                .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+                .expect("metrics request");
+
+            let mut response = String::new();
+            stream
+                .read_to_string(&mut response)
+                .expect("metrics response");
+            response
+        });
+
+        let (stream, _) = listener.accept().expect("metrics client");
+        handle_connection(stream, &test_registry()).expect("metrics request handling");
+
+        let response = client.join().expect("metrics client result");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains(CONTENT_TYPE));
+        assert!(response.contains("\nprocsys_loadavg_load5 0.04\n"));
+    }
+    #[test]
+    fn prometheus_stalled_client() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("metrics listener");
+        let addr = listener.local_addr().expect("metrics listener address");
+        thread::spawn(move || serve_listener(listener, &test_registry()));
+
+        // clients which send nothing, or no complete request line, do not block the scrapes
+        let _stalled = TcpStream::connect(addr).expect("stalled connection");
+        let mut partial = TcpStream::connect(addr).expect("partial connection");
+        partial.write_all(b"GET /metr").expect("partial request");
+
+        let mut stream = TcpStream::connect(addr).expect("metrics connection");
+        stream
+            .set_read_timeout(Some(REQUEST_TIMEOUT / 2))
+            .expect("metrics client timeout");
+        stream
+            .write_all(b"GET /metrics HTTP/1.1\r\n\r\n")
+            .expect("metrics request");
+
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .expect("metrics response");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    }
+}
@@ -0,0 +1,392 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    collector::{FnCollector, Registry},
+    error::{CollectResult, MetricError},
+    procfs::ProcFs,
+    sysfs::SysFs,
+    utils,
+};
+
+/// SnapshotEntry is the recorded output of a single collector
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct SnapshotEntry {
+    /// file or directory the collector read from
+    pub source: PathBuf,
+
+    /// collected value, missing if the collector failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+
+    /// error message of the collector, missing if the collector succeeded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SnapshotEntry {
+    fn new(source: PathBuf, result: CollectResult<Value>) -> Self {
+        match result {
+            Ok(value) => Self {
+                source,
+                value: Some(value),
+                error: None,
+            },
+            Err(err) => Self {
+                source,
+                value: None,
+                error: Some(err.to_string()),
+            },
+        }
+    }
+
+    fn result(&self, name: &str) -> CollectResult<Value> {
+        match &self.value {
+            Some(value) => Ok(value.clone()),
+            None => Err(MetricError::SnapshotCollectorError(
+                name.to_string(),
+                self.error.clone().unwrap_or_default(),
+            )),
+        }
+    }
+}
+
+/// Snapshot bundles the output of all collectors taken at a point in time,
+/// it can be written to disk and read back later to be analyzed like live data
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Snapshot {
+    /// time the snapshot was taken
+    pub timestamp: SystemTime,
+
+    /// hostname of the system the snapshot was taken from
+    pub hostname: String,
+
+    /// output of the collectors keyed by collector name
+    pub collectors: BTreeMap<String, SnapshotEntry>,
+}
+
+impl Snapshot {
+    /// takes a snapshot of all built-in collectors reading from the given mount points
+    pub fn with_mounts(procfs: &ProcFs, sysfs: &SysFs) -> Self {
+        let registry = Registry::with_mounts(procfs, sysfs);
+
+        let hostname = procfs
+            .scope(|| utils::collect_info_string("hostname", &procfs.path("sys/kernel")))
+            .unwrap_or_default()
+            .unwrap_or_default();
+
+        Self::from_registry(&registry, &hostname)
+    }
+
+    /// takes a snapshot of the enabled collectors of a registry
+    pub fn from_registry(registry: &Registry, hostname: &str) -> Self {
+        let mut collectors = BTreeMap::new();
+        let timestamp = SystemTime::now();
+
+        for name in registry.names() {
+            if !registry.is_enabled(name) {
+                continue;
+            }
+
+            if let Some(collector) = registry.get(name) {
+                let entry = SnapshotEntry::new(collector.source(), collector.collect());
+                collectors.insert(name.to_string(), entry);
+            }
+        }
+
+        Self {
+            timestamp,
+            hostname: hostname.to_string(),
+            collectors,
+        }
+    }
+
+    /// reads a snapshot previously written with [`Snapshot::write_to`]
+    pub fn read_from<P: AsRef<Path>>(filename: P) -> CollectResult<Self> {
+        let filename = filename.as_ref();
+
+        let content = match fs::read_to_string(filename) {
+            Ok(c) => c,
+            Err(err) => return Err(MetricError::IOError(filename.to_path_buf(), err)),
+        };
+
+        serde_json::from_str(&content).map_err(MetricError::SerdeJsonError)
+    }
+
+    /// writes the snapshot as json to the given file
+    pub fn write_to<P: AsRef<Path>>(&self, filename: P) -> CollectResult<()> {
+        let filename = filename.as_ref();
+        let content = serde_json::to_string_pretty(self).map_err(MetricError::SerdeJsonError)?;
+
+        match fs::write(filename, content) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(MetricError::IOError(filename.to_path_buf(), err)),
+        }
+    }
+
+    /// returns the recorded output of a collector
+    pub fn value(&self, name: &str) -> CollectResult<Value> {
+        match self.collectors.get(name) {
+            Some(entry) => entry.result(name),
+            None => Err(MetricError::CollectorNotFound(name.to_string())),
+        }
+    }
+
+    /// returns the recorded output of a collector as the type returned by the
+    /// live collection (e.g. `Meminfo` for `meminfo` or `Vec<NetDev>` for `net_dev`)
+    pub fn get<T: DeserializeOwned>(&self, name: &str) -> CollectResult<T> {
+        serde_json::from_value(self.value(name)?).map_err(MetricError::SerdeJsonError)
+    }
+
+    /// returns a registry whose collectors replay the recorded output of the snapshot
+    pub fn registry(&self) -> Registry {
+        let mut registry = Registry::new();
+
+        for (name, entry) in &self.collectors {
+            let collector_name = name.to_owned();
+            let collector_entry = entry.clone();
+
+            registry.register(Box::new(FnCollector::new(
+                name,
+                entry.source.clone(),
+                move || collector_entry.result(&collector_name),
+            )));
+        }
+
+        registry
+    }
+
+    /// returns the time elapsed between an earlier snapshot and this one
+    pub fn elapsed_since(&self, earlier: &Snapshot) -> Duration {
+        self.timestamp
+            .duration_since(earlier.timestamp)
+            .unwrap_or_default()
+    }
+}
+
+/// takes a snapshot of all built-in collectors of the system
+/// # Example
+/// ```
+/// use procsys::{meminfo::Meminfo, snapshot::{self, Snapshot}};
+///
+/// let sys_snapshot = snapshot::collect();
+/// let filename = std::env::temp_dir().join("procsys-snapshot-example.json");
+/// sys_snapshot.write_to(&filename).expect("snapshot written");
+///
+/// let stored_snapshot = Snapshot::read_from(&filename).expect("snapshot read");
+/// let stored_meminfo: Meminfo = stored_snapshot.get("meminfo").expect("memory information");
+/// println!("{}: {:?}", stored_snapshot.hostname, stored_meminfo.mem_total);
+///
+/// ```
+pub fn collect() -> Snapshot {
+    Snapshot::with_mounts(&ProcFs::default(), &SysFs::default())
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(any(
+        feature = "sysfs-storage",
+        feature = "sysfs-power",
+        feature = "sysfs-hw"
+    ))]
+    use std::collections::HashMap;
+
+    use super::*;
+    #[cfg(feature = "process")]
+    use crate::process::Process;
+    #[cfg(feature = "sysfs-storage")]
+    use crate::sysfs::{
+        block::BlockDevice, class_fibrechannel::FibreChannelHost, class_nvme::NVMeDevice,
+        class_sas_device::SASDevice, class_sas_host::SASHost, class_sas_phy::SASPhy,
+        class_sas_port::SASPort, class_scsi_tape::ScsiTapeCounters,
+    };
+    #[cfg(feature = "sysfs-power")]
+    use crate::sysfs::{
+        class_cooling::Cooling, class_power_supply::PowerSupply, class_thermal::ThermalZone,
+    };
+    #[cfg(feature = "sysfs-hw")]
+    use crate::sysfs::{
+        class_dmi::DMI, class_infiniband::InfiniBandDevice, class_watchdog::Watchdog,
+        clocksource::Clocksource,
+    };
+    use crate::{
+        buddyinfo::BuddyInfo, cpuinfo::CpuInfo, crypto::Crypto, diskstats::DiskStats,
+        kernel_random::KernelRandom, loadavg::LoadAvg, meminfo::Meminfo,
+        pagetypeinfo::PageTypeInfo, partitions::Partition, softirqs::Softirqs, stat::Stat,
+        swaps::Swaps, vmstat::Vmstat, zoneinfo::ZoneInfo,
+    };
+    #[cfg(feature = "net")]
+    use crate::{
+        net_arp::ARPEntry, net_dev::NetDev, net_protocols::NetProtocol, net_sockstat::NetSockStat,
+        net_unix::NetUnix, net_wireless::Wireless,
+    };
+
+    fn fixtures_snapshot() -> Snapshot {
+        Snapshot::with_mounts(
+            &ProcFs::new("test_data/fixtures/proc"),
+            &SysFs::new("test_data/fixtures/sys"),
+        )
+    }
+
+    // the recorded value deserializes into the collector type without losing any field
+    fn assert_roundtrip<T: Serialize + DeserializeOwned>(snapshot: &Snapshot, name: &str) {
+        let typed: T = snapshot.get(name).expect(name);
+
+        assert_eq!(
+            serde_json::to_value(typed).expect(name),
+            snapshot.value(name).expect(name),
+            "{} roundtrip",
+            name,
+        );
+    }
+
+    #[test]
+    fn snapshot_write_read() {
+        let sys_snapshot = fixtures_snapshot();
+
+        assert_eq!(sys_snapshot.hostname, "procsys-test");
+        assert_eq!(
+            sys_snapshot.collectors.len(),
+            crate::collector::builtin_collectors(
+                &ProcFs::new("test_data/fixtures/proc"),
+                &SysFs::new("test_data/fixtures/sys"),
+            )
+            .len(),
+        );
+        assert_eq!(
+            sys_snapshot.collectors["meminfo"].source,
+            Path::new("test_data/fixtures/proc/meminfo"),
+        );
+
+        let filename =
+            std::env::temp_dir().join(format!("procsys-snapshot-{}.json", std::process::id()));
+        sys_snapshot.write_to(&filename).expect("snapshot written");
+        let stored_snapshot = Snapshot::read_from(&filename).expect("snapshot read");
+        fs::remove_file(&filename).expect("snapshot removed");
+
+        assert_eq!(stored_snapshot, sys_snapshot);
+        assert_eq!(stored_snapshot.elapsed_since(&sys_snapshot), Duration::ZERO);
+        assert!(Snapshot::read_from(&filename).is_err());
+
+        let stored_meminfo: Meminfo = stored_snapshot.get("meminfo").expect("memory information");
+        assert_eq!(stored_meminfo.mem_total, Some(16042172416));
+
+        let replay_registry = stored_snapshot.registry();
+        let live_registry = Registry::with_mounts(
+            &ProcFs::new("test_data/fixtures/proc"),
+            &SysFs::new("test_data/fixtures/sys"),
+        );
+        let mut live_names = live_registry.names();
+        live_names.sort();
+        assert_eq!(replay_registry.names(), live_names);
+        assert_eq!(
+            replay_registry.collect("diskstats").unwrap(),
+            live_registry.collect("diskstats").unwrap(),
+        );
+        assert_eq!(
+            replay_registry.get("loadavg").unwrap().source(),
+            Path::new("test_data/fixtures/proc/loadavg"),
+        );
+    }
+
+    #[test]
+    fn snapshot_collector_error() {
+        let mut registry = Registry::new();
+        registry.register(Box::new(FnCollector::new("answer", "/dev/null", || Ok(42))));
+        registry.register(Box::new(FnCollector::new(
+            "missing",
+            "/proc/missing",
+            || -> CollectResult<u64> { Err(MetricError::PathNotFound("/proc/missing".into())) },
+        )));
+
+        let sys_snapshot = Snapshot::from_registry(&registry, "localhost");
+
+        assert_eq!(sys_snapshot.get::<u64>("answer").unwrap(), 42);
+        assert!(sys_snapshot.get::<String>("answer").is_err());
+        assert!(sys_snapshot.get::<u64>("unknown").is_err());
+        assert_eq!(
+            sys_snapshot.collectors["missing"].error.as_deref(),
+            Some("path (\"/proc/missing\") not found"),
+        );
+
+        match sys_snapshot.registry().collect("missing") {
+            Err(MetricError::SnapshotCollectorError(name, _)) => assert_eq!(name, "missing"),
+            result => panic!("unexpected replay result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn snapshot_typed_roundtrip() {
+        let sys_snapshot = fixtures_snapshot();
+
+        assert_roundtrip::<Vec<BuddyInfo>>(&sys_snapshot, "buddyinfo");
+        assert_roundtrip::<Vec<String>>(&sys_snapshot, "cmdline");
+        assert_roundtrip::<Vec<CpuInfo>>(&sys_snapshot, "cpuinfo");
+        assert_roundtrip::<Vec<Crypto>>(&sys_snapshot, "crypto");
+        assert_roundtrip::<Vec<DiskStats>>(&sys_snapshot, "diskstats");
+        assert_roundtrip::<KernelRandom>(&sys_snapshot, "kernel_random");
+        assert_roundtrip::<LoadAvg>(&sys_snapshot, "loadavg");
+        assert_roundtrip::<Meminfo>(&sys_snapshot, "meminfo");
+        assert_roundtrip::<PageTypeInfo>(&sys_snapshot, "pagetypeinfo");
+        assert_roundtrip::<Vec<Partition>>(&sys_snapshot, "partitions");
+        assert_roundtrip::<Softirqs>(&sys_snapshot, "softirqs");
+        assert_roundtrip::<Stat>(&sys_snapshot, "stat");
+        assert_roundtrip::<Vec<Swaps>>(&sys_snapshot, "swaps");
+        assert_roundtrip::<Vmstat>(&sys_snapshot, "vmstat");
+        assert_roundtrip::<Vec<ZoneInfo>>(&sys_snapshot, "zoneinfo");
+
+        #[cfg(feature = "net")]
+        {
+            assert_roundtrip::<Vec<ARPEntry>>(&sys_snapshot, "net_arp");
+            assert_roundtrip::<Vec<NetDev>>(&sys_snapshot, "net_dev");
+            assert_roundtrip::<Vec<NetProtocol>>(&sys_snapshot, "net_protocols");
+            assert_roundtrip::<NetSockStat>(&sys_snapshot, "net_sockstat");
+            assert_roundtrip::<NetSockStat>(&sys_snapshot, "net_sockstat6");
+            assert_roundtrip::<Vec<NetUnix>>(&sys_snapshot, "net_unix");
+            assert_roundtrip::<Vec<Wireless>>(&sys_snapshot, "net_wireless");
+        }
+
+        #[cfg(feature = "process")]
+        assert_roundtrip::<Vec<Process>>(&sys_snapshot, "processes");
+
+        #[cfg(feature = "sysfs-storage")]
+        {
+            assert_roundtrip::<HashMap<String, BlockDevice>>(&sys_snapshot, "block");
+            assert_roundtrip::<HashMap<String, FibreChannelHost>>(
+                &sys_snapshot,
+                "class_fibrechannel",
+            );
+            assert_roundtrip::<HashMap<String, NVMeDevice>>(&sys_snapshot, "class_nvme");
+            assert_roundtrip::<HashMap<String, SASDevice>>(&sys_snapshot, "class_sas_device");
+            assert_roundtrip::<HashMap<String, SASHost>>(&sys_snapshot, "class_sas_host");
+            assert_roundtrip::<HashMap<String, SASPhy>>(&sys_snapshot, "class_sas_phy");
+            assert_roundtrip::<HashMap<String, SASPort>>(&sys_snapshot, "class_sas_port");
+            assert_roundtrip::<HashMap<String, ScsiTapeCounters>>(&sys_snapshot, "class_scsi_tape");
+        }
+
+        #[cfg(feature = "sysfs-power")]
+        {
+            assert_roundtrip::<Vec<Cooling>>(&sys_snapshot, "class_cooling");
+            assert_roundtrip::<HashMap<String, PowerSupply>>(&sys_snapshot, "class_power_supply");
+            assert_roundtrip::<Vec<ThermalZone>>(&sys_snapshot, "class_thermal");
+        }
+
+        #[cfg(feature = "sysfs-hw")]
+        {
+            assert_roundtrip::<DMI>(&sys_snapshot, "class_dmi");
+            assert_roundtrip::<HashMap<String, InfiniBandDevice>>(
+                &sys_snapshot,
+                "class_infiniband",
+            );
+            assert_roundtrip::<Vec<Watchdog>>(&sys_snapshot, "class_watchdog");
+            assert_roundtrip::<Vec<Clocksource>>(&sys_snapshot, "clocksource");
+        }
+    }
+}
@@ -1,9 +1,13 @@
-use serde::Serialize;
+use std::path::Path;
 
-use crate::{error::CollectResult, utils};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{error::CollectResult, procfs::ProcFs, utils};
 
 /// Softirqs represents the softirq statistics
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Softirqs {
     pub hi: Vec<u64>,
     pub timer: Vec<u64>,
@@ -29,15 +33,18 @@ impl Softirqs {
 /// use procsys::softirqs;
 ///
 /// let sys_softirqs = softirqs::collect().expect("softirqs information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&sys_softirqs).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<Softirqs> {
-    collect_from("/proc/softirqs")
+    ProcFs::default().softirqs()
 }
 
-fn collect_from(filename: &str) -> CollectResult<Softirqs> {
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<Softirqs> {
     let mut proc_softirqs = Softirqs::new();
 
     let irqsdata = utils::read_file_lines(filename)?;
@@ -120,7 +127,7 @@ mod tests {
 
     #[test]
     fn softirqs_stats() {
-        let sys_softirqs = collect_from("test_data/fixtures/proc/softirqs")
+        let sys_softirqs = collect_from(Path::new("test_data/fixtures/proc/softirqs"))
             .expect("collecting softirqs information");
 
         assert_eq!(sys_softirqs.hi, [3, 0]);
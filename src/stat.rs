@@ -0,0 +1,362 @@
+use std::{collections::HashMap, path::Path};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{CollectResult, MetricError},
+    procfs::ProcFs,
+    utils,
+};
+
+// default number of clock ticks per second (USER_HZ) used when it cannot be queried
+const DEFAULT_CLOCK_TICKS: u64 = 100;
+
+/// returns the number of clock ticks (jiffies) per second of the system
+pub fn clock_ticks() -> u64 {
+    // SAFETY: sysconf has no side effects and is always safe to call
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+
+    match ticks > 0 {
+        true => ticks as u64,
+        false => DEFAULT_CLOCK_TICKS,
+    }
+}
+
+/// converts a value in clock ticks (jiffies) to seconds using the system clock tick rate
+pub fn jiffies_to_seconds(jiffies: u64) -> f64 {
+    jiffies as f64 / clock_ticks() as f64
+}
+
+/// CpuStat represents the time (in clock ticks) a cpu spent in the different modes
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuStat {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64,
+    pub guest: u64,
+    pub guest_nice: u64,
+}
+
+impl CpuStat {
+    fn new() -> Self {
+        Default::default()
+    }
+
+    /// returns the total time in clock ticks, guest time is already
+    /// accounted in user and nice and it's not counted twice
+    pub fn total(&self) -> u64 {
+        self.user
+            + self.nice
+            + self.system
+            + self.idle
+            + self.iowait
+            + self.irq
+            + self.softirq
+            + self.steal
+    }
+
+    /// returns the time in clock ticks the cpu was neither idle nor waiting for I/O
+    pub fn busy(&self) -> u64 {
+        self.total() - self.idle - self.iowait
+    }
+
+    /// returns the cpu times converted from clock ticks to seconds
+    pub fn seconds(&self) -> CpuStatSeconds {
+        self.seconds_with_ticks(clock_ticks())
+    }
+
+    /// returns the cpu times converted to seconds with the given clock ticks per second
+    pub fn seconds_with_ticks(&self, ticks: u64) -> CpuStatSeconds {
+        let to_seconds = |v: u64| v as f64 / ticks as f64;
+
+        CpuStatSeconds {
+            user: to_seconds(self.user),
+            nice: to_seconds(self.nice),
+            system: to_seconds(self.system),
+            idle: to_seconds(self.idle),
+            iowait: to_seconds(self.iowait),
+            irq: to_seconds(self.irq),
+            softirq: to_seconds(self.softirq),
+            steal: to_seconds(self.steal),
+            guest: to_seconds(self.guest),
+            guest_nice: to_seconds(self.guest_nice),
+        }
+    }
+}
+
+/// CpuStatSeconds represents the time (in seconds) a cpu spent in the different modes
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuStatSeconds {
+    pub user: f64,
+    pub nice: f64,
+    pub system: f64,
+    pub idle: f64,
+    pub iowait: f64,
+    pub irq: f64,
+    pub softirq: f64,
+    pub steal: f64,
+    pub guest: f64,
+    pub guest_nice: f64,
+}
+
+/// SoftIRQStat represents the softirq line of /proc/stat
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SoftIRQStat {
+    pub total: u64,
+    pub hi: u64,
+    pub timer: u64,
+    pub net_tx: u64,
+    pub net_rx: u64,
+    pub block: u64,
+    pub irq_poll: u64,
+    pub tasklet: u64,
+    pub sched: u64,
+    pub hrtimer: u64,
+    pub rcu: u64,
+}
+
+/// Stat represents the kernel/system statistics from /proc/stat
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Stat {
+    /// sum of all cpus
+    pub cpu_total: CpuStat,
+
+    /// per cpu statistics (offline cpus are missing)
+    pub cpus: HashMap<usize, CpuStat>,
+
+    /// total number of serviced interrupts
+    pub intr_total: u64,
+
+    /// number of serviced interrupts per numbered interrupt
+    pub intr: Vec<u64>,
+
+    /// number of context switches
+    pub ctxt: u64,
+
+    /// boot time in seconds since the epoch
+    pub btime: u64,
+
+    /// number of forks since boot
+    pub processes: u64,
+
+    /// number of processes in runnable state
+    pub procs_running: u64,
+
+    /// number of processes blocked waiting for I/O
+    pub procs_blocked: u64,
+
+    pub softirq: SoftIRQStat,
+}
+
+impl Stat {
+    fn new() -> Self {
+        Default::default()
+    }
+}
+
+/// collects the kernel/system statistics
+/// # Example
+/// ```
+/// use procsys::stat;
+///
+/// let sys_stat = stat::collect().expect("system statistics");
+/// # #[cfg(feature = "serde")]
+/// # {
+/// let json_output = serde_json::to_string_pretty(&sys_stat).unwrap();
+/// println!("{}", json_output);
+/// # }
+///
+/// println!("user seconds: {}", sys_stat.cpu_total.seconds().user);
+///
+/// ```
+pub fn collect() -> CollectResult<Stat> {
+    ProcFs::default().stat()
+}
+
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<Stat> {
+    let mut sys_stat = Stat::new();
+
+    for line in utils::read_file_lines(filename)? {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.is_empty() {
+            continue;
+        }
+
+        match fields[0] {
+            "cpu" => sys_stat.cpu_total = parse_cpu_stat(&fields, &line)?,
+            "intr" => {
+                let values = parse_values(&fields[1..])?;
+                sys_stat.intr_total = values.first().copied().unwrap_or_default();
+                sys_stat.intr = values.into_iter().skip(1).collect();
+            }
+            "ctxt" => sys_stat.ctxt = parse_single_value(&fields, &line)?,
+            "btime" => sys_stat.btime = parse_single_value(&fields, &line)?,
+            "processes" => sys_stat.processes = parse_single_value(&fields, &line)?,
+            "procs_running" => sys_stat.procs_running = parse_single_value(&fields, &line)?,
+            "procs_blocked" => sys_stat.procs_blocked = parse_single_value(&fields, &line)?,
+            "softirq" => sys_stat.softirq = parse_softirq_stat(&fields, &line)?,
+            name => {
+                if let Some(cpu_id) = name.strip_prefix("cpu") {
+                    let cpu_id = utils::convert_str_to_u64(cpu_id)? as usize;
+                    sys_stat
+                        .cpus
+                        .insert(cpu_id, parse_cpu_stat(&fields, &line)?);
+                }
+            }
+        }
+    }
+
+    Ok(sys_stat)
+}
+
+fn parse_values(fields: &[&str]) -> CollectResult<Vec<u64>> {
+    fields
+        .iter()
+        .map(|v| utils::convert_str_to_u64(v))
+        .collect()
+}
+
+fn parse_single_value(fields: &[&str], line: &str) -> CollectResult<u64> {
+    if fields.len() != 2 {
+        return Err(MetricError::InvalidFieldNumberError(
+            "stat".to_string(),
+            fields.len(),
+            line.to_string(),
+        ));
+    }
+
+    utils::convert_str_to_u64(fields[1])
+}
+
+fn parse_cpu_stat(fields: &[&str], line: &str) -> CollectResult<CpuStat> {
+    // older kernels do not report steal, guest and guest_nice
+    if fields.len() < 5 {
+        return Err(MetricError::InvalidFieldNumberError(
+            "stat cpu".to_string(),
+            fields.len(),
+            line.to_string(),
+        ));
+    }
+
+    let mut cpu_stat = CpuStat::new();
+    let values = parse_values(&fields[1..])?;
+    let value = |index: usize| values.get(index).copied().unwrap_or_default();
+
+    cpu_stat.user = value(0);
+    cpu_stat.nice = value(1);
+    cpu_stat.system = value(2);
+    cpu_stat.idle = value(3);
+    cpu_stat.iowait = value(4);
+    cpu_stat.irq = value(5);
+    cpu_stat.softirq = value(6);
+    cpu_stat.steal = value(7);
+    cpu_stat.guest = value(8);
+    cpu_stat.guest_nice = value(9);
+
+    Ok(cpu_stat)
+}
+
+fn parse_softirq_stat(fields: &[&str], line: &str) -> CollectResult<SoftIRQStat> {
+    if fields.len() < 11 {
+        return Err(MetricError::InvalidFieldNumberError(
+            "stat softirq".to_string(),
+            fields.len(),
+            line.to_string(),
+        ));
+    }
+
+    let values = parse_values(&fields[1..])?;
+
+    Ok(SoftIRQStat {
+        total: values[0],
+        hi: values[1],
+        timer: values[2],
+        net_tx: values[3],
+        net_rx: values[4],
+        block: values[5],
+        irq_poll: values[6],
+        tasklet: values[7],
+        sched: values[8],
+        hrtimer: values[9],
+        rcu: values.get(10).copied().unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sys_stat() {
+        let sys_stat =
+            collect_from(Path::new("test_data/fixtures/proc/stat")).expect("system statistics");
+
+        assert_eq!(sys_stat.cpu_total.user, 301854);
+        assert_eq!(sys_stat.cpu_total.nice, 612);
+        assert_eq!(sys_stat.cpu_total.system, 111922);
+        assert_eq!(sys_stat.cpu_total.idle, 8979004);
+        assert_eq!(sys_stat.cpu_total.iowait, 3552);
+        assert_eq!(sys_stat.cpu_total.irq, 2);
+        assert_eq!(sys_stat.cpu_total.softirq, 3944);
+        assert_eq!(sys_stat.cpu_total.steal, 0);
+        assert_eq!(sys_stat.cpu_total.guest, 0);
+        assert_eq!(sys_stat.cpu_total.guest_nice, 0);
+        assert_eq!(sys_stat.cpu_total.total(), 9400890);
+        assert_eq!(sys_stat.cpu_total.busy(), 418334);
+
+        assert_eq!(sys_stat.cpus.len(), 4);
+        assert_eq!(sys_stat.cpus[&1].user, 47869);
+        assert_eq!(sys_stat.cpus[&3].nice, 102);
+
+        assert_eq!(sys_stat.intr_total, 8885917);
+        assert_eq!(sys_stat.intr.len(), 29);
+        assert_eq!(sys_stat.intr[0], 17);
+        assert_eq!(sys_stat.intr[9], 79281);
+        assert_eq!(sys_stat.ctxt, 38014093);
+        assert_eq!(sys_stat.btime, 1418183276);
+        assert_eq!(sys_stat.processes, 26442);
+        assert_eq!(sys_stat.procs_running, 2);
+        assert_eq!(sys_stat.procs_blocked, 1);
+
+        assert_eq!(sys_stat.softirq.total, 5057579);
+        assert_eq!(sys_stat.softirq.hi, 250191);
+        assert_eq!(sys_stat.softirq.timer, 1481983);
+        assert_eq!(sys_stat.softirq.net_tx, 1647);
+        assert_eq!(sys_stat.softirq.net_rx, 211099);
+        assert_eq!(sys_stat.softirq.block, 186066);
+        assert_eq!(sys_stat.softirq.irq_poll, 0);
+        assert_eq!(sys_stat.softirq.tasklet, 1783454);
+        assert_eq!(sys_stat.softirq.sched, 622196);
+        assert_eq!(sys_stat.softirq.hrtimer, 12499);
+        assert_eq!(sys_stat.softirq.rcu, 508444);
+    }
+
+    #[test]
+    fn cpu_stat_seconds() {
+        let cpu_stat = CpuStat {
+            user: 250,
+            system: 100,
+            idle: 1000,
+            ..Default::default()
+        };
+
+        let cpu_seconds = cpu_stat.seconds_with_ticks(100);
+        assert_eq!(cpu_seconds.user, 2.5);
+        assert_eq!(cpu_seconds.system, 1.0);
+        assert_eq!(cpu_seconds.idle, 10.0);
+        assert_eq!(cpu_seconds.iowait, 0.0);
+
+        assert!(clock_ticks() > 0);
+        assert_eq!(jiffies_to_seconds(clock_ticks() * 3), 3.0,);
+    }
+}
@@ -1,12 +1,17 @@
-use serde::Serialize;
+use std::path::Path;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    error::{CollectResult, MetricError},
+    error::{CollectResult, MetricError, Partial},
+    procfs::ProcFs,
     utils,
 };
 
 /// LoadAvg represents an entry in /proc/loadavg
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Swaps {
     pub filename: String,
     pub swap_type: String,
@@ -27,44 +32,64 @@ impl Swaps {
 /// use procsys::swaps;
 ///
 /// let sys_swapinfo = swaps::collect().expect("swaps information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&sys_swapinfo).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<Vec<Swaps>> {
-    collect_from("/proc/swaps")
+    ProcFs::default().swaps()
+}
+
+/// collects the swap devices, a malformed line of /proc/swaps is reported under its
+/// filename
+pub fn collect_lenient() -> CollectResult<Partial<Vec<Swaps>>> {
+    ProcFs::default().swaps_lenient()
 }
 
-fn collect_from(filename: &str) -> CollectResult<Vec<Swaps>> {
-    let mut swaps_info: Vec<Swaps> = Vec::new();
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<Vec<Swaps>> {
+    collect_lenient_from(filename)?.into_result()
+}
+
+pub(crate) fn collect_lenient_from(filename: &Path) -> CollectResult<Partial<Vec<Swaps>>> {
+    let mut swaps_info: Partial<Vec<Swaps>> = Partial::default();
     let swaps_data = utils::read_file_lines(filename)?;
 
-    for line in &swaps_data[1..] {
-        let swap_info = line.to_owned();
-        let swap_info_field: Vec<&str> = swap_info
-            .split_whitespace()
-            .filter(|s| !s.is_empty())
-            .collect();
-
-        if swap_info_field.len() != 5 {
-            return Err(MetricError::InvalidFieldNumberError(
-                "swaps".to_string(),
-                swap_info_field.len(),
-                line.to_owned(),
-            ));
+    for (line_index, line) in swaps_data.iter().enumerate().skip(1) {
+        match parse_swap(line) {
+            Ok(swap_data) => swaps_info.items.push(swap_data),
+            Err(err) => swaps_info.push_error(
+                &utils::line_item(line, line_index + 1),
+                filename,
+                err.with_line(filename, line_index + 1),
+            ),
         }
+    }
 
-        let mut swap_data = Swaps::new();
-        swap_data.filename = swap_info_field[0].to_string();
-        swap_data.swap_type = swap_info_field[1].to_string();
-        swap_data.size = swap_info_field[2].parse::<u64>().unwrap_or_default();
-        swap_data.used = swap_info_field[3].parse::<u64>().unwrap_or_default();
-        swap_data.priority = swap_info_field[4].parse::<i32>().unwrap_or_default();
+    Ok(swaps_info)
+}
+
+fn parse_swap(line: &str) -> CollectResult<Swaps> {
+    let swap_info_field: Vec<&str> = line.split_whitespace().filter(|s| !s.is_empty()).collect();
 
-        swaps_info.push(swap_data);
+    if swap_info_field.len() != 5 {
+        return Err(MetricError::InvalidFieldNumberError(
+            "swaps".to_string(),
+            swap_info_field.len(),
+            line.to_owned(),
+        ));
     }
 
-    Ok(swaps_info)
+    let mut swap_data = Swaps::new();
+    swap_data.filename = swap_info_field[0].to_string();
+    swap_data.swap_type = swap_info_field[1].to_string();
+    swap_data.size = swap_info_field[2].parse::<u64>().unwrap_or_default();
+    swap_data.used = swap_info_field[3].parse::<u64>().unwrap_or_default();
+    swap_data.priority = swap_info_field[4].parse::<i32>().unwrap_or_default();
+
+    Ok(swap_data)
 }
 
 #[cfg(test)]
@@ -73,7 +98,8 @@ mod tests {
 
     #[test]
     fn system_swaps() {
-        let swaps_info = collect_from("test_data/fixtures/proc/swaps").expect("swaps information");
+        let swaps_info =
+            collect_from(Path::new("test_data/fixtures/proc/swaps")).expect("swaps information");
 
         assert_eq!(swaps_info.len(), 2);
 
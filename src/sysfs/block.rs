@@ -0,0 +1,219 @@
+use std::{collections::HashMap, path::Path};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    diskstats::{self, DiskStats},
+    error::{CollectResult, MetricError, Partial},
+    filesystem,
+    sysfs::SysFs,
+    utils,
+};
+
+/// BlockQueue contains the request queue attributes of a block device from
+/// /sys/block/<dev>/queue
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BlockQueue {
+    pub rotational: Option<bool>,
+    pub logical_block_size: Option<u64>,
+    pub physical_block_size: Option<u64>,
+
+    /// active I/O scheduler
+    pub scheduler: Option<String>,
+
+    /// I/O schedulers available for the device
+    pub schedulers: Vec<String>,
+
+    pub nr_requests: Option<u64>,
+}
+
+impl BlockQueue {
+    fn new() -> Self {
+        Default::default()
+    }
+}
+
+/// BlockDevice contains the I/O statistics and the queue attributes of a block device
+/// from /sys/block/<dev>
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BlockDevice {
+    pub stat: DiskStats,
+    pub queue: BlockQueue,
+}
+
+impl BlockDevice {
+    fn new() -> Self {
+        Default::default()
+    }
+}
+
+/// collects the block devices information keyed by device name
+/// # Example
+/// ```
+/// use procsys::sysfs::block;
+///
+/// let devices = block::collect().expect("block devices information");
+///
+/// for (name, device) in &devices {
+///     println!(
+///         "{}: rotational={:?} scheduler={:?}",
+///         name, device.queue.rotational, device.queue.scheduler,
+///     );
+/// }
+///
+/// ```
+pub fn collect() -> CollectResult<HashMap<String, BlockDevice>> {
+    SysFs::default().block()
+}
+
+/// collects the block devices, a device whose stat or queue attributes cannot be read
+/// is reported under its name
+pub fn collect_lenient() -> CollectResult<Partial<HashMap<String, BlockDevice>>> {
+    SysFs::default().block_lenient()
+}
+
+pub(crate) fn collect_from(base_path: &Path) -> CollectResult<HashMap<String, BlockDevice>> {
+    collect_lenient_from(base_path)?.into_result()
+}
+
+pub(crate) fn collect_lenient_from(
+    base_path: &Path,
+) -> CollectResult<Partial<HashMap<String, BlockDevice>>> {
+    let mut devices: Partial<HashMap<String, BlockDevice>> = Partial::default();
+
+    for device_name in utils::list_dir_content(base_path, "", "") {
+        let device_path = base_path.join(&device_name);
+
+        match collect_block_device(&device_name, &device_path) {
+            Ok(device) => {
+                devices.items.insert(device_name, device);
+            }
+            Err(err) => devices.push_error(&device_name, &device_path, err),
+        }
+    }
+
+    Ok(devices)
+}
+
+fn collect_block_device(name: &str, device_path: &Path) -> CollectResult<BlockDevice> {
+    let mut device = BlockDevice::new();
+
+    if let Some(stat) = utils::collect_info_string("stat", device_path)? {
+        let fields: Vec<&str> = stat.split_whitespace().collect();
+        device.stat = diskstats::parse_stat_fields(&fields, &stat)?;
+    }
+
+    device.stat.name = name.to_string();
+
+    if let Some(dev) = utils::collect_info_string("dev", device_path)? {
+        (device.stat.major, device.stat.minor) = parse_device_number(&dev)?;
+    }
+
+    device.queue = collect_block_queue(&device_path.join("queue"))?;
+
+    Ok(device)
+}
+
+/// parses a "major:minor" device number
+pub(crate) fn parse_device_number(dev: &str) -> CollectResult<(u64, u64)> {
+    match dev.trim().split_once(':') {
+        Some((major, minor)) => Ok((
+            utils::convert_str_to_u64(major)?,
+            utils::convert_str_to_u64(minor)?,
+        )),
+        None => Err(MetricError::ParseError(format!(
+            "invalid device number: {}",
+            dev
+        ))),
+    }
+}
+
+fn collect_block_queue(queue_path: &Path) -> CollectResult<BlockQueue> {
+    let mut queue = BlockQueue::new();
+
+    if !filesystem::is_dir(queue_path) {
+        return Ok(queue);
+    }
+
+    queue.rotational = utils::collect_info_u64("rotational", queue_path)?.map(|v| v == 1);
+    queue.logical_block_size = utils::collect_info_u64("logical_block_size", queue_path)?;
+    queue.physical_block_size = utils::collect_info_u64("physical_block_size", queue_path)?;
+    queue.nr_requests = utils::collect_info_u64("nr_requests", queue_path)?;
+
+    // "mq-deadline kyber [bfq] none", the active scheduler is between brackets
+    if let Some(scheduler) = utils::collect_info_string("scheduler", queue_path)? {
+        for name in scheduler.split_whitespace() {
+            match name.strip_prefix('[').and_then(|n| n.strip_suffix(']')) {
+                Some(active) => {
+                    queue.scheduler = Some(active.to_string());
+                    queue.schedulers.push(active.to_string());
+                }
+                None => queue.schedulers.push(name.to_string()),
+            }
+        }
+
+        // devices without a scheduler only report "none"
+        if queue.scheduler.is_none() && queue.schedulers.len() == 1 {
+            queue.scheduler = queue.schedulers.first().cloned();
+        }
+    }
+
+    Ok(queue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_devices() {
+        let devices = collect_from(Path::new("test_data/fixtures/sys/block"))
+            .expect("block devices information");
+
+        assert_eq!(devices.len(), 6);
+
+        let sda = &devices["sda"];
+        assert_eq!((sda.stat.major, sda.stat.minor), (8, 0));
+        assert_eq!(sda.stat.name, "sda");
+        assert_eq!(sda.stat.reads_completed, 25354637);
+        assert_eq!(sda.stat.flushes_completed, None);
+        assert_eq!(
+            sda.queue,
+            BlockQueue {
+                rotational: Some(true),
+                logical_block_size: Some(512),
+                physical_block_size: Some(4096),
+                scheduler: Some("bfq".to_string()),
+                schedulers: vec![
+                    "mq-deadline".to_string(),
+                    "kyber".to_string(),
+                    "bfq".to_string(),
+                    "none".to_string(),
+                ],
+                nr_requests: Some(64),
+            }
+        );
+
+        let nvme = &devices["nvme0n1"];
+        assert_eq!((nvme.stat.major, nvme.stat.minor), (259, 0));
+        assert_eq!(nvme.stat.flushes_completed, Some(12));
+        assert_eq!(nvme.queue.rotational, Some(false));
+        assert_eq!(nvme.queue.scheduler.as_deref(), Some("none"));
+        assert_eq!(nvme.queue.nr_requests, Some(1023));
+
+        let dm = &devices["dm-2"];
+        assert_eq!((dm.stat.major, dm.stat.minor), (253, 2));
+        assert_eq!(dm.queue.scheduler.as_deref(), Some("none"));
+        assert_eq!(dm.queue.schedulers, ["none"]);
+    }
+
+    #[test]
+    fn block_device_number() {
+        assert_eq!(parse_device_number("259:1\n").unwrap(), (259, 1));
+        assert!(parse_device_number("259").is_err());
+        assert!(parse_device_number("a:1").is_err());
+    }
+}
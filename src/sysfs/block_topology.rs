@@ -0,0 +1,447 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    path::Path,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::CollectResult,
+    filesystem,
+    sysfs::{block, SysFs},
+    utils,
+};
+
+/// BlockNode is a block device of the topology, a disk, a partition or a stacked
+/// device (e.g. device-mapper, md, bcache)
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BlockNode {
+    pub name: String,
+    pub major: u64,
+    pub minor: u64,
+
+    /// partition number, None for the whole devices
+    pub partition: Option<u64>,
+
+    /// disk holding the partition
+    pub parent: Option<String>,
+
+    /// partitions of the disk
+    pub partitions: Vec<String>,
+
+    /// devices stacked on top of this device (e.g. the device-mapper of a LVM volume)
+    pub holders: Vec<String>,
+
+    /// devices this device is stacked on (e.g. the physical volumes of a LVM volume)
+    pub slaves: Vec<String>,
+
+    /// device-mapper name (e.g. `vg0-root`)
+    pub dm_name: Option<String>,
+}
+
+impl BlockNode {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// BlockTopology links the block devices to their partitions and to the devices
+/// they are stacked on
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BlockTopology {
+    /// block devices keyed by name
+    pub devices: BTreeMap<String, BlockNode>,
+
+    // sys filesystem the topology was collected from, the files of backing_disks
+    // are looked up under the root it is mounted in
+    #[cfg_attr(feature = "serde", serde(skip))]
+    sysfs: Option<SysFs>,
+}
+
+impl BlockTopology {
+    fn new() -> Self {
+        Default::default()
+    }
+
+    pub(crate) fn with_sysfs(mut self, sysfs: &SysFs) -> Self {
+        self.sysfs = Some(sysfs.clone());
+        self
+    }
+
+    /// returns the block device of the given name (e.g. `sda1`, `dm-0`)
+    pub fn device(&self, name: &str) -> Option<&BlockNode> {
+        self.devices.get(name)
+    }
+
+    /// returns the block device of the given major and minor numbers
+    pub fn device_by_number(&self, major: u64, minor: u64) -> Option<&BlockNode> {
+        self.devices
+            .values()
+            .find(|node| node.major == major && node.minor == minor)
+    }
+
+    /// returns the device-mapper device of the given name (e.g. `vg0-root`)
+    pub fn device_by_dm_name(&self, dm_name: &str) -> Option<&BlockNode> {
+        self.devices
+            .values()
+            .find(|node| node.dm_name.as_deref() == Some(dm_name))
+    }
+
+    /// returns the disks backing a block device, following its slaves down to the
+    /// disks holding the partitions at the bottom of the stack. A device which is
+    /// not stacked on another one (e.g. `zram0`) is its own backing disk.
+    pub fn physical_disks(&self, name: &str) -> Vec<String> {
+        let mut disks = BTreeSet::new();
+        let mut visited = HashSet::new();
+        let mut pending = vec![name.to_string()];
+
+        while let Some(current) = pending.pop() {
+            if !visited.insert(current.clone()) {
+                continue;
+            }
+
+            let Some(node) = self.devices.get(&current) else {
+                continue;
+            };
+
+            match node.slaves.is_empty() {
+                true => {
+                    disks.insert(node.parent.clone().unwrap_or(current));
+                }
+                false => pending.extend(node.slaves.iter().cloned()),
+            }
+        }
+
+        disks.into_iter().collect()
+    }
+
+    /// returns the disks backing a device node (e.g. `/dev/dm-2`, `/dev/mapper/vg0-swap`,
+    /// `/dev/vg0/swap`) or a file (e.g. a swap file), such as the filename of
+    /// [`crate::swaps::Swaps`], the octal escapes of the kernel (e.g. `\040` for a space)
+    /// are decoded. The device of a file, or of a device node which is not named after
+    /// its kernel name, is read from the filesystem of the [`SysFs`] handle, under the
+    /// root the sys filesystem is mounted in (e.g. `/host` for `/host/sys`); files of
+    /// filesystems without a block device (e.g. tmpfs, btrfs) have no backing disk.
+    /// # Example
+    /// ```
+    /// use procsys::{swaps, sysfs::block_topology};
+    ///
+    /// let topology = block_topology::collect().expect("block devices topology");
+    ///
+    /// for swap in swaps::collect().expect("swaps information") {
+    ///     match topology.backing_disks(&swap.filename) {
+    ///         Ok(disks) => println!("{}: {:?}", swap.filename, disks),
+    ///         Err(err) => println!("{}: {}", swap.filename, err),
+    ///     }
+    /// }
+    ///
+    /// ```
+    pub fn backing_disks(&self, filename: &str) -> CollectResult<Vec<String>> {
+        let filename = unescape_octal(filename);
+
+        if let Some(node) = self.device_node(&filename) {
+            return Ok(self.physical_disks(&node.name));
+        }
+
+        let (major, minor) = match &self.sysfs {
+            Some(sysfs) => {
+                let path = mount_root(sysfs.root()).join(filename.trim_start_matches('/'));
+                sysfs.scope(|| file_device_number(&path))?
+            }
+            None => file_device_number(Path::new(&filename))?,
+        };
+
+        match self.device_by_number(major, minor) {
+            Some(node) => Ok(self.physical_disks(&node.name)),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    // returns the device of a /dev path named after the kernel or device-mapper names
+    fn device_node(&self, filename: &str) -> Option<&BlockNode> {
+        let dev_name = filename.strip_prefix("/dev/")?;
+
+        if let Some(dm_name) = dev_name.strip_prefix("mapper/") {
+            return self.device_by_dm_name(dm_name);
+        }
+
+        match dev_name.split_once('/') {
+            // LVM volumes are linked at /dev/<vg>/<lv> to the "<vg>-<lv>" device-mapper,
+            // the dashes of the names being doubled
+            Some((vg, lv)) => self.device_by_dm_name(&format!(
+                "{}-{}",
+                vg.replace('-', "--"),
+                lv.replace('-', "--")
+            )),
+            None => self.device(dev_name),
+        }
+    }
+}
+
+// returns the major and minor numbers of a block device node, or of the device
+// holding the filesystem of a file
+fn file_device_number(path: &Path) -> CollectResult<(u64, u64)> {
+    Ok(split_device_number(filesystem::device_number(path)?))
+}
+
+// returns the root the sys filesystem is mounted in (e.g. / for /sys, /host for /host/sys)
+fn mount_root(sysfs_root: &Path) -> &Path {
+    sysfs_root.parent().unwrap_or(Path::new("/"))
+}
+
+// decodes the \ooo octal escapes of the paths listed by the kernel (e.g. in /proc/swaps
+// a space is listed as \040)
+fn unescape_octal(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escape = bytes.get(i + 1..i + 4).filter(|digits| {
+            bytes[i] == b'\\'
+                && digits[0] <= b'3'
+                && digits.iter().all(|d| (b'0'..=b'7').contains(d))
+        });
+
+        match escape {
+            Some(digits) => {
+                unescaped.push(digits.iter().fold(0, |byte, d| byte * 8 + (d - b'0')));
+                i += 4;
+            }
+            None => {
+                unescaped.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&unescaped).to_string()
+}
+
+// splits a dev_t into its major and minor numbers, like major(3) and minor(3)
+fn split_device_number(device: u64) -> (u64, u64) {
+    let major = ((device >> 32) & 0xffff_f000) | ((device >> 8) & 0x0000_0fff);
+    let minor = ((device >> 12) & 0xffff_ff00) | (device & 0x0000_00ff);
+
+    (major, minor)
+}
+
+/// collects the block devices topology
+/// # Example
+/// ```
+/// use procsys::sysfs::block_topology;
+///
+/// let topology = block_topology::collect().expect("block devices topology");
+///
+/// for (name, device) in &topology.devices {
+///     println!(
+///         "{} {}:{} slaves={:?} disks={:?}",
+///         name,
+///         device.major,
+///         device.minor,
+///         device.slaves,
+///         topology.physical_disks(name),
+///     );
+/// }
+///
+/// ```
+pub fn collect() -> CollectResult<BlockTopology> {
+    SysFs::default().block_topology()
+}
+
+pub(crate) fn collect_from(sysfs_path: &Path) -> CollectResult<BlockTopology> {
+    let mut topology = BlockTopology::new();
+
+    // the disks are listed in /sys/block, their partitions are subdirectories named
+    // after them (e.g. sda/sda1, nvme0n1/nvme0n1p1)
+    let block_path = sysfs_path.join("block");
+
+    for disk_name in utils::list_dir_content(&block_path, "", "") {
+        let disk_path = block_path.join(&disk_name);
+        topology.devices.insert(
+            disk_name.clone(),
+            collect_block_node(&disk_name, &disk_path)?,
+        );
+
+        for partition_name in utils::list_dir_content(&disk_path, &disk_name, "") {
+            let partition_path = disk_path.join(&partition_name);
+            if !filesystem::exists(&partition_path.join("partition")) {
+                continue;
+            }
+
+            let mut partition = collect_block_node(&partition_name, &partition_path)?;
+            partition.parent = Some(disk_name.clone());
+            topology.devices.insert(partition_name, partition);
+        }
+    }
+
+    // /sys/class/block lists the disks and the partitions, it links to their device
+    // directory (e.g. sda1 -> ../../devices/pci0000:00/.../block/sda/sda1)
+    let class_block_path = sysfs_path.join("class/block");
+
+    for name in utils::list_dir_content(&class_block_path, "", "") {
+        if topology.devices.contains_key(&name) {
+            continue;
+        }
+
+        let device_path = class_block_path.join(&name);
+        let mut node = collect_block_node(&name, &device_path)?;
+
+        if node.partition.is_some() {
+            node.parent = filesystem::read_link(&device_path).ok().and_then(|target| {
+                Some(target.parent()?.file_name()?.to_string_lossy().to_string())
+            });
+        }
+
+        topology.devices.insert(name, node);
+    }
+
+    let partitions: Vec<(String, String)> = topology
+        .devices
+        .values()
+        .filter_map(|node| Some((node.parent.clone()?, node.name.clone())))
+        .collect();
+
+    for (parent, partition) in partitions {
+        if let Some(disk) = topology.devices.get_mut(&parent) {
+            disk.partitions.push(partition);
+        }
+    }
+
+    Ok(topology)
+}
+
+fn collect_block_node(name: &str, device_path: &Path) -> CollectResult<BlockNode> {
+    let mut node = BlockNode::new(name);
+
+    if let Some(dev) = utils::collect_info_string("dev", device_path)? {
+        (node.major, node.minor) = block::parse_device_number(&dev)?;
+    }
+
+    node.partition = utils::collect_info_u64("partition", device_path)?;
+    node.holders = utils::list_dir_content(&device_path.join("holders"), "", "");
+    node.slaves = utils::list_dir_content(&device_path.join("slaves"), "", "");
+    node.dm_name = utils::collect_info_string("name", &device_path.join("dm"))?;
+
+    Ok(node)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, sync::Arc};
+
+    use super::*;
+    use crate::{error::ErrorKind, filesystem::MemoryFileSystem};
+
+    fn fixtures_topology() -> BlockTopology {
+        collect_from(Path::new("test_data/fixtures/sys")).expect("block devices topology")
+    }
+
+    #[test]
+    fn block_topology() {
+        let topology = fixtures_topology();
+
+        assert_eq!(topology.devices.len(), 12);
+
+        let sda = topology.device("sda").unwrap();
+        assert_eq!((sda.major, sda.minor), (8, 0));
+        assert_eq!(sda.partitions, ["sda1", "sda2", "sda3"]);
+        assert_eq!(sda.partition, None);
+
+        let sda2 = topology.device_by_number(8, 2).unwrap();
+        assert_eq!(sda2.name, "sda2");
+        assert_eq!(sda2.partition, Some(2));
+        assert_eq!(sda2.parent.as_deref(), Some("sda"));
+        assert_eq!(sda2.holders, ["dm-0"]);
+
+        let crypt = topology.device_by_dm_name("luks-data").unwrap();
+        assert_eq!(crypt.name, "dm-1");
+        assert_eq!(crypt.slaves, ["nvme0n1p1"]);
+        assert_eq!(crypt.holders, ["dm-2"]);
+
+        // partitions only listed in /sys/class/block are linked to their disk
+        let sdb1 = topology.device("sdb1").unwrap();
+        assert_eq!(sdb1.parent.as_deref(), Some("sdb"));
+        assert_eq!(topology.device("sdb").unwrap().partitions, ["sdb1"]);
+    }
+
+    #[test]
+    fn block_topology_physical_disks() {
+        let topology = fixtures_topology();
+
+        assert_eq!(topology.physical_disks("dm-2"), ["nvme0n1"]);
+        assert_eq!(topology.physical_disks("dm-0"), ["sda"]);
+        assert_eq!(topology.physical_disks("sda3"), ["sda"]);
+        assert_eq!(topology.physical_disks("zram0"), ["zram0"]);
+        assert!(topology.physical_disks("unknown").is_empty());
+    }
+
+    #[test]
+    fn block_topology_backing_disks() {
+        let topology = fixtures_topology();
+
+        // the swaps of the fixtures
+        assert_eq!(topology.backing_disks("/dev/dm-2").unwrap(), ["nvme0n1"]);
+        assert_eq!(topology.backing_disks("/dev/zram0").unwrap(), ["zram0"]);
+
+        assert_eq!(
+            topology.backing_disks("/dev/mapper/vg0-root").unwrap(),
+            ["sda"]
+        );
+        assert_eq!(
+            topology.backing_disks("/dev/vg1/swap").unwrap(),
+            ["nvme0n1"]
+        );
+        assert_eq!(topology.backing_disks("/dev/sda1").unwrap(), ["sda"]);
+        assert!(topology.backing_disks("/missing/swapfile").is_err());
+    }
+
+    #[test]
+    fn block_topology_backing_disks_root() {
+        // the files are looked up under the root the sys filesystem is mounted in
+        let root =
+            std::env::temp_dir().join(format!("procsys-block-topology-{}", std::process::id()));
+        fs::create_dir_all(root.join("sys")).unwrap();
+        fs::write(root.join("swap file"), "").unwrap();
+
+        let topology = SysFs::new(root.join("sys")).block_topology().unwrap();
+        let disks = topology.backing_disks("/swap\\040file");
+        fs::remove_dir_all(&root).unwrap();
+        assert!(disks.unwrap().is_empty());
+
+        // the in-memory filesystems have no device numbers
+        let mut memfs = MemoryFileSystem::new();
+        memfs.insert_file("/sys/block/sda/dev", "8:0\n");
+        memfs.insert_file("/swapfile", "");
+
+        let topology = SysFs::with_filesystem("/sys", Arc::new(memfs))
+            .block_topology()
+            .unwrap();
+        assert_eq!(topology.backing_disks("/dev/sda").unwrap(), ["sda"]);
+
+        let err = topology.backing_disks("/swapfile").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert_eq!(err.path(), Some(Path::new("/swapfile")));
+    }
+
+    #[test]
+    fn block_topology_unescape() {
+        assert_eq!(unescape_octal("/swap\\040file"), "/swap file");
+        assert_eq!(unescape_octal("/a\\011b\\134c"), "/a\tb\\c");
+        assert_eq!(unescape_octal("/swap\\04"), "/swap\\04");
+        assert_eq!(unescape_octal("/swap\\948"), "/swap\\948");
+    }
+
+    #[test]
+    fn block_topology_device_number() {
+        assert_eq!(split_device_number(0x0803), (8, 3));
+        assert_eq!(split_device_number(0x10301), (259, 1));
+        assert_eq!(split_device_number(0x1000_0020_0001), (4096, 0x201));
+    }
+}
@@ -1,9 +1,13 @@
 use std::path::{Path, PathBuf};
 
-use serde::Serialize;
-use walkdir::WalkDir;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-use crate::{error::CollectResult, utils};
+use crate::{
+    error::{CollectResult, Partial},
+    sysfs::SysFs,
+    utils,
+};
 
 enum CoolingInfo {
     CoolingType,
@@ -24,7 +28,8 @@ impl CoolingInfo {
 }
 
 /// Cooling contains a cooling device information from files in /sys/class/thermal/cooling_device[0-9]*
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Cooling {
     pub name: String,
     pub cooling_type: String,
@@ -44,65 +49,70 @@ impl Cooling {
 /// use procsys::sysfs::class_cooling;
 ///
 /// let cooling_devices = class_cooling::collect().expect("cooling information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&cooling_devices).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<Vec<Cooling>> {
-    let cooling_class_path = Path::new("/sys/class/thermal/");
-    collect_from(cooling_class_path)
+    SysFs::default().class_cooling()
 }
 
-fn collect_from(base_path: &Path) -> CollectResult<Vec<Cooling>> {
-    let mut cooling_devs = Vec::new();
+/// collects the cooling devices, an unreadable cooling_device directory is reported
+/// under its name
+pub fn collect_lenient() -> CollectResult<Partial<Vec<Cooling>>> {
+    SysFs::default().class_cooling_lenient()
+}
 
-    for cdevice in utils::list_dir_content(base_path, "cooling_device", "thermal") {
-        let mut cooling_device = Cooling::new();
-        cooling_device.name = cdevice.to_string();
+pub(crate) fn collect_from(base_path: &Path) -> CollectResult<Vec<Cooling>> {
+    collect_lenient_from(base_path)?.into_result()
+}
+
+pub(crate) fn collect_lenient_from(base_path: &Path) -> CollectResult<Partial<Vec<Cooling>>> {
+    let mut cooling_devs: Partial<Vec<Cooling>> = Partial::default();
 
+    for cdevice in utils::list_dir_content(base_path, "cooling_device", "thermal") {
         let mut cdev_path = PathBuf::from(base_path);
 
         cdev_path.push(&cdevice);
 
-        for cdev_info in WalkDir::new(&cdev_path).into_iter().filter_map(|e| e.ok()) {
-            let cdev_info_name = cdev_info
-                .file_name()
-                .to_str()
-                .unwrap_or_default()
-                .to_string();
+        match collect_cooling_device(&cdev_path, &cdevice) {
+            Ok(cooling_device) => cooling_devs.items.push(cooling_device),
+            Err(err) => cooling_devs.push_error(&cdevice, &cdev_path, err),
+        }
+    }
 
-            if cdev_info_name == cdevice {
-                continue;
-            }
+    Ok(cooling_devs)
+}
 
-            match CoolingInfo::from(&cdev_info_name) {
-                CoolingInfo::CoolingType => {
-                    if let Some(c) =
-                        utils::collect_info_string(&cdev_info_name, cdev_path.as_path())?
-                    {
-                        cooling_device.cooling_type = c;
-                    }
+fn collect_cooling_device(cdev_path: &Path, cdevice: &str) -> CollectResult<Cooling> {
+    let mut cooling_device = Cooling::new();
+    cooling_device.name = cdevice.to_string();
+
+    for cdev_info_name in utils::list_dir_content(cdev_path, "", "") {
+        match CoolingInfo::from(&cdev_info_name) {
+            CoolingInfo::CoolingType => {
+                if let Some(c) = utils::collect_info_string(&cdev_info_name, cdev_path)? {
+                    cooling_device.cooling_type = c;
                 }
-                CoolingInfo::MaxState => {
-                    if let Some(c) = utils::collect_info_i64(&cdev_info_name, cdev_path.as_path())?
-                    {
-                        cooling_device.max_state = c;
-                    }
+            }
+            CoolingInfo::MaxState => {
+                if let Some(c) = utils::collect_info_i64(&cdev_info_name, cdev_path)? {
+                    cooling_device.max_state = c;
                 }
-                CoolingInfo::CurState => {
-                    if let Some(c) = utils::collect_info_i64(&cdev_info_name, cdev_path.as_path())?
-                    {
-                        cooling_device.cur_state = c;
-                    }
+            }
+            CoolingInfo::CurState => {
+                if let Some(c) = utils::collect_info_i64(&cdev_info_name, cdev_path)? {
+                    cooling_device.cur_state = c;
                 }
-                CoolingInfo::Unknown => {}
             }
+            CoolingInfo::Unknown => {}
         }
-
-        cooling_devs.push(cooling_device);
     }
 
-    Ok(cooling_devs)
+    Ok(cooling_device)
 }
 
 #[cfg(test)]
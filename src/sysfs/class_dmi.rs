@@ -1,8 +1,11 @@
 use crate::{
     error::{CollectResult, MetricError},
+    filesystem,
+    sysfs::SysFs,
     utils,
 };
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 enum DMIType {
@@ -58,7 +61,8 @@ impl DMIType {
 }
 
 /// The DMI contains the content of Desktop Management Interface from files in in /sys/class/dmi/id
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DMI {
     pub bios_date: Option<String>,
     pub bios_release: Option<String>,
@@ -97,19 +101,21 @@ impl DMI {
 /// let dmi_info = class_dmi::collect().expect("dmi information");
 ///
 /// // print all dmi information in json format
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&dmi_info).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<DMI> {
-    let dmi_class_path = Path::new("/sys/class/dmi/id");
-    collect_from(dmi_class_path)
+    SysFs::default().class_dmi()
 }
 
-fn collect_from(base_path: &Path) -> CollectResult<DMI> {
+pub(crate) fn collect_from(base_path: &Path) -> CollectResult<DMI> {
     let mut dmi = DMI::new();
 
-    if !base_path.exists() {
+    if !filesystem::exists(base_path) {
         return Err(MetricError::DmiSupportError);
     }
 
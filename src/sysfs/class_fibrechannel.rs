@@ -1,11 +1,20 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-use crate::{error::CollectResult, utils};
+use crate::{
+    error::{CollectResult, Partial},
+    sysfs::SysFs,
+    utils,
+};
 
 /// FibreChannelHost contains info for a single fibrechannel host
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FibreChannelHost {
     pub speed: Option<String>,
     pub port_state: Option<String>,
@@ -21,7 +30,8 @@ pub struct FibreChannelHost {
     pub statistics: FibreChannelHostCounters,
 }
 
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FibreChannelHostCounters {
     pub dumped_frames: Option<u64>,
     pub error_frames: Option<u64>,
@@ -57,152 +67,168 @@ impl FibreChannelHostCounters {
 /// use procsys::sysfs::class_fibrechannel;
 ///
 /// let fc_hosts = class_fibrechannel::collect().expect("fibrechannel information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&fc_hosts).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<HashMap<String, FibreChannelHost>> {
-    collect_from("/sys/class/fc_host/")
+    SysFs::default().class_fibrechannel()
 }
 
-fn collect_from(filename: &str) -> CollectResult<HashMap<String, FibreChannelHost>> {
-    let mut fc_hosts: HashMap<String, FibreChannelHost> = HashMap::new();
+/// collects the fibre channel hosts, a host whose attributes or statistics cannot be
+/// read is reported under its name
+pub fn collect_lenient() -> CollectResult<Partial<HashMap<String, FibreChannelHost>>> {
+    SysFs::default().class_fibrechannel_lenient()
+}
+
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<HashMap<String, FibreChannelHost>> {
+    collect_lenient_from(filename)?.into_result()
+}
+
+pub(crate) fn collect_lenient_from(
+    filename: &Path,
+) -> CollectResult<Partial<HashMap<String, FibreChannelHost>>> {
+    let mut fc_hosts: Partial<HashMap<String, FibreChannelHost>> = Partial::default();
 
     let fc_hosts_path = PathBuf::from(filename);
     for fc_host_item in utils::list_dir_content(&fc_hosts_path, "", "fc_host") {
         let mut fc_host_item_path = fc_hosts_path.clone();
         fc_host_item_path.push(&fc_host_item);
 
-        let mut fc_host = FibreChannelHost::new();
-        fc_host.speed = utils::collect_info_string("speed", &fc_host_item_path)?;
+        match collect_fc_host(&fc_host_item_path) {
+            Ok(fc_host) => {
+                fc_hosts.items.insert(fc_host_item, fc_host);
+            }
+            Err(err) => fc_hosts.push_error(&fc_host_item, &fc_host_item_path, err),
+        }
+    }
 
-        fc_host.port_state = utils::collect_info_string("port_state", &fc_host_item_path)?;
+    Ok(fc_hosts)
+}
 
-        fc_host.port_type = utils::collect_info_string("port_type", &fc_host_item_path)?;
+fn collect_fc_host(fc_host_path: &Path) -> CollectResult<FibreChannelHost> {
+    let mut fc_host_item_path = fc_host_path.to_path_buf();
 
-        fc_host.symbolic_name = utils::collect_info_string("symbolic_name", &fc_host_item_path)?;
+    let mut fc_host = FibreChannelHost::new();
+    fc_host.speed = utils::collect_info_string("speed", &fc_host_item_path)?;
 
-        fc_host.node_name = utils::collect_info_string("node_name", &fc_host_item_path)?;
+    fc_host.port_state = utils::collect_info_string("port_state", &fc_host_item_path)?;
 
-        fc_host.port_id = utils::collect_info_string("port_id", &fc_host_item_path)?;
+    fc_host.port_type = utils::collect_info_string("port_type", &fc_host_item_path)?;
 
-        fc_host.port_name = utils::collect_info_string("port_name", &fc_host_item_path)?;
+    fc_host.symbolic_name = utils::collect_info_string("symbolic_name", &fc_host_item_path)?;
 
-        fc_host.fabric_name = utils::collect_info_string("fabric_name", &fc_host_item_path)?;
+    fc_host.node_name = utils::collect_info_string("node_name", &fc_host_item_path)?;
 
-        fc_host.dev_loss_tmo = utils::collect_info_string("dev_loss_tmo", &fc_host_item_path)?;
+    fc_host.port_id = utils::collect_info_string("port_id", &fc_host_item_path)?;
 
-        fc_host.supported_classes =
-            utils::collect_info_string("supported_classes", &fc_host_item_path)?;
+    fc_host.port_name = utils::collect_info_string("port_name", &fc_host_item_path)?;
 
-        fc_host.supported_speeds =
-            utils::collect_info_string("supported_speeds", &fc_host_item_path)?;
+    fc_host.fabric_name = utils::collect_info_string("fabric_name", &fc_host_item_path)?;
 
-        fc_host.statistics = FibreChannelHostCounters::new();
-        fc_host_item_path.push("statistics");
+    fc_host.dev_loss_tmo = utils::collect_info_string("dev_loss_tmo", &fc_host_item_path)?;
 
-        let dumped_frames =
-            utils::collect_info_string("dumped_frames", &fc_host_item_path)?.unwrap_or_default();
-        if !dumped_frames.is_empty() {
-            fc_host.statistics.dumped_frames = Some(utils::convert_hex_to_u64(&dumped_frames)?);
-        }
+    fc_host.supported_classes =
+        utils::collect_info_string("supported_classes", &fc_host_item_path)?;
 
-        let error_frames =
-            utils::collect_info_string("error_frames", &fc_host_item_path)?.unwrap_or_default();
-        if !error_frames.is_empty() {
-            fc_host.statistics.error_frames = Some(utils::convert_hex_to_u64(&error_frames)?);
-        }
+    fc_host.supported_speeds = utils::collect_info_string("supported_speeds", &fc_host_item_path)?;
 
-        let invalid_crc_count =
-            utils::collect_info_string("invalid_crc_count", &fc_host_item_path)?
-                .unwrap_or_default();
-        if !invalid_crc_count.is_empty() {
-            fc_host.statistics.invalid_crc_count =
-                Some(utils::convert_hex_to_u64(&invalid_crc_count)?);
-        }
+    fc_host.statistics = FibreChannelHostCounters::new();
+    fc_host_item_path.push("statistics");
 
-        let rx_frames =
-            utils::collect_info_string("rx_frames", &fc_host_item_path)?.unwrap_or_default();
-        if !rx_frames.is_empty() {
-            fc_host.statistics.rx_frames = Some(utils::convert_hex_to_u64(&rx_frames)?);
-        }
+    let dumped_frames =
+        utils::collect_info_string("dumped_frames", &fc_host_item_path)?.unwrap_or_default();
+    if !dumped_frames.is_empty() {
+        fc_host.statistics.dumped_frames = Some(utils::convert_hex_to_u64(&dumped_frames)?);
+    }
 
-        let rx_words =
-            utils::collect_info_string("rx_words", &fc_host_item_path)?.unwrap_or_default();
-        if !rx_words.is_empty() {
-            fc_host.statistics.rx_words = Some(utils::convert_hex_to_u64(&rx_words)?);
-        }
+    let error_frames =
+        utils::collect_info_string("error_frames", &fc_host_item_path)?.unwrap_or_default();
+    if !error_frames.is_empty() {
+        fc_host.statistics.error_frames = Some(utils::convert_hex_to_u64(&error_frames)?);
+    }
 
-        let tx_frames =
-            utils::collect_info_string("tx_frames", &fc_host_item_path)?.unwrap_or_default();
-        if !tx_frames.is_empty() {
-            fc_host.statistics.tx_frames = Some(utils::convert_hex_to_u64(&tx_frames)?);
-        }
+    let invalid_crc_count =
+        utils::collect_info_string("invalid_crc_count", &fc_host_item_path)?.unwrap_or_default();
+    if !invalid_crc_count.is_empty() {
+        fc_host.statistics.invalid_crc_count = Some(utils::convert_hex_to_u64(&invalid_crc_count)?);
+    }
 
-        let tx_words =
-            utils::collect_info_string("tx_words", &fc_host_item_path)?.unwrap_or_default();
-        if !tx_words.is_empty() {
-            fc_host.statistics.tx_words = Some(utils::convert_hex_to_u64(&tx_words)?);
-        }
+    let rx_frames =
+        utils::collect_info_string("rx_frames", &fc_host_item_path)?.unwrap_or_default();
+    if !rx_frames.is_empty() {
+        fc_host.statistics.rx_frames = Some(utils::convert_hex_to_u64(&rx_frames)?);
+    }
 
-        let seconds_since_last_reset =
-            utils::collect_info_string("seconds_since_last_reset", &fc_host_item_path)?
-                .unwrap_or_default();
-        if !seconds_since_last_reset.is_empty() {
-            fc_host.statistics.seconds_since_last_reset =
-                Some(utils::convert_hex_to_u64(&seconds_since_last_reset)?);
-        }
+    let rx_words = utils::collect_info_string("rx_words", &fc_host_item_path)?.unwrap_or_default();
+    if !rx_words.is_empty() {
+        fc_host.statistics.rx_words = Some(utils::convert_hex_to_u64(&rx_words)?);
+    }
 
-        let invalid_tx_word_count =
-            utils::collect_info_string("invalid_tx_word_count", &fc_host_item_path)?
-                .unwrap_or_default();
-        if !invalid_tx_word_count.is_empty() {
-            fc_host.statistics.invalid_tx_word_count =
-                Some(utils::convert_hex_to_u64(&invalid_tx_word_count)?);
-        }
+    let tx_frames =
+        utils::collect_info_string("tx_frames", &fc_host_item_path)?.unwrap_or_default();
+    if !tx_frames.is_empty() {
+        fc_host.statistics.tx_frames = Some(utils::convert_hex_to_u64(&tx_frames)?);
+    }
 
-        let link_failure_count =
-            utils::collect_info_string("link_failure_count", &fc_host_item_path)?
-                .unwrap_or_default();
-        if !link_failure_count.is_empty() {
-            fc_host.statistics.link_failure_count =
-                Some(utils::convert_hex_to_u64(&link_failure_count)?);
-        }
+    let tx_words = utils::collect_info_string("tx_words", &fc_host_item_path)?.unwrap_or_default();
+    if !tx_words.is_empty() {
+        fc_host.statistics.tx_words = Some(utils::convert_hex_to_u64(&tx_words)?);
+    }
 
-        let loss_of_sync_count =
-            utils::collect_info_string("loss_of_sync_count", &fc_host_item_path)?
-                .unwrap_or_default();
-        if !loss_of_sync_count.is_empty() {
-            fc_host.statistics.loss_of_sync_count =
-                Some(utils::convert_hex_to_u64(&loss_of_sync_count)?);
-        }
+    let seconds_since_last_reset =
+        utils::collect_info_string("seconds_since_last_reset", &fc_host_item_path)?
+            .unwrap_or_default();
+    if !seconds_since_last_reset.is_empty() {
+        fc_host.statistics.seconds_since_last_reset =
+            Some(utils::convert_hex_to_u64(&seconds_since_last_reset)?);
+    }
 
-        let loss_of_signal_count =
-            utils::collect_info_string("loss_of_signal_count", &fc_host_item_path)?
-                .unwrap_or_default();
-        if !loss_of_signal_count.is_empty() {
-            fc_host.statistics.loss_of_signal_count =
-                Some(utils::convert_hex_to_u64(&loss_of_signal_count)?);
-        }
+    let invalid_tx_word_count =
+        utils::collect_info_string("invalid_tx_word_count", &fc_host_item_path)?
+            .unwrap_or_default();
+    if !invalid_tx_word_count.is_empty() {
+        fc_host.statistics.invalid_tx_word_count =
+            Some(utils::convert_hex_to_u64(&invalid_tx_word_count)?);
+    }
 
-        let nos_count =
-            utils::collect_info_string("nos_count", &fc_host_item_path)?.unwrap_or_default();
-        if !nos_count.is_empty() {
-            fc_host.statistics.nos_count = Some(utils::convert_hex_to_u64(&nos_count)?);
-        }
+    let link_failure_count =
+        utils::collect_info_string("link_failure_count", &fc_host_item_path)?.unwrap_or_default();
+    if !link_failure_count.is_empty() {
+        fc_host.statistics.link_failure_count =
+            Some(utils::convert_hex_to_u64(&link_failure_count)?);
+    }
 
-        let fcp_packet_aborts =
-            utils::collect_info_string("fcp_packet_aborts", &fc_host_item_path)?
-                .unwrap_or_default();
-        if !fcp_packet_aborts.is_empty() {
-            fc_host.statistics.fcp_packet_aborts =
-                Some(utils::convert_hex_to_u64(&fcp_packet_aborts)?);
-        }
+    let loss_of_sync_count =
+        utils::collect_info_string("loss_of_sync_count", &fc_host_item_path)?.unwrap_or_default();
+    if !loss_of_sync_count.is_empty() {
+        fc_host.statistics.loss_of_sync_count =
+            Some(utils::convert_hex_to_u64(&loss_of_sync_count)?);
+    }
 
-        fc_hosts.insert(fc_host_item, fc_host);
+    let loss_of_signal_count =
+        utils::collect_info_string("loss_of_signal_count", &fc_host_item_path)?.unwrap_or_default();
+    if !loss_of_signal_count.is_empty() {
+        fc_host.statistics.loss_of_signal_count =
+            Some(utils::convert_hex_to_u64(&loss_of_signal_count)?);
     }
 
-    Ok(fc_hosts)
+    let nos_count =
+        utils::collect_info_string("nos_count", &fc_host_item_path)?.unwrap_or_default();
+    if !nos_count.is_empty() {
+        fc_host.statistics.nos_count = Some(utils::convert_hex_to_u64(&nos_count)?);
+    }
+
+    let fcp_packet_aborts =
+        utils::collect_info_string("fcp_packet_aborts", &fc_host_item_path)?.unwrap_or_default();
+    if !fcp_packet_aborts.is_empty() {
+        fc_host.statistics.fcp_packet_aborts = Some(utils::convert_hex_to_u64(&fcp_packet_aborts)?);
+    }
+
+    Ok(fc_host)
 }
 
 #[cfg(test)]
@@ -211,7 +237,7 @@ mod tests {
 
     #[test]
     fn fchost_information() {
-        let fc_hosts = collect_from("test_data/fixtures/sys/class/fc_host/")
+        let fc_hosts = collect_from(Path::new("test_data/fixtures/sys/class/fc_host/"))
             .expect("collecting fibrechannels information");
 
         for (name, fc_host) in fc_hosts {
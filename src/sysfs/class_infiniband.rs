@@ -1,5 +1,10 @@
-use crate::{error::CollectResult, utils};
-use serde::Serialize;
+use crate::{
+    error::{CollectResult, Partial},
+    sysfs::SysFs,
+    utils,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
@@ -7,7 +12,8 @@ use std::{
 
 /// InfiniBandDevice contains info from files in /sys/class/infiniband for a
 /// single InfiniBand device.
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct InfiniBandDevice {
     pub name: String,
     pub board_id: String,         // /sys/class/infiniband/<Name>/board_id
@@ -19,7 +25,8 @@ pub struct InfiniBandDevice {
 /// InfiniBandPort contains info from files in
 /// /sys/class/infiniband/<Name>/ports/<Port>
 /// for a single port of one InfiniBand device.
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct InfiniBandPort {
     pub name: String,
     pub port: u64,
@@ -35,7 +42,8 @@ pub struct InfiniBandPort {
 /// InfiniBandHwCounters contains counter value from files in
 /// /sys/class/infiniband/<Name>/ports/<Port>/hw_counters
 /// for a single port of one InfiniBand device.
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct InfiniBandHwCounters {
     pub duplicate_request: Option<u64>,
     pub implied_nak_seq_err: Option<u64>,
@@ -73,7 +81,8 @@ pub struct InfiniBandHwCounters {
 /// /sys/class/infiniband/<Name>/ports/<Port>/counters or
 /// /sys/class/infiniband/<Name>/ports/<Port>/counters_ext
 /// for a single port of one InfiniBand device.
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct InfiniBandCounters {
     pub legacy_port_multicast_rcv_packets: Option<u64>,
     pub legacy_port_multicast_xmit_packets: Option<u64>,
@@ -138,44 +147,73 @@ impl InfiniBandCounters {
 /// use procsys::sysfs::class_infiniband;
 ///
 /// let infiniband_devices = class_infiniband::collect().expect("infiniband information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&infiniband_devices).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<HashMap<String, InfiniBandDevice>> {
-    collect_from("test_data/fixtures/sys/class/infiniband/")
+    SysFs::default().class_infiniband()
+}
+
+/// collects the infiniband devices, a device whose ports cannot be read is reported
+/// under its name
+pub fn collect_lenient() -> CollectResult<Partial<HashMap<String, InfiniBandDevice>>> {
+    SysFs::default().class_infiniband_lenient()
 }
 
-fn collect_from(filename: &str) -> CollectResult<HashMap<String, InfiniBandDevice>> {
-    let mut infiniband_devices: HashMap<String, InfiniBandDevice> = HashMap::new();
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<HashMap<String, InfiniBandDevice>> {
+    collect_lenient_from(filename)?.into_result()
+}
+
+pub(crate) fn collect_lenient_from(
+    filename: &Path,
+) -> CollectResult<Partial<HashMap<String, InfiniBandDevice>>> {
+    let mut infiniband_devices: Partial<HashMap<String, InfiniBandDevice>> = Partial::default();
 
     let infi_devices_path = PathBuf::from(filename);
     for infi_device in utils::list_dir_content(&infi_devices_path, "", "infiniband") {
         let mut new_device_path = infi_devices_path.clone();
         new_device_path.push(&infi_device);
 
-        let mut new_device = InfiniBandDevice::new();
+        match collect_infiniband_device(&infi_devices_path, &infi_device) {
+            Ok(new_device) => {
+                infiniband_devices.items.insert(infi_device, new_device);
+            }
+            Err(err) => infiniband_devices.push_error(&infi_device, &new_device_path, err),
+        }
+    }
+
+    Ok(infiniband_devices)
+}
 
-        // firmware version
-        new_device.firmware_version =
-            utils::collect_info_string("fw_ver", &new_device_path)?.unwrap_or_default();
+fn collect_infiniband_device(
+    infi_devices_path: &Path,
+    infi_device: &str,
+) -> CollectResult<InfiniBandDevice> {
+    let new_device_path = infi_devices_path.join(infi_device);
 
-        // board id
-        new_device.board_id =
-            utils::collect_info_string("board_id", &new_device_path)?.unwrap_or_default();
+    let mut new_device = InfiniBandDevice::new();
 
-        // hca type
-        new_device.hca_type =
-            utils::collect_info_string("hca_type", &new_device_path)?.unwrap_or_default();
+    // firmware version
+    new_device.firmware_version =
+        utils::collect_info_string("fw_ver", &new_device_path)?.unwrap_or_default();
 
-        new_device.name = infi_device.clone();
+    // board id
+    new_device.board_id =
+        utils::collect_info_string("board_id", &new_device_path)?.unwrap_or_default();
 
-        new_device.ports = collect_infiniband_ports(&infi_device, &infi_devices_path)?;
+    // hca type
+    new_device.hca_type =
+        utils::collect_info_string("hca_type", &new_device_path)?.unwrap_or_default();
 
-        infiniband_devices.insert(infi_device, new_device);
-    }
+    new_device.name = infi_device.to_string();
 
-    Ok(infiniband_devices)
+    new_device.ports = collect_infiniband_ports(infi_device, infi_devices_path)?;
+
+    Ok(new_device)
 }
 
 fn collect_infiniband_ports(
@@ -372,7 +410,7 @@ mod tests {
 
     #[test]
     fn infiniband_device_information() {
-        let infini_devices = collect_from("test_data/fixtures/sys/class/infiniband/")
+        let infini_devices = collect_from(Path::new("test_data/fixtures/sys/class/infiniband/"))
             .expect("collecting infiniband information");
 
         for (name, device) in infini_devices {
@@ -1,11 +1,20 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-use crate::{error::CollectResult, utils};
+use crate::{
+    error::{CollectResult, Partial},
+    sysfs::SysFs,
+    utils,
+};
 
 /// NVMeDevice contains info from files in /sys/class/nvme for a single NVMe device
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NVMeDevice {
     pub serial: String,
     pub model: String,
@@ -25,16 +34,31 @@ impl NVMeDevice {
 /// use procsys::sysfs::class_nvme;
 ///
 /// let nvme_devices = class_nvme::collect().expect("nvme devices information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&nvme_devices).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<HashMap<String, NVMeDevice>> {
-    collect_from("/sys/class/nvme/")
+    SysFs::default().class_nvme()
 }
 
-fn collect_from(filename: &str) -> CollectResult<HashMap<String, NVMeDevice>> {
-    let mut nvme_devices: HashMap<String, NVMeDevice> = HashMap::new();
+/// collects the NVMe controllers, a controller with an unreadable attribute is reported
+/// under its name
+pub fn collect_lenient() -> CollectResult<Partial<HashMap<String, NVMeDevice>>> {
+    SysFs::default().class_nvme_lenient()
+}
+
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<HashMap<String, NVMeDevice>> {
+    collect_lenient_from(filename)?.into_result()
+}
+
+pub(crate) fn collect_lenient_from(
+    filename: &Path,
+) -> CollectResult<Partial<HashMap<String, NVMeDevice>>> {
+    let mut nvme_devices: Partial<HashMap<String, NVMeDevice>> = Partial::default();
 
     let nvme_path = PathBuf::from(filename);
 
@@ -42,33 +66,62 @@ fn collect_from(filename: &str) -> CollectResult<HashMap<String, NVMeDevice>> {
         let mut nvme_item_path = nvme_path.clone();
         nvme_item_path.push(&nvme_item);
 
-        let mut nvme_device = NVMeDevice::new();
+        match collect_nvme_device(&nvme_item_path) {
+            Ok(nvme_device) => {
+                nvme_devices.items.insert(nvme_item, nvme_device);
+            }
+            Err(err) => nvme_devices.push_error(&nvme_item, &nvme_item_path, err),
+        }
+    }
 
-        nvme_device.serial =
-            utils::collect_info_string("serial", &nvme_item_path)?.unwrap_or_default();
+    Ok(nvme_devices)
+}
 
-        nvme_device.model =
-            utils::collect_info_string("model", &nvme_item_path)?.unwrap_or_default();
+fn collect_nvme_device(nvme_item_path: &Path) -> CollectResult<NVMeDevice> {
+    let mut nvme_device = NVMeDevice::new();
 
-        nvme_device.state =
-            utils::collect_info_string("state", &nvme_item_path)?.unwrap_or_default();
+    nvme_device.serial = utils::collect_info_string("serial", nvme_item_path)?.unwrap_or_default();
 
-        nvme_device.firmware_revision =
-            utils::collect_info_string("firmware_rev", &nvme_item_path)?.unwrap_or_default();
+    nvme_device.model = utils::collect_info_string("model", nvme_item_path)?.unwrap_or_default();
 
-        nvme_devices.insert(nvme_item, nvme_device);
-    }
+    nvme_device.state = utils::collect_info_string("state", nvme_item_path)?.unwrap_or_default();
 
-    Ok(nvme_devices)
+    nvme_device.firmware_revision =
+        utils::collect_info_string("firmware_rev", nvme_item_path)?.unwrap_or_default();
+
+    Ok(nvme_device)
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use crate::filesystem::MemoryFileSystem;
+
     use super::*;
 
+    #[test]
+    fn nvme_information_lenient() {
+        let mut memfs = MemoryFileSystem::new();
+        memfs.insert_file("sys/class/nvme/nvme0/serial", "S680HF8N190894I\n");
+        memfs.insert_file("sys/class/nvme/nvme0/state", "live\n");
+        // an unreadable attribute only drops the device it belongs to
+        memfs.insert_dir("sys/class/nvme/nvme1/serial");
+        let sysfs = SysFs::with_filesystem("/sys", Arc::new(memfs));
+
+        let partial = sysfs.class_nvme_lenient().expect("collecting nvme devices");
+        assert_eq!(partial.items.len(), 1);
+        assert_eq!(partial.items["nvme0"].serial, "S680HF8N190894I");
+        assert_eq!(partial.errors.len(), 1);
+        assert_eq!(partial.errors[0].item, "nvme1");
+        assert_eq!(partial.errors[0].path, Path::new("/sys/class/nvme/nvme1"));
+
+        assert!(partial.into_result().is_err());
+    }
+
     #[test]
     fn nvme_information() {
-        let nvme_devices = collect_from("test_data/fixtures/sys/class/nvme/")
+        let nvme_devices = collect_from(Path::new("test_data/fixtures/sys/class/nvme/"))
             .expect("collecting nvme devices information");
 
         for (name, nvme_dev) in nvme_devices {
@@ -1,12 +1,21 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-use crate::{error::CollectResult, utils};
+use crate::{
+    error::{CollectResult, Partial},
+    sysfs::SysFs,
+    utils,
+};
 
 /// PowerSupply contains info from files in /sys/class/power_supply for a
 // single power supply
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PowerSupply {
     pub authentic: Option<i64>,
     pub calibrate: Option<i64>,
@@ -91,16 +100,31 @@ impl PowerSupply {
 /// use procsys::sysfs::class_power_supply;
 ///
 /// let sys_power_supplies = class_power_supply::collect().expect("power supplies information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&sys_power_supplies).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<HashMap<String, PowerSupply>> {
-    collect_from("/sys/class/power_supply/")
+    SysFs::default().class_power_supply()
 }
 
-fn collect_from(filename: &str) -> CollectResult<HashMap<String, PowerSupply>> {
-    let mut power_supplies: HashMap<String, PowerSupply> = HashMap::new();
+/// collects the power supplies, a supply whose attributes cannot be parsed is reported
+/// under its name
+pub fn collect_lenient() -> CollectResult<Partial<HashMap<String, PowerSupply>>> {
+    SysFs::default().class_power_supply_lenient()
+}
+
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<HashMap<String, PowerSupply>> {
+    collect_lenient_from(filename)?.into_result()
+}
+
+pub(crate) fn collect_lenient_from(
+    filename: &Path,
+) -> CollectResult<Partial<HashMap<String, PowerSupply>>> {
+    let mut power_supplies: Partial<HashMap<String, PowerSupply>> = Partial::default();
 
     let proc_ps_path = PathBuf::from(filename);
 
@@ -108,170 +132,170 @@ fn collect_from(filename: &str) -> CollectResult<HashMap<String, PowerSupply>> {
         let mut ps_item_path = proc_ps_path.clone();
         ps_item_path.push(&ps_item);
 
-        let mut power_supply = PowerSupply::new();
-        power_supply.authentic = utils::collect_info_i64("authentic", &ps_item_path)?;
+        match collect_power_supply(&ps_item_path) {
+            Ok(power_supply) => {
+                power_supplies.items.insert(ps_item, power_supply);
+            }
+            Err(err) => power_supplies.push_error(&ps_item, &ps_item_path, err),
+        }
+    }
+
+    Ok(power_supplies)
+}
 
-        power_supply.calibrate = utils::collect_info_i64("calibrate", &ps_item_path)?;
+fn collect_power_supply(ps_item_path: &Path) -> CollectResult<PowerSupply> {
+    let mut power_supply = PowerSupply::new();
+    power_supply.authentic = utils::collect_info_i64("authentic", ps_item_path)?;
 
-        power_supply.capacity = utils::collect_info_i64("capacity", &ps_item_path)?;
+    power_supply.calibrate = utils::collect_info_i64("calibrate", ps_item_path)?;
 
-        power_supply.capacity_alert_max =
-            utils::collect_info_i64("capacity_alert_max", &ps_item_path)?;
+    power_supply.capacity = utils::collect_info_i64("capacity", ps_item_path)?;
 
-        power_supply.capacity_alert_min =
-            utils::collect_info_i64("capacity_alert_min", &ps_item_path)?;
+    power_supply.capacity_alert_max = utils::collect_info_i64("capacity_alert_max", ps_item_path)?;
 
-        power_supply.capacity_level = utils::collect_info_string("capacity_level", &ps_item_path)?;
+    power_supply.capacity_alert_min = utils::collect_info_i64("capacity_alert_min", ps_item_path)?;
 
-        power_supply.charge_avg = utils::collect_info_i64("charge_avg", &ps_item_path)?;
+    power_supply.capacity_level = utils::collect_info_string("capacity_level", ps_item_path)?;
 
-        power_supply.charge_control_limit =
-            utils::collect_info_i64("charge_control_limit", &ps_item_path)?;
+    power_supply.charge_avg = utils::collect_info_i64("charge_avg", ps_item_path)?;
 
-        power_supply.charge_control_limit_max =
-            utils::collect_info_i64("charge_control_limit_max", &ps_item_path)?;
+    power_supply.charge_control_limit =
+        utils::collect_info_i64("charge_control_limit", ps_item_path)?;
 
-        power_supply.charge_counter = utils::collect_info_i64("charge_counter", &ps_item_path)?;
+    power_supply.charge_control_limit_max =
+        utils::collect_info_i64("charge_control_limit_max", ps_item_path)?;
 
-        power_supply.charge_empty = utils::collect_info_i64("charge_empty", &ps_item_path)?;
+    power_supply.charge_counter = utils::collect_info_i64("charge_counter", ps_item_path)?;
 
-        power_supply.charge_empty_design =
-            utils::collect_info_i64("charge_empty_design", &ps_item_path)?;
+    power_supply.charge_empty = utils::collect_info_i64("charge_empty", ps_item_path)?;
 
-        power_supply.charge_start_threshold =
-            utils::collect_info_i64("charge_start_threshold", &ps_item_path)?;
+    power_supply.charge_empty_design =
+        utils::collect_info_i64("charge_empty_design", ps_item_path)?;
 
-        power_supply.charge_stop_threshold =
-            utils::collect_info_i64("charge_stop_threshold", &ps_item_path)?;
+    power_supply.charge_start_threshold =
+        utils::collect_info_i64("charge_start_threshold", ps_item_path)?;
 
-        power_supply.charge_full = utils::collect_info_i64("charge_full", &ps_item_path)?;
+    power_supply.charge_stop_threshold =
+        utils::collect_info_i64("charge_stop_threshold", ps_item_path)?;
 
-        power_supply.charge_full_design =
-            utils::collect_info_i64("charge_full_design", &ps_item_path)?;
+    power_supply.charge_full = utils::collect_info_i64("charge_full", ps_item_path)?;
 
-        power_supply.charge_now = utils::collect_info_i64("charge_now", &ps_item_path)?;
+    power_supply.charge_full_design = utils::collect_info_i64("charge_full_design", ps_item_path)?;
 
-        power_supply.charge_term_current =
-            utils::collect_info_i64("charge_term_current", &ps_item_path)?;
+    power_supply.charge_now = utils::collect_info_i64("charge_now", ps_item_path)?;
 
-        power_supply.charge_type = utils::collect_info_string("charge_type", &ps_item_path)?;
+    power_supply.charge_term_current =
+        utils::collect_info_i64("charge_term_current", ps_item_path)?;
 
-        power_supply.constant_charge_current =
-            utils::collect_info_i64("constant_charge_current", &ps_item_path)?;
+    power_supply.charge_type = utils::collect_info_string("charge_type", ps_item_path)?;
 
-        power_supply.constant_charge_current_max =
-            utils::collect_info_i64("constant_charge_current_max", &ps_item_path)?;
+    power_supply.constant_charge_current =
+        utils::collect_info_i64("constant_charge_current", ps_item_path)?;
 
-        power_supply.constant_charge_voltage =
-            utils::collect_info_i64("constant_charge_voltage", &ps_item_path)?;
+    power_supply.constant_charge_current_max =
+        utils::collect_info_i64("constant_charge_current_max", ps_item_path)?;
 
-        power_supply.constant_charge_voltage_max =
-            utils::collect_info_i64("constant_charge_voltage_max", &ps_item_path)?;
+    power_supply.constant_charge_voltage =
+        utils::collect_info_i64("constant_charge_voltage", ps_item_path)?;
 
-        power_supply.current_avg = utils::collect_info_i64("current_avg", &ps_item_path)?;
+    power_supply.constant_charge_voltage_max =
+        utils::collect_info_i64("constant_charge_voltage_max", ps_item_path)?;
 
-        power_supply.current_boot = utils::collect_info_i64("current_boot", &ps_item_path)?;
+    power_supply.current_avg = utils::collect_info_i64("current_avg", ps_item_path)?;
 
-        power_supply.current_max = utils::collect_info_i64("current_max", &ps_item_path)?;
+    power_supply.current_boot = utils::collect_info_i64("current_boot", ps_item_path)?;
 
-        power_supply.current_now = utils::collect_info_i64("current_now", &ps_item_path)?;
+    power_supply.current_max = utils::collect_info_i64("current_max", ps_item_path)?;
 
-        power_supply.cycle_count = utils::collect_info_i64("cycle_count", &ps_item_path)?;
+    power_supply.current_now = utils::collect_info_i64("current_now", ps_item_path)?;
 
-        power_supply.energy_avg = utils::collect_info_i64("energy_avg", &ps_item_path)?;
+    power_supply.cycle_count = utils::collect_info_i64("cycle_count", ps_item_path)?;
 
-        power_supply.energy_empty = utils::collect_info_i64("energy_empty", &ps_item_path)?;
+    power_supply.energy_avg = utils::collect_info_i64("energy_avg", ps_item_path)?;
 
-        power_supply.energy_empty_design =
-            utils::collect_info_i64("energy_empty_design", &ps_item_path)?;
+    power_supply.energy_empty = utils::collect_info_i64("energy_empty", ps_item_path)?;
 
-        power_supply.energy_full = utils::collect_info_i64("energy_full", &ps_item_path)?;
+    power_supply.energy_empty_design =
+        utils::collect_info_i64("energy_empty_design", ps_item_path)?;
 
-        power_supply.energy_full_design =
-            utils::collect_info_i64("energy_full_design", &ps_item_path)?;
+    power_supply.energy_full = utils::collect_info_i64("energy_full", ps_item_path)?;
 
-        power_supply.energy_now = utils::collect_info_i64("energy_now", &ps_item_path)?;
+    power_supply.energy_full_design = utils::collect_info_i64("energy_full_design", ps_item_path)?;
 
-        power_supply.health = utils::collect_info_string("health", &ps_item_path)?;
+    power_supply.energy_now = utils::collect_info_i64("energy_now", ps_item_path)?;
 
-        power_supply.input_current_limit =
-            utils::collect_info_i64("input_current_limit", &ps_item_path)?;
+    power_supply.health = utils::collect_info_string("health", ps_item_path)?;
 
-        power_supply.manufacturer = utils::collect_info_string("manufacturer", &ps_item_path)?;
+    power_supply.input_current_limit =
+        utils::collect_info_i64("input_current_limit", ps_item_path)?;
 
-        power_supply.model_name = utils::collect_info_string("model_name", &ps_item_path)?;
+    power_supply.manufacturer = utils::collect_info_string("manufacturer", ps_item_path)?;
 
-        power_supply.online = utils::collect_info_i64("online", &ps_item_path)?;
+    power_supply.model_name = utils::collect_info_string("model_name", ps_item_path)?;
 
-        power_supply.power_avg = utils::collect_info_i64("power_avg", &ps_item_path)?;
+    power_supply.online = utils::collect_info_i64("online", ps_item_path)?;
 
-        power_supply.power_now = utils::collect_info_i64("power_now", &ps_item_path)?;
+    power_supply.power_avg = utils::collect_info_i64("power_avg", ps_item_path)?;
 
-        power_supply.precharge_current =
-            utils::collect_info_i64("precharge_current", &ps_item_path)?;
+    power_supply.power_now = utils::collect_info_i64("power_now", ps_item_path)?;
 
-        power_supply.present = utils::collect_info_i64("present", &ps_item_path)?;
+    power_supply.precharge_current = utils::collect_info_i64("precharge_current", ps_item_path)?;
 
-        power_supply.scope = utils::collect_info_string("scope", &ps_item_path)?;
+    power_supply.present = utils::collect_info_i64("present", ps_item_path)?;
 
-        power_supply.serial_number = utils::collect_info_string("serial_number", &ps_item_path)?;
+    power_supply.scope = utils::collect_info_string("scope", ps_item_path)?;
 
-        power_supply.status = utils::collect_info_string("status", &ps_item_path)?;
+    power_supply.serial_number = utils::collect_info_string("serial_number", ps_item_path)?;
 
-        power_supply.technology = utils::collect_info_string("technology", &ps_item_path)?;
+    power_supply.status = utils::collect_info_string("status", ps_item_path)?;
 
-        power_supply.temp = utils::collect_info_i64("temp", &ps_item_path)?;
+    power_supply.technology = utils::collect_info_string("technology", ps_item_path)?;
 
-        power_supply.temp_alert_max = utils::collect_info_i64("temp_alert_max", &ps_item_path)?;
+    power_supply.temp = utils::collect_info_i64("temp", ps_item_path)?;
 
-        power_supply.temp_alert_min = utils::collect_info_i64("temp_alert_min", &ps_item_path)?;
+    power_supply.temp_alert_max = utils::collect_info_i64("temp_alert_max", ps_item_path)?;
 
-        power_supply.temp_ambient = utils::collect_info_i64("temp_ambient", &ps_item_path)?;
+    power_supply.temp_alert_min = utils::collect_info_i64("temp_alert_min", ps_item_path)?;
 
-        power_supply.temp_ambient_max = utils::collect_info_i64("temp_ambient_max", &ps_item_path)?;
+    power_supply.temp_ambient = utils::collect_info_i64("temp_ambient", ps_item_path)?;
 
-        power_supply.temp_ambient_min = utils::collect_info_i64("temp_ambient_min", &ps_item_path)?;
+    power_supply.temp_ambient_max = utils::collect_info_i64("temp_ambient_max", ps_item_path)?;
 
-        power_supply.temp_max = utils::collect_info_i64("temp_max", &ps_item_path)?;
+    power_supply.temp_ambient_min = utils::collect_info_i64("temp_ambient_min", ps_item_path)?;
 
-        power_supply.temp_min = utils::collect_info_i64("temp_min", &ps_item_path)?;
+    power_supply.temp_max = utils::collect_info_i64("temp_max", ps_item_path)?;
 
-        power_supply.time_to_empty_avg =
-            utils::collect_info_i64("time_to_empty_avg", &ps_item_path)?;
+    power_supply.temp_min = utils::collect_info_i64("temp_min", ps_item_path)?;
 
-        power_supply.time_to_empty_now =
-            utils::collect_info_i64("time_to_empty_now", &ps_item_path)?;
+    power_supply.time_to_empty_avg = utils::collect_info_i64("time_to_empty_avg", ps_item_path)?;
 
-        power_supply.time_to_full_avg = utils::collect_info_i64("time_to_full_avg", &ps_item_path)?;
+    power_supply.time_to_empty_now = utils::collect_info_i64("time_to_empty_now", ps_item_path)?;
 
-        power_supply.time_to_full_now = utils::collect_info_i64("time_to_full_now", &ps_item_path)?;
+    power_supply.time_to_full_avg = utils::collect_info_i64("time_to_full_avg", ps_item_path)?;
 
-        power_supply.ps_type = utils::collect_info_string("type", &ps_item_path)?;
+    power_supply.time_to_full_now = utils::collect_info_i64("time_to_full_now", ps_item_path)?;
 
-        power_supply.usb_type = utils::collect_info_string("usb_type", &ps_item_path)?;
+    power_supply.ps_type = utils::collect_info_string("type", ps_item_path)?;
 
-        power_supply.voltage_avg = utils::collect_info_i64("voltage_avg", &ps_item_path)?;
+    power_supply.usb_type = utils::collect_info_string("usb_type", ps_item_path)?;
 
-        power_supply.voltage_boot = utils::collect_info_i64("voltage_boot", &ps_item_path)?;
+    power_supply.voltage_avg = utils::collect_info_i64("voltage_avg", ps_item_path)?;
 
-        power_supply.voltage_max = utils::collect_info_i64("voltage_max", &ps_item_path)?;
+    power_supply.voltage_boot = utils::collect_info_i64("voltage_boot", ps_item_path)?;
 
-        power_supply.voltage_max_design =
-            utils::collect_info_i64("voltage_max_design", &ps_item_path)?;
+    power_supply.voltage_max = utils::collect_info_i64("voltage_max", ps_item_path)?;
 
-        power_supply.voltage_min = utils::collect_info_i64("voltage_min", &ps_item_path)?;
+    power_supply.voltage_max_design = utils::collect_info_i64("voltage_max_design", ps_item_path)?;
 
-        power_supply.voltage_min_design =
-            utils::collect_info_i64("voltage_min_design", &ps_item_path)?;
+    power_supply.voltage_min = utils::collect_info_i64("voltage_min", ps_item_path)?;
 
-        power_supply.voltage_now = utils::collect_info_i64("voltage_now", &ps_item_path)?;
+    power_supply.voltage_min_design = utils::collect_info_i64("voltage_min_design", ps_item_path)?;
 
-        power_supply.voltage_ocv = utils::collect_info_i64("voltage_ocv", &ps_item_path)?;
+    power_supply.voltage_now = utils::collect_info_i64("voltage_now", ps_item_path)?;
 
-        power_supplies.insert(ps_item, power_supply);
-    }
+    power_supply.voltage_ocv = utils::collect_info_i64("voltage_ocv", ps_item_path)?;
 
-    Ok(power_supplies)
+    Ok(power_supply)
 }
 
 #[cfg(test)]
@@ -280,7 +304,7 @@ mod tests {
 
     #[test]
     fn power_supplies() {
-        let power_supplies = collect_from("test_data/fixtures/sys/class/power_supply/")
+        let power_supplies = collect_from(Path::new("test_data/fixtures/sys/class/power_supply/"))
             .expect("collecting system power supplies information");
 
         for (ps_name, ps_info) in &power_supplies {
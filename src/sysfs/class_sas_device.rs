@@ -1,13 +1,18 @@
-use regex::Regex;
-use serde::Serialize;
-use std::{collections::HashMap, path::PathBuf};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 use crate::{
-    error::{CollectResult, MetricError},
+    error::{CollectResult, Partial},
+    sysfs::SysFs,
     utils,
 };
 
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SASDevice {
     /// /sys/class/sas_device/<Name>/sas_address
     pub sas_address: String,
@@ -34,88 +39,99 @@ impl SASDevice {
 /// use procsys::sysfs::class_sas_device;
 ///
 /// let sasdevices = class_sas_device::collect().expect("sas devices information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&sasdevices).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<HashMap<String, SASDevice>> {
-    collect_from("/sys/class/sas_device/")
+    SysFs::default().class_sas_device()
 }
 
-fn collect_from(dirname: &str) -> CollectResult<HashMap<String, SASDevice>> {
-    let re_phy = match Regex::new(r"^phy-[0-9:]+$") {
-        Ok(r) => r,
-        Err(err) => return Err(MetricError::RegexError(err)),
-    };
-
-    let re_port = match Regex::new(r"^port-[0-9:]+$") {
-        Ok(r) => r,
-        Err(err) => return Err(MetricError::RegexError(err)),
-    };
-
-    let re_target = match Regex::new(r"^target[0-9:]+$") {
-        Ok(r) => r,
-        Err(err) => return Err(MetricError::RegexError(err)),
-    };
+/// collects the SAS devices, a device whose attributes or links cannot be read is
+/// reported under its name
+pub fn collect_lenient() -> CollectResult<Partial<HashMap<String, SASDevice>>> {
+    SysFs::default().class_sas_device_lenient()
+}
 
-    let re_subdevice = match Regex::new(r"[0-9]+:.*") {
-        Ok(r) => r,
-        Err(err) => return Err(MetricError::RegexError(err)),
-    };
+pub(crate) fn collect_from(dirname: &Path) -> CollectResult<HashMap<String, SASDevice>> {
+    collect_lenient_from(dirname)?.into_result()
+}
 
-    let mut sasdevices: HashMap<String, SASDevice> = HashMap::new();
+pub(crate) fn collect_lenient_from(
+    dirname: &Path,
+) -> CollectResult<Partial<HashMap<String, SASDevice>>> {
+    let mut sasdevices: Partial<HashMap<String, SASDevice>> = Partial::default();
 
     let sas_devices_path = PathBuf::from(dirname);
     for sdevice in utils::list_dir_content(&sas_devices_path, "", "sas_device") {
         let mut sasdevice_path = sas_devices_path.clone();
         sasdevice_path.push(&sdevice);
 
-        let mut sasdevice = SASDevice::new();
-        sasdevice.sas_address =
-            utils::collect_info_string("sas_address", &sasdevice_path)?.unwrap_or_default();
-
-        let mut sas_device_path_device = sasdevice_path.clone();
-        sas_device_path_device.push("device");
-
-        for item in utils::list_dir_content(&sas_device_path_device, "", "") {
-            if re_phy.is_match(&item) {
-                sasdevice.sas_phys.push(item);
-            } else if re_port.is_match(&item) {
-                sasdevice.sas_ports.push(item);
-            } else if re_target.is_match(&item) {
-                let item_target = item.clone();
-                let mut item_target_path = sas_device_path_device.clone();
-                item_target_path.push(&item_target);
-
-                for sub_target in utils::list_dir_content(&item_target_path, "", &item_target) {
-                    if !re_subdevice.is_match(&sub_target) {
-                        continue;
-                    }
-
-                    let mut sub_target_path = item_target_path.clone();
-                    sub_target_path.push(sub_target);
-                    sub_target_path.push("block");
-
-                    for block in utils::list_dir_content(&sub_target_path, "", "block") {
-                        sasdevice.block_devices.push(block);
-                    }
-                }
+        match collect_sas_device(&sasdevice_path) {
+            Ok(sasdevice) => {
+                sasdevices.items.insert(sdevice, sasdevice);
             }
+            Err(err) => sasdevices.push_error(&sdevice, &sasdevice_path, err),
         }
-
-        sasdevices.insert(sdevice, sasdevice);
     }
 
     Ok(sasdevices)
 }
 
+fn collect_sas_device(sasdevice_path: &Path) -> CollectResult<SASDevice> {
+    let mut sasdevice = SASDevice::new();
+    sasdevice.sas_address =
+        utils::collect_info_string("sas_address", sasdevice_path)?.unwrap_or_default();
+
+    let mut sas_device_path_device = sasdevice_path.to_path_buf();
+    sas_device_path_device.push("device");
+
+    for item in utils::list_dir_content(&sas_device_path_device, "", "") {
+        if utils::is_device_name(&item, "phy-") {
+            sasdevice.sas_phys.push(item);
+        } else if utils::is_device_name(&item, "port-") {
+            sasdevice.sas_ports.push(item);
+        } else if utils::is_device_name(&item, "target") {
+            let item_target = item.clone();
+            let mut item_target_path = sas_device_path_device.clone();
+            item_target_path.push(&item_target);
+
+            for sub_target in utils::list_dir_content(&item_target_path, "", &item_target) {
+                if !is_scsi_device(&sub_target) {
+                    continue;
+                }
+
+                let mut sub_target_path = item_target_path.clone();
+                sub_target_path.push(sub_target);
+                sub_target_path.push("block");
+
+                for block in utils::list_dir_content(&sub_target_path, "", "block") {
+                    sasdevice.block_devices.push(block);
+                }
+            }
+        }
+    }
+
+    Ok(sasdevice)
+}
+
+// scsi devices are named after their host:channel:target:lun address
+fn is_scsi_device(name: &str) -> bool {
+    name.as_bytes()
+        .windows(2)
+        .any(|pair| pair[0].is_ascii_digit() && pair[1] == b':')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn sas_devices_information() {
-        let sasdevices = collect_from("test_data/fixtures/sys/class/sas_device/")
+        let sasdevices = collect_from(Path::new("test_data/fixtures/sys/class/sas_device/"))
             .expect("sas device information");
 
         for (sasdev_name, sasdev_info) in sasdevices {
@@ -1,13 +1,14 @@
-use regex::Regex;
-use serde::Serialize;
-use std::{collections::HashMap, path::PathBuf};
-
-use crate::{
-    error::{CollectResult, MetricError},
-    utils,
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
 };
 
-#[derive(Debug, Serialize, Clone, Default)]
+use crate::{error::CollectResult, sysfs::SysFs, utils};
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SASHost {
     /// /sys/class/sas_host/<Name>/device/phy-*
     pub sas_phys: Vec<String>,
@@ -28,28 +29,21 @@ impl SASHost {
 /// use procsys::sysfs::class_sas_host;
 ///
 /// let sashosts = class_sas_host::collect().expect("sas hosts information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&sashosts).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<HashMap<String, SASHost>> {
-    collect_from("/sys/class/sas_host/")
+    SysFs::default().class_sas_host()
 }
 
-fn collect_from(dirname: &str) -> CollectResult<HashMap<String, SASHost>> {
+pub(crate) fn collect_from(dirname: &Path) -> CollectResult<HashMap<String, SASHost>> {
     let mut sashosts: HashMap<String, SASHost> = HashMap::new();
     let sas_host_path = PathBuf::from(dirname);
 
-    let re_phy = match Regex::new(r"^phy-[0-9:]+$") {
-        Ok(r) => r,
-        Err(err) => return Err(MetricError::RegexError(err)),
-    };
-
-    let re_port = match Regex::new(r"^port-[0-9:]+$") {
-        Ok(r) => r,
-        Err(err) => return Err(MetricError::RegexError(err)),
-    };
-
     for item in utils::list_dir_content(&sas_host_path, "", "sas_host") {
         let mut sas_host = SASHost::new();
         let mut item_host_path = sas_host_path.clone();
@@ -57,9 +51,9 @@ fn collect_from(dirname: &str) -> CollectResult<HashMap<String, SASHost>> {
         item_host_path.push("device");
 
         for device_item in utils::list_dir_content(&item_host_path, "", &item) {
-            if re_phy.is_match(&device_item) {
+            if utils::is_device_name(&device_item, "phy-") {
                 sas_host.sas_phys.push(device_item);
-            } else if re_port.is_match(&device_item) {
+            } else if utils::is_device_name(&device_item, "port-") {
                 sas_host.sas_ports.push(device_item);
             }
         }
@@ -76,8 +70,8 @@ mod tests {
 
     #[test]
     fn sas_host_information() {
-        let sashosts =
-            collect_from("test_data/fixtures/sys/class/sas_host/").expect("sas hosts information");
+        let sashosts = collect_from(Path::new("test_data/fixtures/sys/class/sas_host/"))
+            .expect("sas hosts information");
 
         assert_eq!(sashosts.len(), 1);
 
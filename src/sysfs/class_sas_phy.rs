@@ -1,17 +1,19 @@
-use regex::Regex;
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
-    fs,
     path::{Path, PathBuf},
 };
 
 use crate::{
-    error::{CollectResult, MetricError},
+    error::{CollectResult, MetricError, Partial},
+    filesystem,
+    sysfs::SysFs,
     utils,
 };
 
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SASPhy {
     /// /sys/class/sas_phy/<Name>/sas_address
     pub sas_address: String,
@@ -71,144 +73,176 @@ impl SASPhy {
 /// use procsys::sysfs::class_sas_phy;
 ///
 /// let sas_phys = class_sas_phy::collect().expect("sas phys information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&sas_phys).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<HashMap<String, SASPhy>> {
-    collect_from("/sys/class/sas_phy/")
+    SysFs::default().class_sas_phy()
 }
 
-fn collect_from(dirname: &str) -> CollectResult<HashMap<String, SASPhy>> {
-    let mut sas_phys: HashMap<String, SASPhy> = HashMap::new();
-    let sas_phy_path = PathBuf::from(dirname);
+/// collects the SAS phys, a phy which cannot be read (e.g. a dangling port link or an
+/// unexpected counter value) is reported with its name and path instead of failing
+/// the whole collection
+/// # Example
+/// ```
+/// use procsys::sysfs::class_sas_phy;
+///
+/// let partial = class_sas_phy::collect_lenient().expect("sas phys information");
+///
+/// for (name, phy) in &partial.items {
+///     println!("{}: {}", name, phy.sas_address);
+/// }
+///
+/// for err in &partial.errors {
+///     println!("skipped {} ({}): {}", err.item, err.path.display(), err.error);
+/// }
+///
+/// ```
+pub fn collect_lenient() -> CollectResult<Partial<HashMap<String, SASPhy>>> {
+    SysFs::default().class_sas_phy_lenient()
+}
 
-    let re_port = match Regex::new(r"^port-[0-9:]+$") {
-        Ok(r) => r,
-        Err(err) => return Err(MetricError::RegexError(err)),
-    };
+pub(crate) fn collect_from(dirname: &Path) -> CollectResult<HashMap<String, SASPhy>> {
+    collect_lenient_from(dirname)?.into_result()
+}
+
+pub(crate) fn collect_lenient_from(
+    dirname: &Path,
+) -> CollectResult<Partial<HashMap<String, SASPhy>>> {
+    let mut sas_phys: Partial<HashMap<String, SASPhy>> = Partial::default();
+    let sas_phy_path = PathBuf::from(dirname);
 
     for sas_phy_item in utils::list_dir_content(&sas_phy_path, "", "sas_phy") {
-        let mut sas_phy = SASPhy::new();
         let mut sas_phy_item_path = sas_phy_path.clone();
         sas_phy_item_path.push(&sas_phy_item);
 
-        for phy_info in utils::list_dir_content(&sas_phy_item_path, "", &sas_phy_item) {
-            match phy_info.as_str() {
-                "sas_address" => {
-                    sas_phy.sas_address =
-                        utils::collect_info_string("sas_address", &sas_phy_item_path)?
+        match collect_sas_phy(&sas_phy_item_path, &sas_phy_item) {
+            Ok(sas_phy) => {
+                sas_phys.items.insert(sas_phy_item, sas_phy);
+            }
+            Err(err) => sas_phys.push_error(&sas_phy_item, &sas_phy_item_path, err),
+        }
+    }
+
+    Ok(sas_phys)
+}
+
+fn collect_sas_phy(sas_phy_item_path: &Path, sas_phy_item: &str) -> CollectResult<SASPhy> {
+    let mut sas_phy = SASPhy::new();
+
+    for phy_info in utils::list_dir_content(sas_phy_item_path, "", sas_phy_item) {
+        match phy_info.as_str() {
+            "sas_address" => {
+                sas_phy.sas_address = utils::collect_info_string("sas_address", sas_phy_item_path)?
+                    .unwrap_or_default();
+            }
+            "device" => {
+                let mut sas_phy_ports_path = sas_phy_item_path.to_path_buf();
+                sas_phy_ports_path.push("device");
+                sas_phy_ports_path.push("port");
+                sas_phy.sas_port = match filesystem::read_link(&sas_phy_ports_path) {
+                    Ok(p) => {
+                        let port = p
+                            .file_name()
+                            .unwrap_or_default()
+                            .to_str()
                             .unwrap_or_default();
-                }
-                "device" => {
-                    let mut sas_phy_ports_path = sas_phy_item_path.clone();
-                    sas_phy_ports_path.push("device");
-                    sas_phy_ports_path.push("port");
-                    sas_phy.sas_port = match fs::read_link(&sas_phy_ports_path) {
-                        Ok(p) => {
-                            let port = p
-                                .file_name()
-                                .unwrap_or_default()
-                                .to_str()
-                                .unwrap_or_default();
-
-                            if re_port.is_match(port) {
-                                port.to_string()
-                            } else {
-                                "".to_string()
-                            }
+
+                        if utils::is_device_name(port, "port-") {
+                            port.to_string()
+                        } else {
+                            "".to_string()
                         }
-                        Err(err) => return Err(MetricError::IOError(sas_phy_ports_path, err)),
-                    };
-                }
-                "device_type" => {
-                    sas_phy.device_type =
-                        utils::collect_info_string("device_type", &sas_phy_item_path)?
-                            .unwrap_or_default();
-                }
-                "initiator_port_protocols" => {
-                    let port_protocols =
-                        utils::collect_info_string("initiator_port_protocols", &sas_phy_item_path)?
-                            .unwrap_or_default();
-                    let initiator_port_protocols: Vec<String> = port_protocols
-                        .trim()
-                        .replace(", ", ",")
-                        .split(',')
-                        .filter(|s| !s.is_empty())
-                        .map(str::to_string)
-                        .collect();
-
-                    sas_phy.initiator_port_protocols = initiator_port_protocols
-                }
-                "invalid_dword_count" => {
-                    sas_phy.invalid_dword_count =
-                        utils::collect_info_i64("invalid_dword_count", &sas_phy_item_path)?
-                            .unwrap_or_default();
-                }
-                "loss_of_dword_sync_count" => {
-                    sas_phy.loss_of_dword_sync_count =
-                        utils::collect_info_i64("loss_of_dword_sync_count", &sas_phy_item_path)?
-                            .unwrap_or_default();
-                }
-                "maximum_linkrate" => {
-                    let linkrate = get_linkrate("maximum_linkrate", &sas_phy_item_path)?;
-                    sas_phy.maximum_linkrate = linkrate;
-                }
-                "maximum_linkrate_hw" => {
-                    let linkrate = get_linkrate("maximum_linkrate_hw", &sas_phy_item_path)?;
-                    sas_phy.maximum_linkrate_hw = linkrate;
-                }
-                "minimum_linkrate" => {
-                    let linkrate = get_linkrate("minimum_linkrate", &sas_phy_item_path)?;
-                    sas_phy.minimum_linkrate = linkrate;
-                }
-                "minimum_linkrate_hw" => {
-                    let linkrate = get_linkrate("minimum_linkrate_hw", &sas_phy_item_path)?;
-                    sas_phy.minimum_linkrate_hw = linkrate;
-                }
-                "negotiated_linkrate" => {
-                    let linkrate = get_linkrate("negotiated_linkrate", &sas_phy_item_path)?;
-                    sas_phy.negotiated_linkrate = linkrate;
-                }
-                "phy_identifier" => {
-                    sas_phy.phy_identifier =
-                        utils::collect_info_string("phy_identifier", &sas_phy_item_path)?
-                            .unwrap_or_default();
-                }
-                "phy_reset_problem_count" => {
-                    sas_phy.phy_reset_problem_count =
-                        utils::collect_info_i64("phy_reset_problem_count", &sas_phy_item_path)?
-                            .unwrap_or_default();
-                }
-                "running_disparity_error_count" => {
-                    sas_phy.running_disparity_error_count = utils::collect_info_i64(
-                        "running_disparity_error_count",
-                        &sas_phy_item_path,
-                    )?
+                    }
+                    Err(err) => return Err(MetricError::IOError(sas_phy_ports_path, err)),
+                };
+            }
+            "device_type" => {
+                sas_phy.device_type = utils::collect_info_string("device_type", sas_phy_item_path)?
                     .unwrap_or_default();
-                }
-                "target_port_protocols" => {
-                    let port_protocols =
-                        utils::collect_info_string("target_port_protocols", &sas_phy_item_path)?
-                            .unwrap_or_default();
-                    let target_port_protocols: Vec<String> = port_protocols
-                        .trim()
-                        .replace(", ", ",")
-                        .split(',')
-                        .filter(|s| !s.is_empty())
-                        .map(str::to_string)
-                        .collect();
-
-                    sas_phy.target_port_protocols = target_port_protocols
-                }
-                _ => {}
             }
+            "initiator_port_protocols" => {
+                let port_protocols =
+                    utils::collect_info_string("initiator_port_protocols", sas_phy_item_path)?
+                        .unwrap_or_default();
+                let initiator_port_protocols: Vec<String> = port_protocols
+                    .trim()
+                    .replace(", ", ",")
+                    .split(',')
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+                    .collect();
+
+                sas_phy.initiator_port_protocols = initiator_port_protocols
+            }
+            "invalid_dword_count" => {
+                sas_phy.invalid_dword_count =
+                    utils::collect_info_i64("invalid_dword_count", sas_phy_item_path)?
+                        .unwrap_or_default();
+            }
+            "loss_of_dword_sync_count" => {
+                sas_phy.loss_of_dword_sync_count =
+                    utils::collect_info_i64("loss_of_dword_sync_count", sas_phy_item_path)?
+                        .unwrap_or_default();
+            }
+            "maximum_linkrate" => {
+                let linkrate = get_linkrate("maximum_linkrate", sas_phy_item_path)?;
+                sas_phy.maximum_linkrate = linkrate;
+            }
+            "maximum_linkrate_hw" => {
+                let linkrate = get_linkrate("maximum_linkrate_hw", sas_phy_item_path)?;
+                sas_phy.maximum_linkrate_hw = linkrate;
+            }
+            "minimum_linkrate" => {
+                let linkrate = get_linkrate("minimum_linkrate", sas_phy_item_path)?;
+                sas_phy.minimum_linkrate = linkrate;
+            }
+            "minimum_linkrate_hw" => {
+                let linkrate = get_linkrate("minimum_linkrate_hw", sas_phy_item_path)?;
+                sas_phy.minimum_linkrate_hw = linkrate;
+            }
+            "negotiated_linkrate" => {
+                let linkrate = get_linkrate("negotiated_linkrate", sas_phy_item_path)?;
+                sas_phy.negotiated_linkrate = linkrate;
+            }
+            "phy_identifier" => {
+                sas_phy.phy_identifier =
+                    utils::collect_info_string("phy_identifier", sas_phy_item_path)?
+                        .unwrap_or_default();
+            }
+            "phy_reset_problem_count" => {
+                sas_phy.phy_reset_problem_count =
+                    utils::collect_info_i64("phy_reset_problem_count", sas_phy_item_path)?
+                        .unwrap_or_default();
+            }
+            "running_disparity_error_count" => {
+                sas_phy.running_disparity_error_count =
+                    utils::collect_info_i64("running_disparity_error_count", sas_phy_item_path)?
+                        .unwrap_or_default();
+            }
+            "target_port_protocols" => {
+                let port_protocols =
+                    utils::collect_info_string("target_port_protocols", sas_phy_item_path)?
+                        .unwrap_or_default();
+                let target_port_protocols: Vec<String> = port_protocols
+                    .trim()
+                    .replace(", ", ",")
+                    .split(',')
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+                    .collect();
+
+                sas_phy.target_port_protocols = target_port_protocols
+            }
+            _ => {}
         }
-
-        sas_phys.insert(sas_phy_item, sas_phy);
     }
 
-    Ok(sas_phys)
+    Ok(sas_phy)
 }
 
 fn get_linkrate(link: &str, phy_path: &Path) -> CollectResult<f64> {
@@ -240,12 +274,56 @@ fn get_linkrate(link: &str, phy_path: &Path) -> CollectResult<f64> {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use crate::{error::ErrorKind, filesystem::MemoryFileSystem};
+
     use super::*;
 
+    #[test]
+    fn sas_phy_information_lenient() {
+        let mut memfs = MemoryFileSystem::new();
+        memfs.insert_file(
+            "sys/class/sas_phy/phy-11:0:2/sas_address",
+            "0x5000ccab0200947e\n",
+        );
+        memfs.insert_file("sys/class/sas_phy/phy-11:0:2/invalid_dword_count", "18\n");
+        memfs.insert_file(
+            "sys/class/sas_phy/phy-11:0:4/sas_address",
+            "0x5000ccab0200947e\n",
+        );
+        // a broken counter only drops the phy it belongs to
+        memfs.insert_file(
+            "sys/class/sas_phy/phy-11:0:6/sas_address",
+            "0x5000ccab0200947e\n",
+        );
+        memfs.insert_file("sys/class/sas_phy/phy-11:0:6/invalid_dword_count", "n/a\n");
+        let sysfs = SysFs::with_filesystem("/sys", Arc::new(memfs));
+
+        let partial = sysfs.class_sas_phy_lenient().expect("sas phys information");
+        assert_eq!(partial.items.len(), 2);
+        assert_eq!(partial.items["phy-11:0:2"].invalid_dword_count, 18);
+        assert!(partial.items.contains_key("phy-11:0:4"));
+
+        assert_eq!(partial.errors.len(), 1);
+        assert_eq!(partial.errors[0].item, "phy-11:0:6");
+        assert_eq!(
+            partial.errors[0].path,
+            Path::new("/sys/class/sas_phy/phy-11:0:6")
+        );
+        assert_eq!(partial.errors[0].error.kind(), ErrorKind::Parse);
+        assert_eq!(
+            partial.errors[0].error.path(),
+            Some(Path::new("/sys/class/sas_phy/phy-11:0:6")),
+        );
+
+        assert!(sysfs.class_sas_phy().is_err());
+    }
+
     #[test]
     fn sas_phy_information() {
-        let sas_phys =
-            collect_from("test_data/fixtures/sys/class/sas_phy/").expect("sas phys information");
+        let sas_phys = collect_from(Path::new("test_data/fixtures/sys/class/sas_phy/"))
+            .expect("sas phys information");
 
         for (phy_name, phy_info) in sas_phys {
             match phy_name.as_str() {
@@ -1,13 +1,14 @@
-use regex::Regex;
-use serde::Serialize;
-use std::{collections::HashMap, path::PathBuf};
-
-use crate::{
-    error::{CollectResult, MetricError},
-    utils,
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
 };
 
-#[derive(Debug, Serialize, Clone, Default)]
+use crate::{error::CollectResult, sysfs::SysFs, utils};
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SASPort {
     /// /sys/class/sas_device/<Name>/device/phy-*
     pub sas_phys: Vec<String>,
@@ -31,33 +32,21 @@ impl SASPort {
 /// use procsys::sysfs::class_sas_port;
 ///
 /// let sasports = class_sas_port::collect().expect("sas ports information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&sasports).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<HashMap<String, SASPort>> {
-    collect_from("/sys/class/sas_port/")
+    SysFs::default().class_sas_port()
 }
 
-fn collect_from(dirname: &str) -> CollectResult<HashMap<String, SASPort>> {
+pub(crate) fn collect_from(dirname: &Path) -> CollectResult<HashMap<String, SASPort>> {
     let mut sasports: HashMap<String, SASPort> = HashMap::new();
     let sas_port_path = PathBuf::from(dirname);
 
-    let re_phy = match Regex::new(r"^phy-[0-9:]+$") {
-        Ok(r) => r,
-        Err(err) => return Err(MetricError::RegexError(err)),
-    };
-
-    let re_expanders = match Regex::new(r"expander-[0-9:]+$") {
-        Ok(r) => r,
-        Err(err) => return Err(MetricError::RegexError(err)),
-    };
-
-    let re_end_devices = match Regex::new(r"^end_device-[0-9:]+$") {
-        Ok(r) => r,
-        Err(err) => return Err(MetricError::RegexError(err)),
-    };
-
     for sas_port_item in utils::list_dir_content(&sas_port_path, "", "sas_port") {
         let mut sas_port = SASPort::new();
         let mut sas_port_item_path = sas_port_path.clone();
@@ -65,11 +54,11 @@ fn collect_from(dirname: &str) -> CollectResult<HashMap<String, SASPort>> {
         sas_port_item_path.push("device");
 
         for sas_port_device_item in utils::list_dir_content(&sas_port_item_path, "", "device") {
-            if re_phy.is_match(&sas_port_device_item) {
+            if utils::is_device_name(&sas_port_device_item, "phy-") {
                 sas_port.sas_phys.push(sas_port_device_item)
-            } else if re_expanders.is_match(&sas_port_device_item) {
+            } else if is_expander(&sas_port_device_item) {
                 sas_port.expanders.push(sas_port_device_item)
-            } else if re_end_devices.is_match(&sas_port_device_item) {
+            } else if utils::is_device_name(&sas_port_device_item, "end_device-") {
                 sas_port.end_devices.push(sas_port_device_item)
             }
         }
@@ -80,14 +69,22 @@ fn collect_from(dirname: &str) -> CollectResult<HashMap<String, SASPort>> {
     Ok(sasports)
 }
 
+// returns true if the name ends with an expander name (e.g. `expander-11:0`)
+fn is_expander(name: &str) -> bool {
+    match name.rfind("expander-") {
+        Some(index) => utils::is_device_name(&name[index..], "expander-"),
+        None => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn sas_port_information() {
-        let sasports =
-            collect_from("test_data/fixtures/sys/class/sas_port/").expect("sas ports information");
+        let sasports = collect_from(Path::new("test_data/fixtures/sys/class/sas_port/"))
+            .expect("sas ports information");
 
         for (sasport_name, sasport_info) in sasports {
             match sasport_name.as_str() {
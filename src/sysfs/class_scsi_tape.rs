@@ -1,11 +1,20 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-use crate::{error::CollectResult, utils};
+use crate::{
+    error::{CollectResult, Partial},
+    sysfs::SysFs,
+    utils,
+};
 
 /// ScsiTapeCounters contains statistics info for a single scsi tape
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ScsiTapeCounters {
     pub write_ns: u64,
     pub read_byte_cnt: u64,
@@ -31,16 +40,31 @@ impl ScsiTapeCounters {
 /// use procsys::sysfs::class_scsi_tape;
 ///
 /// let sys_scsi_tapes = class_scsi_tape::collect().expect("scsi tape statistics information");
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&sys_scsi_tapes).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<HashMap<String, ScsiTapeCounters>> {
-    collect_from("/sys/class/scsi_tape/")
+    SysFs::default().class_scsi_tape()
 }
 
-fn collect_from(filename: &str) -> CollectResult<HashMap<String, ScsiTapeCounters>> {
-    let mut scsi_tapes: HashMap<String, ScsiTapeCounters> = HashMap::new();
+/// collects the SCSI tape statistics, a tape whose stats cannot be read is reported
+/// under its name
+pub fn collect_lenient() -> CollectResult<Partial<HashMap<String, ScsiTapeCounters>>> {
+    SysFs::default().class_scsi_tape_lenient()
+}
+
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<HashMap<String, ScsiTapeCounters>> {
+    collect_lenient_from(filename)?.into_result()
+}
+
+pub(crate) fn collect_lenient_from(
+    filename: &Path,
+) -> CollectResult<Partial<HashMap<String, ScsiTapeCounters>>> {
+    let mut scsi_tapes: Partial<HashMap<String, ScsiTapeCounters>> = Partial::default();
 
     let scsi_tape_path = PathBuf::from(filename);
 
@@ -49,42 +73,51 @@ fn collect_from(filename: &str) -> CollectResult<HashMap<String, ScsiTapeCounter
         scsitape_item_path.push(&scsitape_item);
         scsitape_item_path.push("stats");
 
-        let mut scsitape_counters = ScsiTapeCounters::new();
+        match collect_scsi_tape_counters(&scsitape_item_path) {
+            Ok(scsitape_counters) => {
+                scsi_tapes.items.insert(scsitape_item, scsitape_counters);
+            }
+            Err(err) => scsi_tapes.push_error(&scsitape_item, &scsitape_item_path, err),
+        }
+    }
+
+    Ok(scsi_tapes)
+}
 
-        scsitape_counters.write_ns =
-            utils::collect_info_u64("write_ns", &scsitape_item_path)?.unwrap_or_default();
+fn collect_scsi_tape_counters(scsitape_item_path: &Path) -> CollectResult<ScsiTapeCounters> {
+    let mut scsitape_counters = ScsiTapeCounters::new();
 
-        scsitape_counters.read_byte_cnt =
-            utils::collect_info_u64("read_byte_cnt", &scsitape_item_path)?.unwrap_or_default();
+    scsitape_counters.write_ns =
+        utils::collect_info_u64("write_ns", scsitape_item_path)?.unwrap_or_default();
 
-        scsitape_counters.io_ns =
-            utils::collect_info_u64("io_ns", &scsitape_item_path)?.unwrap_or_default();
+    scsitape_counters.read_byte_cnt =
+        utils::collect_info_u64("read_byte_cnt", scsitape_item_path)?.unwrap_or_default();
 
-        scsitape_counters.write_cnt =
-            utils::collect_info_u64("write_cnt", &scsitape_item_path)?.unwrap_or_default();
+    scsitape_counters.io_ns =
+        utils::collect_info_u64("io_ns", scsitape_item_path)?.unwrap_or_default();
 
-        scsitape_counters.resid_cnt =
-            utils::collect_info_u64("resid_cnt", &scsitape_item_path)?.unwrap_or_default();
+    scsitape_counters.write_cnt =
+        utils::collect_info_u64("write_cnt", scsitape_item_path)?.unwrap_or_default();
 
-        scsitape_counters.read_ns =
-            utils::collect_info_u64("read_ns", &scsitape_item_path)?.unwrap_or_default();
+    scsitape_counters.resid_cnt =
+        utils::collect_info_u64("resid_cnt", scsitape_item_path)?.unwrap_or_default();
 
-        scsitape_counters.in_flight =
-            utils::collect_info_u64("in_flight", &scsitape_item_path)?.unwrap_or_default();
+    scsitape_counters.read_ns =
+        utils::collect_info_u64("read_ns", scsitape_item_path)?.unwrap_or_default();
 
-        scsitape_counters.other_cnt =
-            utils::collect_info_u64("other_cnt", &scsitape_item_path)?.unwrap_or_default();
+    scsitape_counters.in_flight =
+        utils::collect_info_u64("in_flight", scsitape_item_path)?.unwrap_or_default();
 
-        scsitape_counters.read_cnt =
-            utils::collect_info_u64("read_cnt", &scsitape_item_path)?.unwrap_or_default();
+    scsitape_counters.other_cnt =
+        utils::collect_info_u64("other_cnt", scsitape_item_path)?.unwrap_or_default();
 
-        scsitape_counters.write_byte_cnt =
-            utils::collect_info_u64("write_byte_cnt", &scsitape_item_path)?.unwrap_or_default();
+    scsitape_counters.read_cnt =
+        utils::collect_info_u64("read_cnt", scsitape_item_path)?.unwrap_or_default();
 
-        scsi_tapes.insert(scsitape_item, scsitape_counters);
-    }
+    scsitape_counters.write_byte_cnt =
+        utils::collect_info_u64("write_byte_cnt", scsitape_item_path)?.unwrap_or_default();
 
-    Ok(scsi_tapes)
+    Ok(scsitape_counters)
 }
 
 #[cfg(test)]
@@ -93,7 +126,7 @@ mod tests {
 
     #[test]
     fn scsi_tape_counters() {
-        let scsi_tapes = collect_from("test_data/fixtures/sys/class/scsi_tape/")
+        let scsi_tapes = collect_from(Path::new("test_data/fixtures/sys/class/scsi_tape/"))
             .expect("collecting scsi tapes stats information");
 
         for (name, stats) in scsi_tapes {
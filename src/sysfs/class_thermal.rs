@@ -1,9 +1,13 @@
 use std::path::{Path, PathBuf};
 
-use serde::Serialize;
-use walkdir::WalkDir;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-use crate::{error::CollectResult, utils};
+use crate::{
+    error::{CollectResult, Partial},
+    sysfs::SysFs,
+    utils,
+};
 
 enum ThermalZoneInfo {
     ZoneType,
@@ -28,7 +32,8 @@ impl ThermalZoneInfo {
 }
 
 /// ThermalZone contains info from files in /sys/class/thermal/thermal_zoneX.
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ThermalZone {
     pub name: String,
     pub zone_type: String,
@@ -59,81 +64,82 @@ impl ThermalZone {
 /// }
 ///
 /// // print all thermal devices information in json format
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&thermal_devices).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<Vec<ThermalZone>> {
-    let thermal_zone_class_path = Path::new("/sys/class/thermal/");
-    collect_from(thermal_zone_class_path)
+    SysFs::default().class_thermal()
 }
 
-fn collect_from(base_path: &Path) -> CollectResult<Vec<ThermalZone>> {
-    let mut thermal_zone_devices = Vec::new();
+/// collects the thermal zones, an unreadable thermal_zone directory is reported under
+/// its name
+pub fn collect_lenient() -> CollectResult<Partial<Vec<ThermalZone>>> {
+    SysFs::default().class_thermal_lenient()
+}
 
-    for tdevice in utils::list_dir_content(base_path, "thermal_zone", "thermal") {
-        let mut thermal_device = ThermalZone::new();
-        thermal_device.name = tdevice.to_string();
+pub(crate) fn collect_from(base_path: &Path) -> CollectResult<Vec<ThermalZone>> {
+    collect_lenient_from(base_path)?.into_result()
+}
 
+pub(crate) fn collect_lenient_from(base_path: &Path) -> CollectResult<Partial<Vec<ThermalZone>>> {
+    let mut thermal_zone_devices: Partial<Vec<ThermalZone>> = Partial::default();
+
+    for tdevice in utils::list_dir_content(base_path, "thermal_zone", "thermal") {
         let mut tdev_path = PathBuf::from(base_path);
 
         tdev_path.push(&tdevice);
 
-        for tdev_info in WalkDir::new(&tdev_path).into_iter().filter_map(|e| e.ok()) {
-            let tdev_info_name = tdev_info
-                .file_name()
-                .to_str()
-                .unwrap_or_default()
-                .to_string();
+        match collect_thermal_zone(&tdev_path, &tdevice) {
+            Ok(thermal_device) => thermal_zone_devices.items.push(thermal_device),
+            Err(err) => thermal_zone_devices.push_error(&tdevice, &tdev_path, err),
+        }
+    }
 
-            if tdev_info_name == tdevice {
-                continue;
-            }
+    Ok(thermal_zone_devices)
+}
 
-            match ThermalZoneInfo::from(&tdev_info_name) {
-                ThermalZoneInfo::Mode => {
-                    if let Some(c) =
-                        utils::collect_info_string(&tdev_info_name, tdev_path.as_path())?
-                    {
-                        match c.as_str() {
-                            "enabled" => thermal_device.mode = Some(true),
-                            "disabled" => thermal_device.mode = Some(false),
-                            _ => thermal_device.mode = None,
-                        }
+fn collect_thermal_zone(tdev_path: &Path, tdevice: &str) -> CollectResult<ThermalZone> {
+    let mut thermal_device = ThermalZone::new();
+    thermal_device.name = tdevice.to_string();
+
+    for tdev_info_name in utils::list_dir_content(tdev_path, "", "") {
+        match ThermalZoneInfo::from(&tdev_info_name) {
+            ThermalZoneInfo::Mode => {
+                if let Some(c) = utils::collect_info_string(&tdev_info_name, tdev_path)? {
+                    match c.as_str() {
+                        "enabled" => thermal_device.mode = Some(true),
+                        "disabled" => thermal_device.mode = Some(false),
+                        _ => thermal_device.mode = None,
                     }
                 }
-                ThermalZoneInfo::Temp => {
-                    if let Some(c) = utils::collect_info_i64(&tdev_info_name, tdev_path.as_path())?
-                    {
-                        thermal_device.temp = c;
-                    }
-                }
-                ThermalZoneInfo::Passive => {
-                    thermal_device.passive =
-                        utils::collect_info_u64(&tdev_info_name, tdev_path.as_path())?;
+            }
+            ThermalZoneInfo::Temp => {
+                if let Some(c) = utils::collect_info_i64(&tdev_info_name, tdev_path)? {
+                    thermal_device.temp = c;
                 }
-                ThermalZoneInfo::Policy => {
-                    if let Some(c) =
-                        utils::collect_info_string(&tdev_info_name, tdev_path.as_path())?
-                    {
-                        thermal_device.policy = c;
-                    }
+            }
+            ThermalZoneInfo::Passive => {
+                thermal_device.passive = utils::collect_info_u64(&tdev_info_name, tdev_path)?;
+            }
+            ThermalZoneInfo::Policy => {
+                if let Some(c) = utils::collect_info_string(&tdev_info_name, tdev_path)? {
+                    thermal_device.policy = c;
                 }
-                ThermalZoneInfo::ZoneType => {
-                    if let Some(c) =
-                        utils::collect_info_string(&tdev_info_name, tdev_path.as_path())?
-                    {
-                        thermal_device.zone_type = c;
-                    }
+            }
+            ThermalZoneInfo::ZoneType => {
+                if let Some(c) = utils::collect_info_string(&tdev_info_name, tdev_path)? {
+                    thermal_device.zone_type = c;
                 }
-                ThermalZoneInfo::Unknown => {}
             }
+            ThermalZoneInfo::Unknown => {}
         }
-
-        thermal_zone_devices.push(thermal_device);
     }
 
-    Ok(thermal_zone_devices)
+    Ok(thermal_device)
 }
 
 #[cfg(test)]
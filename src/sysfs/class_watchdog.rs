@@ -1,9 +1,13 @@
 use std::path::{Path, PathBuf};
 
-use serde::Serialize;
-use walkdir::WalkDir;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-use crate::{error::CollectResult, utils};
+use crate::{
+    error::{CollectResult, Partial},
+    sysfs::SysFs,
+    utils,
+};
 
 enum WatchdogInfo {
     BootStatus,
@@ -46,7 +50,8 @@ impl WatchdogInfo {
 }
 
 /// Watchdog contains a watchdog device stat information from files in /sys/class/watchdog
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Watchdog {
     pub name: String,
     pub boot_status: Option<i64>,
@@ -86,107 +91,97 @@ impl Watchdog {
 /// }
 ///
 /// // print all watchdog devices information in json format
+/// # #[cfg(feature = "serde")]
+/// # {
 /// let json_output = serde_json::to_string_pretty(&watchdog_devices).unwrap();
 /// println!("{}", json_output);
+/// # }
 ///
 /// ```
 pub fn collect() -> CollectResult<Vec<Watchdog>> {
-    let watchdog_class_path = Path::new("/sys/class/watchdog/");
-    collect_from(watchdog_class_path)
+    SysFs::default().class_watchdog()
 }
 
-fn collect_from(base_path: &Path) -> CollectResult<Vec<Watchdog>> {
-    let mut devices = Vec::new();
+/// collects the watchdog devices, a watchdog with an unreadable attribute is reported
+/// under its name
+pub fn collect_lenient() -> CollectResult<Partial<Vec<Watchdog>>> {
+    SysFs::default().class_watchdog_lenient()
+}
 
-    for device in utils::list_dir_content(base_path, "", "watchdog") {
-        let mut watchdog_dev = Watchdog::new();
-        watchdog_dev.name = device.to_string();
+pub(crate) fn collect_from(base_path: &Path) -> CollectResult<Vec<Watchdog>> {
+    collect_lenient_from(base_path)?.into_result()
+}
 
+pub(crate) fn collect_lenient_from(base_path: &Path) -> CollectResult<Partial<Vec<Watchdog>>> {
+    let mut devices: Partial<Vec<Watchdog>> = Partial::default();
+
+    for device in utils::list_dir_content(base_path, "", "watchdog") {
         let mut wdev_path = PathBuf::from(base_path);
         wdev_path.push(&device);
 
-        for dev_info in WalkDir::new(&wdev_path).into_iter().filter_map(|e| e.ok()) {
-            if *dev_info
-                .file_name()
-                .to_str()
-                .unwrap_or_default()
-                .to_string()
-                == device
-            {
-                continue;
-            }
+        match collect_watchdog(&wdev_path, &device) {
+            Ok(watchdog_dev) => devices.items.push(watchdog_dev),
+            Err(err) => devices.push_error(&device, &wdev_path, err),
+        }
+    }
 
-            let wdev_filename = dev_info
-                .file_name()
-                .to_str()
-                .unwrap_or_default()
-                .to_string();
+    Ok(devices)
+}
 
-            match WatchdogInfo::from(wdev_filename.as_str()) {
-                WatchdogInfo::BootStatus => {
-                    watchdog_dev.boot_status =
-                        utils::collect_info_i64(&wdev_filename, wdev_path.as_path())?;
-                }
-                WatchdogInfo::Options => {
-                    watchdog_dev.options =
-                        utils::collect_info_string(&wdev_filename, wdev_path.as_path())?;
-                }
-                WatchdogInfo::FwVersion => {
-                    watchdog_dev.fw_version =
-                        utils::collect_info_i64(&wdev_filename, wdev_path.as_path())?;
-                }
-                WatchdogInfo::Identity => {
-                    watchdog_dev.identity =
-                        utils::collect_info_string(&wdev_filename, wdev_path.as_path())?;
-                }
-                WatchdogInfo::Nowayout => {
-                    watchdog_dev.nowayout =
-                        utils::collect_info_i64(&wdev_filename, wdev_path.as_path())?;
-                }
-                WatchdogInfo::State => {
-                    watchdog_dev.state =
-                        utils::collect_info_string(&wdev_filename, wdev_path.as_path())?;
-                }
-                WatchdogInfo::Status => {
-                    watchdog_dev.status =
-                        utils::collect_info_string(&wdev_filename, wdev_path.as_path())?;
-                }
-                WatchdogInfo::Timeleft => {
-                    watchdog_dev.timeleft =
-                        utils::collect_info_i64(&wdev_filename, wdev_path.as_path())?;
-                }
-                WatchdogInfo::Timeout => {
-                    watchdog_dev.timeout =
-                        utils::collect_info_i64(&wdev_filename, wdev_path.as_path())?;
-                }
-                WatchdogInfo::MinTimeout => {
-                    watchdog_dev.min_timeout =
-                        utils::collect_info_i64(&wdev_filename, wdev_path.as_path())?;
-                }
-                WatchdogInfo::MaxTimeout => {
-                    watchdog_dev.max_timeout =
-                        utils::collect_info_i64(&wdev_filename, wdev_path.as_path())?;
-                }
-                WatchdogInfo::Pretimeout => {
-                    watchdog_dev.pretimeout =
-                        utils::collect_info_i64(&wdev_filename, wdev_path.as_path())?;
-                }
-                WatchdogInfo::PretimeoutGovernor => {
-                    watchdog_dev.pretimeout_governor =
-                        utils::collect_info_string(&wdev_filename, wdev_path.as_path())?;
-                }
-                WatchdogInfo::AccessCs0 => {
-                    watchdog_dev.access_cs0 =
-                        utils::collect_info_i64(&wdev_filename, wdev_path.as_path())?;
-                }
-                WatchdogInfo::Unknown => {}
+fn collect_watchdog(wdev_path: &Path, device: &str) -> CollectResult<Watchdog> {
+    let mut watchdog_dev = Watchdog::new();
+    watchdog_dev.name = device.to_string();
+
+    for wdev_filename in utils::list_dir_content(wdev_path, "", "") {
+        match WatchdogInfo::from(wdev_filename.as_str()) {
+            WatchdogInfo::BootStatus => {
+                watchdog_dev.boot_status = utils::collect_info_i64(&wdev_filename, wdev_path)?;
+            }
+            WatchdogInfo::Options => {
+                watchdog_dev.options = utils::collect_info_string(&wdev_filename, wdev_path)?;
+            }
+            WatchdogInfo::FwVersion => {
+                watchdog_dev.fw_version = utils::collect_info_i64(&wdev_filename, wdev_path)?;
+            }
+            WatchdogInfo::Identity => {
+                watchdog_dev.identity = utils::collect_info_string(&wdev_filename, wdev_path)?;
+            }
+            WatchdogInfo::Nowayout => {
+                watchdog_dev.nowayout = utils::collect_info_i64(&wdev_filename, wdev_path)?;
+            }
+            WatchdogInfo::State => {
+                watchdog_dev.state = utils::collect_info_string(&wdev_filename, wdev_path)?;
             }
+            WatchdogInfo::Status => {
+                watchdog_dev.status = utils::collect_info_string(&wdev_filename, wdev_path)?;
+            }
+            WatchdogInfo::Timeleft => {
+                watchdog_dev.timeleft = utils::collect_info_i64(&wdev_filename, wdev_path)?;
+            }
+            WatchdogInfo::Timeout => {
+                watchdog_dev.timeout = utils::collect_info_i64(&wdev_filename, wdev_path)?;
+            }
+            WatchdogInfo::MinTimeout => {
+                watchdog_dev.min_timeout = utils::collect_info_i64(&wdev_filename, wdev_path)?;
+            }
+            WatchdogInfo::MaxTimeout => {
+                watchdog_dev.max_timeout = utils::collect_info_i64(&wdev_filename, wdev_path)?;
+            }
+            WatchdogInfo::Pretimeout => {
+                watchdog_dev.pretimeout = utils::collect_info_i64(&wdev_filename, wdev_path)?;
+            }
+            WatchdogInfo::PretimeoutGovernor => {
+                watchdog_dev.pretimeout_governor =
+                    utils::collect_info_string(&wdev_filename, wdev_path)?;
+            }
+            WatchdogInfo::AccessCs0 => {
+                watchdog_dev.access_cs0 = utils::collect_info_i64(&wdev_filename, wdev_path)?;
+            }
+            WatchdogInfo::Unknown => {}
         }
-
-        devices.push(watchdog_dev);
     }
 
-    Ok(devices)
+    Ok(watchdog_dev)
 }
 
 #[cfg(test)]
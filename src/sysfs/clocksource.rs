@@ -1,9 +1,14 @@
-use std::{fs, path::Path};
+use std::path::Path;
 
-use serde::Serialize;
-use walkdir::WalkDir;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-use crate::error::{CollectResult, MetricError};
+use crate::{
+    error::{CollectResult, Partial},
+    filesystem,
+    sysfs::SysFs,
+    utils,
+};
 
 enum ClocksourceInfo {
     AvailableClockSource,
@@ -22,7 +27,8 @@ impl ClocksourceInfo {
 }
 
 /// Clocksource contains a clocksource information read from '/sys/devices/system/clocksource'
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Clocksource {
     pub name: String,
     pub available_clocksource: Vec<String>,
@@ -58,24 +64,24 @@ impl Clocksource {
 ///
 /// ```
 pub fn collect() -> CollectResult<Vec<Clocksource>> {
-    let clock_source_path = Path::new("/sys/devices/system/clocksource");
-    collect_from(clock_source_path)
+    SysFs::default().clocksource()
 }
 
-fn collect_from(base_path: &Path) -> CollectResult<Vec<Clocksource>> {
-    let mut clock_sources = Vec::new();
+/// collects the clocksources, an unreadable clocksource directory is reported under
+/// its name
+pub fn collect_lenient() -> CollectResult<Partial<Vec<Clocksource>>> {
+    SysFs::default().clocksource_lenient()
+}
 
-    for clock_dev in WalkDir::new(base_path).into_iter().filter_map(|e| e.ok()) {
-        if clock_dev.file_name() == "clocksource" {
-            continue;
-        }
+pub(crate) fn collect_from(base_path: &Path) -> CollectResult<Vec<Clocksource>> {
+    collect_lenient_from(base_path)?.into_result()
+}
+
+pub(crate) fn collect_lenient_from(base_path: &Path) -> CollectResult<Partial<Vec<Clocksource>>> {
+    let mut clock_sources: Partial<Vec<Clocksource>> = Partial::default();
 
-        let clocksource_name = clock_dev
-            .file_name()
-            .to_str()
-            .unwrap_or_default()
-            .trim()
-            .to_string();
+    for clock_dev in utils::list_dir_content(base_path, "", "clocksource") {
+        let clocksource_name = clock_dev.trim().to_string();
 
         if clocksource_name.is_empty() {
             continue;
@@ -85,33 +91,42 @@ fn collect_from(base_path: &Path) -> CollectResult<Vec<Clocksource>> {
             continue;
         }
 
-        let current_clocksource = collect_clocksource_info(
-            ClocksourceInfo::CurrentClockSource,
-            &clocksource_name,
-            base_path,
-        )?
-        .unwrap_or_default();
-
-        let available_clocksource = collect_clocksource_info(
-            ClocksourceInfo::AvailableClockSource,
-            &clocksource_name,
-            base_path,
-        )?
-        .unwrap_or_default()
-        .split(' ')
-        .map(|v| v.to_string())
-        .collect::<Vec<String>>();
-
-        clock_sources.push(Clocksource::new(
-            clocksource_name,
-            available_clocksource,
-            current_clocksource,
-        ));
+        match collect_clocksource(base_path, &clocksource_name) {
+            Ok(clocksource) => clock_sources.items.push(clocksource),
+            Err(err) => {
+                clock_sources.push_error(&clocksource_name, &base_path.join(&clocksource_name), err)
+            }
+        }
     }
 
     Ok(clock_sources)
 }
 
+fn collect_clocksource(base_path: &Path, clocksource_name: &str) -> CollectResult<Clocksource> {
+    let current_clocksource = collect_clocksource_info(
+        ClocksourceInfo::CurrentClockSource,
+        clocksource_name,
+        base_path,
+    )?
+    .unwrap_or_default();
+
+    let available_clocksource = collect_clocksource_info(
+        ClocksourceInfo::AvailableClockSource,
+        clocksource_name,
+        base_path,
+    )?
+    .unwrap_or_default()
+    .split(' ')
+    .map(|v| v.to_string())
+    .collect::<Vec<String>>();
+
+    Ok(Clocksource::new(
+        clocksource_name.to_string(),
+        available_clocksource,
+        current_clocksource,
+    ))
+}
+
 fn collect_clocksource_info(
     info: ClocksourceInfo,
     name: &str,
@@ -120,10 +135,9 @@ fn collect_clocksource_info(
     let info_str = info.into_string();
     let info_path = Path::new(class_path).join(name).join(info_str);
 
-    match fs::read_to_string(info_path.as_path()) {
-        Ok(content) => Ok(Some(content.trim().to_string())),
-        Err(err) => Err(MetricError::IOError(info_path, err)),
-    }
+    let content = filesystem::read_to_string(&info_path)?;
+
+    Ok(Some(content.trim().to_string()))
 }
 
 #[cfg(test)]
@@ -1,14 +1,360 @@
+#[cfg(any(
+    feature = "sysfs-storage",
+    feature = "sysfs-power",
+    feature = "sysfs-hw"
+))]
+use std::collections::HashMap;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+#[cfg(any(
+    feature = "sysfs-storage",
+    feature = "sysfs-power",
+    feature = "sysfs-hw"
+))]
+use crate::error::Partial;
+use crate::{
+    error::CollectResult,
+    filesystem::{self, FileSystem},
+};
+
+#[cfg(feature = "sysfs-storage")]
+pub mod block;
+#[cfg(feature = "sysfs-storage")]
+pub mod block_topology;
+#[cfg(feature = "sysfs-power")]
 pub mod class_cooling;
+#[cfg(feature = "sysfs-hw")]
 pub mod class_dmi;
+#[cfg(feature = "sysfs-storage")]
 pub mod class_fibrechannel;
+#[cfg(feature = "sysfs-hw")]
 pub mod class_infiniband;
+#[cfg(feature = "sysfs-storage")]
 pub mod class_nvme;
+#[cfg(feature = "sysfs-power")]
 pub mod class_power_supply;
+#[cfg(feature = "sysfs-storage")]
 pub mod class_sas_device;
+#[cfg(feature = "sysfs-storage")]
 pub mod class_sas_host;
+#[cfg(feature = "sysfs-storage")]
 pub mod class_sas_phy;
+#[cfg(feature = "sysfs-storage")]
 pub mod class_sas_port;
+#[cfg(feature = "sysfs-storage")]
 pub mod class_scsi_tape;
+#[cfg(feature = "sysfs-power")]
 pub mod class_thermal;
+#[cfg(feature = "sysfs-hw")]
 pub mod class_watchdog;
+#[cfg(feature = "sysfs-hw")]
 pub mod clocksource;
+
+/// default mount point of the sys pseudo-filesystem
+pub const DEFAULT_MOUNT_POINT: &str = "/sys";
+
+/// SysFs is a handle to a sys filesystem mounted at an arbitrary root
+/// (e.g. a host's /sys bind mounted at /host/sys inside a container)
+#[derive(Debug, Clone)]
+pub struct SysFs {
+    root: PathBuf,
+    fs: Arc<dyn FileSystem>,
+}
+
+impl PartialEq for SysFs {
+    fn eq(&self, other: &Self) -> bool {
+        self.root == other.root && Arc::ptr_eq(&self.fs, &other.fs)
+    }
+}
+
+impl Eq for SysFs {}
+
+impl Default for SysFs {
+    fn default() -> Self {
+        Self::new(DEFAULT_MOUNT_POINT)
+    }
+}
+
+impl SysFs {
+    /// returns a new sys filesystem handle for the given mount point
+    /// # Example
+    /// ```
+    /// use procsys::SysFs;
+    ///
+    /// let sysfs = SysFs::new("/sys");
+    /// # #[cfg(feature = "sysfs-power")]
+    /// # {
+    /// let thermal_zones = sysfs.class_thermal().expect("thermal zones information");
+    /// println!("{:?}", thermal_zones);
+    /// # }
+    ///
+    /// ```
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self::with_filesystem(root, filesystem::real())
+    }
+
+    /// returns a new sys filesystem handle for the given mount point reading from `fs`
+    /// instead of the real filesystem (e.g. a [`crate::filesystem::MemoryFileSystem`])
+    pub fn with_filesystem<P: AsRef<Path>>(root: P, fs: Arc<dyn FileSystem>) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            fs,
+        }
+    }
+
+    /// returns the mount point of the sys filesystem
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// returns the absolute path of an entry relative to the mount point
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.root.join(name)
+    }
+
+    /// returns the filesystem the handle reads from
+    pub fn filesystem(&self) -> &Arc<dyn FileSystem> {
+        &self.fs
+    }
+
+    // runs a parser reading from the filesystem of the handle
+    pub(crate) fn scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        filesystem::scope(&self.fs, f)
+    }
+
+    // runs the parser of `<root>/<name>` reading from the filesystem of the
+    // handle, and adds the path to the errors which do not carry one
+    pub(crate) fn collect_at<T>(
+        &self,
+        name: &str,
+        f: impl FnOnce(&Path) -> CollectResult<T>,
+    ) -> CollectResult<T> {
+        let path = self.path(name);
+        self.scope(|| f(&path)).map_err(|err| err.with_path(&path))
+    }
+
+    /// collects block devices statistics and queue attributes from `<root>/block`
+    #[cfg(feature = "sysfs-storage")]
+    pub fn block(&self) -> CollectResult<HashMap<String, block::BlockDevice>> {
+        self.collect_at("block", block::collect_from)
+    }
+
+    /// like [`SysFs::block`], but keeps the entries that parsed and reports the others
+    #[cfg(feature = "sysfs-storage")]
+    pub fn block_lenient(&self) -> CollectResult<Partial<HashMap<String, block::BlockDevice>>> {
+        self.collect_at("block", block::collect_lenient_from)
+    }
+
+    /// builds the block devices topology from `<root>/block` and `<root>/class/block`
+    #[cfg(feature = "sysfs-storage")]
+    pub fn block_topology(&self) -> CollectResult<block_topology::BlockTopology> {
+        self.scope(|| block_topology::collect_from(&self.root))
+            .map(|topology| topology.with_sysfs(self))
+    }
+
+    /// collects cooling devices from `<root>/class/thermal/cooling_device<X>`
+    #[cfg(feature = "sysfs-power")]
+    pub fn class_cooling(&self) -> CollectResult<Vec<class_cooling::Cooling>> {
+        self.collect_at("class/thermal", class_cooling::collect_from)
+    }
+
+    /// like [`SysFs::class_cooling`], but keeps the entries that parsed and reports the others
+    #[cfg(feature = "sysfs-power")]
+    pub fn class_cooling_lenient(&self) -> CollectResult<Partial<Vec<class_cooling::Cooling>>> {
+        self.collect_at("class/thermal", class_cooling::collect_lenient_from)
+    }
+
+    /// collects DMI information from `<root>/class/dmi/id`
+    #[cfg(feature = "sysfs-hw")]
+    pub fn class_dmi(&self) -> CollectResult<class_dmi::DMI> {
+        self.collect_at("class/dmi/id", class_dmi::collect_from)
+    }
+
+    /// collects fibre channel hosts from `<root>/class/fc_host`
+    #[cfg(feature = "sysfs-storage")]
+    pub fn class_fibrechannel(
+        &self,
+    ) -> CollectResult<HashMap<String, class_fibrechannel::FibreChannelHost>> {
+        self.collect_at("class/fc_host", class_fibrechannel::collect_from)
+    }
+
+    /// like [`SysFs::class_fibrechannel`], but keeps the entries that parsed and reports the others
+    #[cfg(feature = "sysfs-storage")]
+    pub fn class_fibrechannel_lenient(
+        &self,
+    ) -> CollectResult<Partial<HashMap<String, class_fibrechannel::FibreChannelHost>>> {
+        self.collect_at("class/fc_host", class_fibrechannel::collect_lenient_from)
+    }
+
+    /// collects infiniband devices from `<root>/class/infiniband`
+    #[cfg(feature = "sysfs-hw")]
+    pub fn class_infiniband(
+        &self,
+    ) -> CollectResult<HashMap<String, class_infiniband::InfiniBandDevice>> {
+        self.collect_at("class/infiniband", class_infiniband::collect_from)
+    }
+
+    /// like [`SysFs::class_infiniband`], but keeps the entries that parsed and reports the others
+    #[cfg(feature = "sysfs-hw")]
+    pub fn class_infiniband_lenient(
+        &self,
+    ) -> CollectResult<Partial<HashMap<String, class_infiniband::InfiniBandDevice>>> {
+        self.collect_at("class/infiniband", class_infiniband::collect_lenient_from)
+    }
+
+    /// collects NVMe devices from `<root>/class/nvme`
+    #[cfg(feature = "sysfs-storage")]
+    pub fn class_nvme(&self) -> CollectResult<HashMap<String, class_nvme::NVMeDevice>> {
+        self.collect_at("class/nvme", class_nvme::collect_from)
+    }
+
+    /// like [`SysFs::class_nvme`], but keeps the entries that parsed and reports the others
+    #[cfg(feature = "sysfs-storage")]
+    pub fn class_nvme_lenient(
+        &self,
+    ) -> CollectResult<Partial<HashMap<String, class_nvme::NVMeDevice>>> {
+        self.collect_at("class/nvme", class_nvme::collect_lenient_from)
+    }
+
+    /// collects power supplies from `<root>/class/power_supply`
+    #[cfg(feature = "sysfs-power")]
+    pub fn class_power_supply(
+        &self,
+    ) -> CollectResult<HashMap<String, class_power_supply::PowerSupply>> {
+        self.collect_at("class/power_supply", class_power_supply::collect_from)
+    }
+
+    /// like [`SysFs::class_power_supply`], but keeps the entries that parsed and reports the others
+    #[cfg(feature = "sysfs-power")]
+    pub fn class_power_supply_lenient(
+        &self,
+    ) -> CollectResult<Partial<HashMap<String, class_power_supply::PowerSupply>>> {
+        self.collect_at(
+            "class/power_supply",
+            class_power_supply::collect_lenient_from,
+        )
+    }
+
+    /// collects SAS devices from `<root>/class/sas_device`
+    #[cfg(feature = "sysfs-storage")]
+    pub fn class_sas_device(&self) -> CollectResult<HashMap<String, class_sas_device::SASDevice>> {
+        self.collect_at("class/sas_device", class_sas_device::collect_from)
+    }
+
+    /// like [`SysFs::class_sas_device`], but keeps the entries that parsed and reports the others
+    #[cfg(feature = "sysfs-storage")]
+    pub fn class_sas_device_lenient(
+        &self,
+    ) -> CollectResult<Partial<HashMap<String, class_sas_device::SASDevice>>> {
+        self.collect_at("class/sas_device", class_sas_device::collect_lenient_from)
+    }
+
+    /// collects SAS hosts from `<root>/class/sas_host`
+    #[cfg(feature = "sysfs-storage")]
+    pub fn class_sas_host(&self) -> CollectResult<HashMap<String, class_sas_host::SASHost>> {
+        self.collect_at("class/sas_host", class_sas_host::collect_from)
+    }
+
+    /// collects SAS phys from `<root>/class/sas_phy`
+    #[cfg(feature = "sysfs-storage")]
+    pub fn class_sas_phy(&self) -> CollectResult<HashMap<String, class_sas_phy::SASPhy>> {
+        self.collect_at("class/sas_phy", class_sas_phy::collect_from)
+    }
+
+    /// like [`SysFs::class_sas_phy`], but keeps the entries that parsed and reports the others
+    #[cfg(feature = "sysfs-storage")]
+    pub fn class_sas_phy_lenient(
+        &self,
+    ) -> CollectResult<Partial<HashMap<String, class_sas_phy::SASPhy>>> {
+        self.collect_at("class/sas_phy", class_sas_phy::collect_lenient_from)
+    }
+
+    /// collects SAS ports from `<root>/class/sas_port`
+    #[cfg(feature = "sysfs-storage")]
+    pub fn class_sas_port(&self) -> CollectResult<HashMap<String, class_sas_port::SASPort>> {
+        self.collect_at("class/sas_port", class_sas_port::collect_from)
+    }
+
+    /// collects SCSI tape statistics from `<root>/class/scsi_tape`
+    #[cfg(feature = "sysfs-storage")]
+    pub fn class_scsi_tape(
+        &self,
+    ) -> CollectResult<HashMap<String, class_scsi_tape::ScsiTapeCounters>> {
+        self.collect_at("class/scsi_tape", class_scsi_tape::collect_from)
+    }
+
+    /// like [`SysFs::class_scsi_tape`], but keeps the entries that parsed and reports the others
+    #[cfg(feature = "sysfs-storage")]
+    pub fn class_scsi_tape_lenient(
+        &self,
+    ) -> CollectResult<Partial<HashMap<String, class_scsi_tape::ScsiTapeCounters>>> {
+        self.collect_at("class/scsi_tape", class_scsi_tape::collect_lenient_from)
+    }
+
+    /// collects thermal zones from `<root>/class/thermal/thermal_zone<X>`
+    #[cfg(feature = "sysfs-power")]
+    pub fn class_thermal(&self) -> CollectResult<Vec<class_thermal::ThermalZone>> {
+        self.collect_at("class/thermal", class_thermal::collect_from)
+    }
+
+    /// like [`SysFs::class_thermal`], but keeps the entries that parsed and reports the others
+    #[cfg(feature = "sysfs-power")]
+    pub fn class_thermal_lenient(&self) -> CollectResult<Partial<Vec<class_thermal::ThermalZone>>> {
+        self.collect_at("class/thermal", class_thermal::collect_lenient_from)
+    }
+
+    /// collects watchdog devices from `<root>/class/watchdog`
+    #[cfg(feature = "sysfs-hw")]
+    pub fn class_watchdog(&self) -> CollectResult<Vec<class_watchdog::Watchdog>> {
+        self.collect_at("class/watchdog", class_watchdog::collect_from)
+    }
+
+    /// like [`SysFs::class_watchdog`], but keeps the entries that parsed and reports the others
+    #[cfg(feature = "sysfs-hw")]
+    pub fn class_watchdog_lenient(&self) -> CollectResult<Partial<Vec<class_watchdog::Watchdog>>> {
+        self.collect_at("class/watchdog", class_watchdog::collect_lenient_from)
+    }
+
+    /// collects clock sources from `<root>/devices/system/clocksource`
+    #[cfg(feature = "sysfs-hw")]
+    pub fn clocksource(&self) -> CollectResult<Vec<clocksource::Clocksource>> {
+        self.collect_at("devices/system/clocksource", clocksource::collect_from)
+    }
+
+    /// like [`SysFs::clocksource`], but keeps the entries that parsed and reports the others
+    #[cfg(feature = "sysfs-hw")]
+    pub fn clocksource_lenient(&self) -> CollectResult<Partial<Vec<clocksource::Clocksource>>> {
+        self.collect_at(
+            "devices/system/clocksource",
+            clocksource::collect_lenient_from,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sysfs_alternate_root() {
+        let sysfs = SysFs::new("test_data/fixtures/sys");
+        assert_eq!(sysfs.root(), Path::new("test_data/fixtures/sys"));
+
+        #[cfg(feature = "sysfs-storage")]
+        {
+            let nvme_devices = sysfs.class_nvme().expect("nvme devices information");
+            assert_eq!(nvme_devices["nvme0"].serial, "S680HF8N190894I");
+        }
+
+        #[cfg(feature = "sysfs-hw")]
+        {
+            let infini_devices = sysfs.class_infiniband().expect("infiniband information");
+            assert!(infini_devices.contains_key("mlx4_0"));
+        }
+
+        assert_eq!(SysFs::default().root(), Path::new(DEFAULT_MOUNT_POINT));
+    }
+}
@@ -0,0 +1,349 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use crate::error::{CollectResult, MetricError};
+
+// separator written after every entry, identical to the one written by test_data/ttar.sh
+const ENTRY_SEPARATOR: &str =
+    "# ttar - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -";
+
+/// TtarEntry is a single file, directory or symbolic link of a ttar archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TtarEntry {
+    Directory { mode: u32 },
+    File { content: Vec<u8>, mode: u32 },
+    Symlink { target: String },
+}
+
+/// TtarArchive is a text archive in the format of `test_data/ttar.sh`, the
+/// format the test fixtures of procsys are stored in
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TtarArchive {
+    entries: BTreeMap<String, TtarEntry>,
+}
+
+impl TtarArchive {
+    /// returns an empty archive
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// adds an entry to the archive, replacing any entry with the same path,
+    /// the missing parent directories of the entry are added as well
+    pub fn insert(&mut self, path: &str, entry: TtarEntry) {
+        let path = path.trim_matches('/');
+
+        let components: Vec<&str> = path.split('/').collect();
+        for depth in 1..components.len() {
+            self.entries
+                .entry(components[..depth].join("/"))
+                .or_insert(TtarEntry::Directory { mode: 0o755 });
+        }
+
+        self.entries.insert(path.to_string(), entry);
+    }
+
+    /// returns the entry with the given path
+    pub fn get(&self, path: &str) -> Option<&TtarEntry> {
+        self.entries.get(path.trim_matches('/'))
+    }
+
+    /// returns all entries of the archive ordered by path
+    pub fn entries(&self) -> impl Iterator<Item = (&String, &TtarEntry)> {
+        self.entries.iter()
+    }
+
+    /// returns the number of entries in the archive
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// returns true if the archive has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// returns the archive content, it can be extracted with `ttar.sh -x -f <archive>`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut output = b"# Archive created by procsys\n".to_vec();
+
+        for (path, entry) in &self.entries {
+            match entry {
+                TtarEntry::Directory { mode } => {
+                    output.extend(format!("Directory: {}\nMode: {:o}\n", path, mode).as_bytes());
+                }
+                TtarEntry::File { content, mode } => {
+                    let (lines, encoded_content) = encode_content(content);
+
+                    output.extend(format!("Path: {}\nLines: {}\n", path, lines).as_bytes());
+                    output.extend(encoded_content);
+                    output.extend(format!("Mode: {:o}\n", mode).as_bytes());
+                }
+                TtarEntry::Symlink { target } => {
+                    output.extend(format!("Path: {}\nSymlinkTo: {}\n", path, target).as_bytes());
+                }
+            }
+
+            output.extend(ENTRY_SEPARATOR.as_bytes());
+            output.push(b'\n');
+        }
+
+        output
+    }
+
+    /// parses the content of an archive created by `ttar.sh -c` or [`TtarArchive::to_bytes`]
+    pub fn parse(content: &[u8]) -> CollectResult<Self> {
+        let mut archive = TtarArchive::new();
+        let mut lines = content
+            .strip_suffix(b"\n")
+            .unwrap_or(content)
+            .split(|b| *b == b'\n')
+            .enumerate();
+
+        // path of the entry the Lines, Mode and SymlinkTo keywords belong to
+        let mut path = String::new();
+
+        while let Some((line_no, line)) = lines.next() {
+            let line = String::from_utf8_lossy(line);
+
+            if let Some(entry_path) = line.strip_prefix("Path: ") {
+                path = entry_path.to_string();
+            } else if let Some(lines_count) = line.strip_prefix("Lines: ") {
+                let lines_count = lines_count.trim().parse::<usize>().map_err(|_| {
+                    MetricError::ParseError(format!("ttar line {}: {}", line_no + 1, line))
+                })?;
+
+                let mut file_content = Vec::new();
+                for _ in 0..lines_count {
+                    match lines.next() {
+                        Some((_, content_line)) => decode_line(content_line, &mut file_content),
+                        None => {
+                            return Err(MetricError::ParseError(format!(
+                                "ttar file {} truncated",
+                                path,
+                            )))
+                        }
+                    }
+                }
+
+                archive.insert(
+                    &path,
+                    TtarEntry::File {
+                        content: file_content,
+                        mode: 0o644,
+                    },
+                );
+            } else if let Some(mode) = line.strip_prefix("Mode: ") {
+                let mode = u32::from_str_radix(mode.trim(), 8).map_err(|_| {
+                    MetricError::ParseError(format!("ttar line {}: {}", line_no + 1, line))
+                })?;
+
+                match archive.entries.get_mut(path.trim_matches('/')) {
+                    Some(TtarEntry::Directory { mode: entry_mode })
+                    | Some(TtarEntry::File {
+                        mode: entry_mode, ..
+                    }) => *entry_mode = mode,
+                    _ => {
+                        return Err(MetricError::ParseError(format!(
+                            "ttar line {}: mode without entry",
+                            line_no + 1,
+                        )))
+                    }
+                }
+            } else if let Some(dir_path) = line.strip_prefix("Directory: ") {
+                path = dir_path.to_string();
+                archive.insert(&path, TtarEntry::Directory { mode: 0o755 });
+            } else if let Some(target) = line.strip_prefix("SymlinkTo: ") {
+                archive.insert(
+                    &path,
+                    TtarEntry::Symlink {
+                        target: target.to_string(),
+                    },
+                );
+            } else if !line.is_empty() && !line.starts_with('#') {
+                return Err(MetricError::ParseError(format!(
+                    "ttar line {}: unknown keyword: {}",
+                    line_no + 1,
+                    line,
+                )));
+            }
+        }
+
+        Ok(archive)
+    }
+
+    /// reads and parses the given archive file
+    pub fn read_from<P: AsRef<Path>>(filename: P) -> CollectResult<Self> {
+        let filename = filename.as_ref();
+
+        match fs::read(filename) {
+            Ok(content) => Self::parse(&content),
+            Err(err) => Err(MetricError::IOError(filename.to_path_buf(), err)),
+        }
+    }
+
+    /// writes the archive to the given file
+    pub fn write_to<P: AsRef<Path>>(&self, filename: P) -> CollectResult<()> {
+        let filename = filename.as_ref();
+
+        match fs::write(filename, self.to_bytes()) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(MetricError::IOError(filename.to_path_buf(), err)),
+        }
+    }
+}
+
+// escapes the EOF and NULLBYTE keywords and null bytes the same way as ttar.sh does,
+// a last line without line feed is terminated with EOF
+fn encode_content(content: &[u8]) -> (usize, Vec<u8>) {
+    let mut encoded = Vec::with_capacity(content.len());
+    let mut index = 0;
+
+    while index < content.len() {
+        let rest = &content[index..];
+
+        if rest.starts_with(b"EOF") {
+            encoded.extend(b"\\EOF");
+            index += 3;
+        } else if rest.starts_with(b"NULLBYTE") {
+            encoded.extend(b"\\NULLBYTE");
+            index += 8;
+        } else if rest[0] == 0 {
+            encoded.extend(b"NULLBYTE");
+            index += 1;
+        } else {
+            encoded.push(rest[0]);
+            index += 1;
+        }
+    }
+
+    let mut lines = content.iter().filter(|b| **b == b'\n').count();
+
+    if content.last().is_some_and(|b| *b != b'\n') {
+        encoded.extend(b"EOF\n");
+        lines += 1;
+    }
+
+    (lines, encoded)
+}
+
+// reverses encode_content for a single content line, an unescaped EOF marks
+// a last line without line feed
+fn decode_line(line: &[u8], content: &mut Vec<u8>) {
+    let mut index = 0;
+    let mut line_feed = true;
+
+    while index < line.len() {
+        let rest = &line[index..];
+
+        if rest.starts_with(b"\\EOF") {
+            content.extend(b"EOF");
+            index += 4;
+        } else if rest.starts_with(b"\\NULLBYTE") {
+            content.extend(b"NULLBYTE");
+            index += 9;
+        } else if rest.starts_with(b"EOF") {
+            line_feed = false;
+            index += 3;
+        } else if rest.starts_with(b"NULLBYTE") {
+            content.push(0);
+            index += 8;
+        } else {
+            content.push(rest[0]);
+            index += 1;
+        }
+    }
+
+    if line_feed {
+        content.push(b'\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ttar_archive_bytes() {
+        let mut archive = TtarArchive::new();
+        archive.insert(
+            "fixtures/proc/26231/cmdline",
+            TtarEntry::File {
+                content: b"vim\0test.go\0+10\0".to_vec(),
+                mode: 0o644,
+            },
+        );
+        archive.insert(
+            "fixtures/proc/26231/cwd",
+            TtarEntry::Symlink {
+                target: "/usr/bin".to_string(),
+            },
+        );
+        archive.insert(
+            "fixtures/proc/loadavg",
+            TtarEntry::File {
+                content: b"EOF NULLBYTE\n\nlast".to_vec(),
+                mode: 0o444,
+            },
+        );
+
+        assert_eq!(archive.len(), 6);
+        assert_eq!(
+            archive.get("fixtures/proc"),
+            Some(&TtarEntry::Directory { mode: 0o755 }),
+        );
+
+        let separator = format!("{}\n", ENTRY_SEPARATOR);
+        let expected = [
+            "# Archive created by procsys\n",
+            "Directory: fixtures\nMode: 755\n",
+            &separator,
+            "Directory: fixtures/proc\nMode: 755\n",
+            &separator,
+            "Directory: fixtures/proc/26231\nMode: 755\n",
+            &separator,
+            "Path: fixtures/proc/26231/cmdline\nLines: 1\n",
+            "vimNULLBYTEtest.goNULLBYTE+10NULLBYTEEOF\nMode: 644\n",
+            &separator,
+            "Path: fixtures/proc/26231/cwd\nSymlinkTo: /usr/bin\n",
+            &separator,
+            "Path: fixtures/proc/loadavg\nLines: 3\n",
+            "\\EOF \\NULLBYTE\n\nlastEOF\nMode: 444\n",
+            &separator,
+        ]
+        .concat();
+
+        assert_eq!(String::from_utf8(archive.to_bytes()).unwrap(), expected);
+        assert_eq!(TtarArchive::parse(&archive.to_bytes()).unwrap(), archive);
+    }
+
+    #[test]
+    fn ttar_archive_fixtures() {
+        let archive = TtarArchive::read_from("test_data/fixtures.ttar").expect("fixtures archive");
+
+        assert_eq!(
+            archive.get("fixtures/proc/26231/cmdline"),
+            Some(&TtarEntry::File {
+                content: b"vim\0test.go\0+10\0".to_vec(),
+                mode: 0o644,
+            }),
+        );
+        assert_eq!(
+            archive.get("fixtures/proc/26231/cwd"),
+            Some(&TtarEntry::Symlink {
+                target: "/usr/bin".to_string(),
+            }),
+        );
+
+        let loadavg = fs::read("test_data/fixtures/proc/loadavg").unwrap();
+        assert_eq!(
+            archive.get("fixtures/proc/loadavg"),
+            Some(&TtarEntry::File {
+                content: loadavg,
+                mode: 0o444,
+            }),
+        );
+
+        assert!(TtarArchive::parse(b"Path: a\nLines: 2\nx\n").is_err());
+        assert!(TtarArchive::parse(b"Unknown: a\n").is_err());
+    }
+}
@@ -1,12 +1,9 @@
-use std::{
-    fs::{self, File},
-    io::{BufRead, BufReader},
-    path::{Path, PathBuf},
-};
-
-use walkdir::WalkDir;
+use std::path::Path;
 
-use crate::error::{CollectResult, MetricError};
+use crate::{
+    error::{CollectResult, MetricError},
+    filesystem,
+};
 
 pub fn collect_info_string(filename: &str, dir_path: &Path) -> CollectResult<Option<String>> {
     if filename.is_empty() {
@@ -15,21 +12,17 @@ pub fn collect_info_string(filename: &str, dir_path: &Path) -> CollectResult<Opt
 
     let info_path = Path::new(dir_path).join(filename);
 
-    if !info_path.exists() {
+    if !filesystem::exists(&info_path) {
         return Ok(None);
     }
 
-    match fs::read_to_string(info_path.as_path()) {
-        Ok(c) => {
-            let value = c.trim().to_string();
-            if value.is_empty() {
-                return Ok(None);
-            }
-
-            Ok(Some(c.trim().to_string()))
-        }
-        Err(err) => Err(MetricError::IOError(info_path, err)),
+    let c = filesystem::read_to_string(&info_path)?;
+    let value = c.trim().to_string();
+    if value.is_empty() {
+        return Ok(None);
     }
+
+    Ok(Some(value))
 }
 
 pub fn collect_info_i64(filename: &str, dir_path: &Path) -> CollectResult<Option<i64>> {
@@ -65,48 +58,47 @@ pub fn list_dir_content(
 ) -> Vec<String> {
     let mut content = Vec::new();
 
-    for dir_item in WalkDir::new(dir_path)
-        .max_depth(1)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if dir_item.file_name() == exclude_pattern {
+    for dir_item_name in filesystem::list_dir(dir_path).unwrap_or_default() {
+        if dir_item_name == exclude_pattern {
             continue;
         }
 
-        let dir_item_name = dir_item.file_name().to_str().unwrap_or_default();
-
         if include_pattern.is_empty() {
-            content.push(dir_item_name.to_string());
+            content.push(dir_item_name);
 
             continue;
         }
 
         if dir_item_name.starts_with(include_pattern) {
-            content.push(dir_item_name.to_string());
+            content.push(dir_item_name);
         }
     }
 
     content
 }
 
-pub fn read_file_lines(filename: &str) -> CollectResult<Vec<String>> {
-    let mut result = Vec::new();
-
-    match File::open(filename) {
-        Ok(file) => {
-            let reader = BufReader::new(file);
-            for line_result in reader.lines() {
-                match line_result {
-                    Ok(line) => result.push(line),
-                    Err(err) => return Err(MetricError::IOError(PathBuf::from(filename), err)),
-                }
-            }
+/// returns true if the name is the prefix followed by a device number (e.g. `phy-0:1`)
+pub fn is_device_name(name: &str, prefix: &str) -> bool {
+    match name.strip_prefix(prefix) {
+        Some(number) => {
+            !number.is_empty() && number.bytes().all(|c| c.is_ascii_digit() || c == b':')
         }
-        Err(err) => return Err(MetricError::IOError(PathBuf::from(filename), err)),
+        None => false,
     }
+}
+
+pub fn read_file_lines<P: AsRef<Path>>(filename: P) -> CollectResult<Vec<String>> {
+    let content = filesystem::read_to_string(filename.as_ref())?;
 
-    Ok(result)
+    Ok(content.lines().map(|line| line.to_string()).collect())
+}
+
+/// returns the name used to report a broken line, its first field or its line number
+pub fn line_item(line: &str, line_number: usize) -> String {
+    match line.split_whitespace().next() {
+        Some(field) => field.trim_end_matches(':').to_string(),
+        None => format!("line {}", line_number),
+    }
 }
 
 pub fn convert_to_bytes(num: u64, unit: &str) -> CollectResult<Option<u64>> {
@@ -140,6 +132,13 @@ pub fn convert_hex_to_u64(value: &str) -> CollectResult<u64> {
     }
 }
 
+pub fn convert_radix_str_to_u64(value: &str, radix: u32) -> CollectResult<u64> {
+    match u64::from_str_radix(value, radix) {
+        Ok(v) => Ok(v),
+        Err(err) => Err(MetricError::ParseIntError(value.to_string(), err)),
+    }
+}
+
 pub fn convert_hex_to_i32(value: &str) -> CollectResult<i32> {
     match i32::from_str_radix(value.strip_prefix("0x").unwrap_or_default(), 16) {
         Ok(v) => Ok(v),
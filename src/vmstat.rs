@@ -0,0 +1,542 @@
+use std::{collections::HashMap, path::Path};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{CollectResult, MetricError},
+    procfs::ProcFs,
+    utils,
+};
+
+enum VmstatType {
+    NrFreePages,
+    NrInactiveAnon,
+    NrActiveAnon,
+    NrInactiveFile,
+    NrActiveFile,
+    NrUnevictable,
+    NrMlock,
+    NrAnonPages,
+    NrMapped,
+    NrFilePages,
+    NrDirty,
+    NrWriteback,
+    NrShmem,
+    NrSlabReclaimable,
+    NrSlabUnreclaimable,
+    NrPageTablePages,
+    NrKernelStack,
+    NrAnonTransparentHugepages,
+    NrDirtyThreshold,
+    NrDirtyBackgroundThreshold,
+    NrDirtied,
+    NrWritten,
+    Pgpgin,
+    Pgpgout,
+    Pswpin,
+    Pswpout,
+    Pgfault,
+    Pgmajfault,
+    Pgfree,
+    Pgactivate,
+    Pgdeactivate,
+    Pgrefill,
+    Pgrotated,
+    PgstealKswapd,
+    PgstealDirect,
+    PgstealKhugepaged,
+    PgstealAnon,
+    PgstealFile,
+    PgscanKswapd,
+    PgscanDirect,
+    PgscanKhugepaged,
+    PgscanDirectThrottle,
+    PgscanAnon,
+    PgscanFile,
+    Pginodesteal,
+    SlabsScanned,
+    KswapdInodesteal,
+    KswapdLowWmarkHitQuickly,
+    KswapdHighWmarkHitQuickly,
+    Pageoutrun,
+    AllocstallDma,
+    AllocstallDma32,
+    AllocstallNormal,
+    AllocstallMovable,
+    DropPagecache,
+    DropSlab,
+    OomKill,
+    WorkingsetRefaultAnon,
+    WorkingsetRefaultFile,
+    WorkingsetActivateAnon,
+    WorkingsetActivateFile,
+    WorkingsetRestoreAnon,
+    WorkingsetRestoreFile,
+    WorkingsetNodereclaim,
+    NumaHit,
+    NumaMiss,
+    NumaForeign,
+    NumaInterleave,
+    NumaLocal,
+    NumaOther,
+    CompactMigrateScanned,
+    CompactFreeScanned,
+    CompactIsolated,
+    CompactStall,
+    CompactFail,
+    CompactSuccess,
+    CompactDaemonWake,
+    CompactDaemonMigrateScanned,
+    CompactDaemonFreeScanned,
+    ThpFaultAlloc,
+    ThpFaultFallback,
+    ThpFaultFallbackCharge,
+    ThpCollapseAlloc,
+    ThpCollapseAllocFailed,
+    ThpFileAlloc,
+    ThpFileMapped,
+    ThpSplitPage,
+    ThpSplitPageFailed,
+    ThpDeferredSplitPage,
+    ThpSplitPmd,
+    ThpZeroPageAlloc,
+    ThpZeroPageAllocFailed,
+    ThpSwpout,
+    ThpSwpoutFallback,
+    Unknown,
+}
+
+impl VmstatType {
+    fn from(name: &str) -> VmstatType {
+        match name {
+            "nr_free_pages" => VmstatType::NrFreePages,
+            "nr_inactive_anon" => VmstatType::NrInactiveAnon,
+            "nr_active_anon" => VmstatType::NrActiveAnon,
+            "nr_inactive_file" => VmstatType::NrInactiveFile,
+            "nr_active_file" => VmstatType::NrActiveFile,
+            "nr_unevictable" => VmstatType::NrUnevictable,
+            "nr_mlock" => VmstatType::NrMlock,
+            "nr_anon_pages" => VmstatType::NrAnonPages,
+            "nr_mapped" => VmstatType::NrMapped,
+            "nr_file_pages" => VmstatType::NrFilePages,
+            "nr_dirty" => VmstatType::NrDirty,
+            "nr_writeback" => VmstatType::NrWriteback,
+            "nr_shmem" => VmstatType::NrShmem,
+            "nr_slab_reclaimable" => VmstatType::NrSlabReclaimable,
+            "nr_slab_unreclaimable" => VmstatType::NrSlabUnreclaimable,
+            "nr_page_table_pages" => VmstatType::NrPageTablePages,
+            "nr_kernel_stack" => VmstatType::NrKernelStack,
+            "nr_anon_transparent_hugepages" => VmstatType::NrAnonTransparentHugepages,
+            "nr_dirty_threshold" => VmstatType::NrDirtyThreshold,
+            "nr_dirty_background_threshold" => VmstatType::NrDirtyBackgroundThreshold,
+            "nr_dirtied" => VmstatType::NrDirtied,
+            "nr_written" => VmstatType::NrWritten,
+            "pgpgin" => VmstatType::Pgpgin,
+            "pgpgout" => VmstatType::Pgpgout,
+            "pswpin" => VmstatType::Pswpin,
+            "pswpout" => VmstatType::Pswpout,
+            "pgfault" => VmstatType::Pgfault,
+            "pgmajfault" => VmstatType::Pgmajfault,
+            "pgfree" => VmstatType::Pgfree,
+            "pgactivate" => VmstatType::Pgactivate,
+            "pgdeactivate" => VmstatType::Pgdeactivate,
+            "pgrefill" => VmstatType::Pgrefill,
+            "pgrotated" => VmstatType::Pgrotated,
+            "pgsteal_kswapd" => VmstatType::PgstealKswapd,
+            "pgsteal_direct" => VmstatType::PgstealDirect,
+            "pgsteal_khugepaged" => VmstatType::PgstealKhugepaged,
+            "pgsteal_anon" => VmstatType::PgstealAnon,
+            "pgsteal_file" => VmstatType::PgstealFile,
+            "pgscan_kswapd" => VmstatType::PgscanKswapd,
+            "pgscan_direct" => VmstatType::PgscanDirect,
+            "pgscan_khugepaged" => VmstatType::PgscanKhugepaged,
+            "pgscan_direct_throttle" => VmstatType::PgscanDirectThrottle,
+            "pgscan_anon" => VmstatType::PgscanAnon,
+            "pgscan_file" => VmstatType::PgscanFile,
+            "pginodesteal" => VmstatType::Pginodesteal,
+            "slabs_scanned" => VmstatType::SlabsScanned,
+            "kswapd_inodesteal" => VmstatType::KswapdInodesteal,
+            "kswapd_low_wmark_hit_quickly" => VmstatType::KswapdLowWmarkHitQuickly,
+            "kswapd_high_wmark_hit_quickly" => VmstatType::KswapdHighWmarkHitQuickly,
+            "pageoutrun" => VmstatType::Pageoutrun,
+            "allocstall_dma" => VmstatType::AllocstallDma,
+            "allocstall_dma32" => VmstatType::AllocstallDma32,
+            "allocstall_normal" => VmstatType::AllocstallNormal,
+            "allocstall_movable" => VmstatType::AllocstallMovable,
+            "drop_pagecache" => VmstatType::DropPagecache,
+            "drop_slab" => VmstatType::DropSlab,
+            "oom_kill" => VmstatType::OomKill,
+            "workingset_refault_anon" => VmstatType::WorkingsetRefaultAnon,
+            "workingset_refault_file" => VmstatType::WorkingsetRefaultFile,
+            "workingset_activate_anon" => VmstatType::WorkingsetActivateAnon,
+            "workingset_activate_file" => VmstatType::WorkingsetActivateFile,
+            "workingset_restore_anon" => VmstatType::WorkingsetRestoreAnon,
+            "workingset_restore_file" => VmstatType::WorkingsetRestoreFile,
+            "workingset_nodereclaim" => VmstatType::WorkingsetNodereclaim,
+            "numa_hit" => VmstatType::NumaHit,
+            "numa_miss" => VmstatType::NumaMiss,
+            "numa_foreign" => VmstatType::NumaForeign,
+            "numa_interleave" => VmstatType::NumaInterleave,
+            "numa_local" => VmstatType::NumaLocal,
+            "numa_other" => VmstatType::NumaOther,
+            "compact_migrate_scanned" => VmstatType::CompactMigrateScanned,
+            "compact_free_scanned" => VmstatType::CompactFreeScanned,
+            "compact_isolated" => VmstatType::CompactIsolated,
+            "compact_stall" => VmstatType::CompactStall,
+            "compact_fail" => VmstatType::CompactFail,
+            "compact_success" => VmstatType::CompactSuccess,
+            "compact_daemon_wake" => VmstatType::CompactDaemonWake,
+            "compact_daemon_migrate_scanned" => VmstatType::CompactDaemonMigrateScanned,
+            "compact_daemon_free_scanned" => VmstatType::CompactDaemonFreeScanned,
+            "thp_fault_alloc" => VmstatType::ThpFaultAlloc,
+            "thp_fault_fallback" => VmstatType::ThpFaultFallback,
+            "thp_fault_fallback_charge" => VmstatType::ThpFaultFallbackCharge,
+            "thp_collapse_alloc" => VmstatType::ThpCollapseAlloc,
+            "thp_collapse_alloc_failed" => VmstatType::ThpCollapseAllocFailed,
+            "thp_file_alloc" => VmstatType::ThpFileAlloc,
+            "thp_file_mapped" => VmstatType::ThpFileMapped,
+            "thp_split_page" => VmstatType::ThpSplitPage,
+            "thp_split_page_failed" => VmstatType::ThpSplitPageFailed,
+            "thp_deferred_split_page" => VmstatType::ThpDeferredSplitPage,
+            "thp_split_pmd" => VmstatType::ThpSplitPmd,
+            "thp_zero_page_alloc" => VmstatType::ThpZeroPageAlloc,
+            "thp_zero_page_alloc_failed" => VmstatType::ThpZeroPageAllocFailed,
+            "thp_swpout" => VmstatType::ThpSwpout,
+            "thp_swpout_fallback" => VmstatType::ThpSwpoutFallback,
+            _ => VmstatType::Unknown,
+        }
+    }
+}
+
+/// Vmstat represents the virtual memory statistics from /proc/vmstat, the counters
+/// missing on the running kernel are None and the keys which are not known by procsys
+/// are kept in `unknown`
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Vmstat {
+    // free, active/inactive and dirty page gauges
+    pub nr_free_pages: Option<u64>,
+    pub nr_inactive_anon: Option<u64>,
+    pub nr_active_anon: Option<u64>,
+    pub nr_inactive_file: Option<u64>,
+    pub nr_active_file: Option<u64>,
+    pub nr_unevictable: Option<u64>,
+    pub nr_mlock: Option<u64>,
+    pub nr_anon_pages: Option<u64>,
+    pub nr_mapped: Option<u64>,
+    pub nr_file_pages: Option<u64>,
+    pub nr_dirty: Option<u64>,
+    pub nr_writeback: Option<u64>,
+    pub nr_shmem: Option<u64>,
+    pub nr_slab_reclaimable: Option<u64>,
+    pub nr_slab_unreclaimable: Option<u64>,
+    pub nr_page_table_pages: Option<u64>,
+    pub nr_kernel_stack: Option<u64>,
+    pub nr_anon_transparent_hugepages: Option<u64>,
+    pub nr_dirty_threshold: Option<u64>,
+    pub nr_dirty_background_threshold: Option<u64>,
+    pub nr_dirtied: Option<u64>,
+    pub nr_written: Option<u64>,
+
+    // paging and swapping
+    pub pgpgin: Option<u64>,
+    pub pgpgout: Option<u64>,
+    pub pswpin: Option<u64>,
+    pub pswpout: Option<u64>,
+    pub pgfault: Option<u64>,
+    pub pgmajfault: Option<u64>,
+    pub pgfree: Option<u64>,
+    pub pgactivate: Option<u64>,
+    pub pgdeactivate: Option<u64>,
+    pub pgrefill: Option<u64>,
+    pub pgrotated: Option<u64>,
+
+    // page reclaim, by kswapd and by direct reclaim
+    pub pgsteal_kswapd: Option<u64>,
+    pub pgsteal_direct: Option<u64>,
+    pub pgsteal_khugepaged: Option<u64>,
+    pub pgsteal_anon: Option<u64>,
+    pub pgsteal_file: Option<u64>,
+    pub pgscan_kswapd: Option<u64>,
+    pub pgscan_direct: Option<u64>,
+    pub pgscan_khugepaged: Option<u64>,
+    pub pgscan_direct_throttle: Option<u64>,
+    pub pgscan_anon: Option<u64>,
+    pub pgscan_file: Option<u64>,
+    pub pginodesteal: Option<u64>,
+    pub slabs_scanned: Option<u64>,
+    pub kswapd_inodesteal: Option<u64>,
+    pub kswapd_low_wmark_hit_quickly: Option<u64>,
+    pub kswapd_high_wmark_hit_quickly: Option<u64>,
+    pub pageoutrun: Option<u64>,
+    pub allocstall_dma: Option<u64>,
+    pub allocstall_dma32: Option<u64>,
+    pub allocstall_normal: Option<u64>,
+    pub allocstall_movable: Option<u64>,
+    pub drop_pagecache: Option<u64>,
+    pub drop_slab: Option<u64>,
+    pub oom_kill: Option<u64>,
+
+    // workingset
+    pub workingset_refault_anon: Option<u64>,
+    pub workingset_refault_file: Option<u64>,
+    pub workingset_activate_anon: Option<u64>,
+    pub workingset_activate_file: Option<u64>,
+    pub workingset_restore_anon: Option<u64>,
+    pub workingset_restore_file: Option<u64>,
+    pub workingset_nodereclaim: Option<u64>,
+
+    // numa
+    pub numa_hit: Option<u64>,
+    pub numa_miss: Option<u64>,
+    pub numa_foreign: Option<u64>,
+    pub numa_interleave: Option<u64>,
+    pub numa_local: Option<u64>,
+    pub numa_other: Option<u64>,
+
+    // compaction
+    pub compact_migrate_scanned: Option<u64>,
+    pub compact_free_scanned: Option<u64>,
+    pub compact_isolated: Option<u64>,
+    pub compact_stall: Option<u64>,
+    pub compact_fail: Option<u64>,
+    pub compact_success: Option<u64>,
+    pub compact_daemon_wake: Option<u64>,
+    pub compact_daemon_migrate_scanned: Option<u64>,
+    pub compact_daemon_free_scanned: Option<u64>,
+
+    // transparent huge pages
+    pub thp_fault_alloc: Option<u64>,
+    pub thp_fault_fallback: Option<u64>,
+    pub thp_fault_fallback_charge: Option<u64>,
+    pub thp_collapse_alloc: Option<u64>,
+    pub thp_collapse_alloc_failed: Option<u64>,
+    pub thp_file_alloc: Option<u64>,
+    pub thp_file_mapped: Option<u64>,
+    pub thp_split_page: Option<u64>,
+    pub thp_split_page_failed: Option<u64>,
+    pub thp_deferred_split_page: Option<u64>,
+    pub thp_split_pmd: Option<u64>,
+    pub thp_zero_page_alloc: Option<u64>,
+    pub thp_zero_page_alloc_failed: Option<u64>,
+    pub thp_swpout: Option<u64>,
+    pub thp_swpout_fallback: Option<u64>,
+
+    /// counters which do not have a field, keyed by their /proc/vmstat name
+    pub unknown: HashMap<String, u64>,
+}
+
+impl Vmstat {
+    fn new() -> Self {
+        Default::default()
+    }
+}
+
+/// collects the virtual memory statistics
+/// # Example
+/// ```
+/// use procsys::vmstat;
+///
+/// let sys_vmstat = vmstat::collect().expect("virtual memory statistics");
+/// println!("major page faults: {:?}", sys_vmstat.pgmajfault);
+/// println!("oom kills: {:?}", sys_vmstat.oom_kill);
+///
+/// # #[cfg(feature = "serde")]
+/// # {
+/// let json_output = serde_json::to_string_pretty(&sys_vmstat).unwrap();
+/// println!("{}", json_output);
+/// # }
+///
+/// ```
+pub fn collect() -> CollectResult<Vmstat> {
+    ProcFs::default().vmstat()
+}
+
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<Vmstat> {
+    let mut vmstat = Vmstat::new();
+
+    for (index, line) in utils::read_file_lines(filename)?.into_iter().enumerate() {
+        let item_fields: Vec<&str> = line.split_whitespace().collect();
+
+        if item_fields.len() != 2 {
+            let err = MetricError::InvalidFieldNumberError(
+                "vmstat".to_string(),
+                item_fields.len(),
+                line.to_owned(),
+            );
+
+            return Err(err.with_line(filename, index + 1));
+        }
+
+        let value = utils::convert_str_to_u64(item_fields[1])
+            .map_err(|err| err.with_line(filename, index + 1))?;
+
+        match VmstatType::from(item_fields[0]) {
+            VmstatType::NrFreePages => vmstat.nr_free_pages = Some(value),
+            VmstatType::NrInactiveAnon => vmstat.nr_inactive_anon = Some(value),
+            VmstatType::NrActiveAnon => vmstat.nr_active_anon = Some(value),
+            VmstatType::NrInactiveFile => vmstat.nr_inactive_file = Some(value),
+            VmstatType::NrActiveFile => vmstat.nr_active_file = Some(value),
+            VmstatType::NrUnevictable => vmstat.nr_unevictable = Some(value),
+            VmstatType::NrMlock => vmstat.nr_mlock = Some(value),
+            VmstatType::NrAnonPages => vmstat.nr_anon_pages = Some(value),
+            VmstatType::NrMapped => vmstat.nr_mapped = Some(value),
+            VmstatType::NrFilePages => vmstat.nr_file_pages = Some(value),
+            VmstatType::NrDirty => vmstat.nr_dirty = Some(value),
+            VmstatType::NrWriteback => vmstat.nr_writeback = Some(value),
+            VmstatType::NrShmem => vmstat.nr_shmem = Some(value),
+            VmstatType::NrSlabReclaimable => vmstat.nr_slab_reclaimable = Some(value),
+            VmstatType::NrSlabUnreclaimable => vmstat.nr_slab_unreclaimable = Some(value),
+            VmstatType::NrPageTablePages => vmstat.nr_page_table_pages = Some(value),
+            VmstatType::NrKernelStack => vmstat.nr_kernel_stack = Some(value),
+            VmstatType::NrAnonTransparentHugepages => {
+                vmstat.nr_anon_transparent_hugepages = Some(value)
+            }
+            VmstatType::NrDirtyThreshold => vmstat.nr_dirty_threshold = Some(value),
+            VmstatType::NrDirtyBackgroundThreshold => {
+                vmstat.nr_dirty_background_threshold = Some(value)
+            }
+            VmstatType::NrDirtied => vmstat.nr_dirtied = Some(value),
+            VmstatType::NrWritten => vmstat.nr_written = Some(value),
+            VmstatType::Pgpgin => vmstat.pgpgin = Some(value),
+            VmstatType::Pgpgout => vmstat.pgpgout = Some(value),
+            VmstatType::Pswpin => vmstat.pswpin = Some(value),
+            VmstatType::Pswpout => vmstat.pswpout = Some(value),
+            VmstatType::Pgfault => vmstat.pgfault = Some(value),
+            VmstatType::Pgmajfault => vmstat.pgmajfault = Some(value),
+            VmstatType::Pgfree => vmstat.pgfree = Some(value),
+            VmstatType::Pgactivate => vmstat.pgactivate = Some(value),
+            VmstatType::Pgdeactivate => vmstat.pgdeactivate = Some(value),
+            VmstatType::Pgrefill => vmstat.pgrefill = Some(value),
+            VmstatType::Pgrotated => vmstat.pgrotated = Some(value),
+            VmstatType::PgstealKswapd => vmstat.pgsteal_kswapd = Some(value),
+            VmstatType::PgstealDirect => vmstat.pgsteal_direct = Some(value),
+            VmstatType::PgstealKhugepaged => vmstat.pgsteal_khugepaged = Some(value),
+            VmstatType::PgstealAnon => vmstat.pgsteal_anon = Some(value),
+            VmstatType::PgstealFile => vmstat.pgsteal_file = Some(value),
+            VmstatType::PgscanKswapd => vmstat.pgscan_kswapd = Some(value),
+            VmstatType::PgscanDirect => vmstat.pgscan_direct = Some(value),
+            VmstatType::PgscanKhugepaged => vmstat.pgscan_khugepaged = Some(value),
+            VmstatType::PgscanDirectThrottle => vmstat.pgscan_direct_throttle = Some(value),
+            VmstatType::PgscanAnon => vmstat.pgscan_anon = Some(value),
+            VmstatType::PgscanFile => vmstat.pgscan_file = Some(value),
+            VmstatType::Pginodesteal => vmstat.pginodesteal = Some(value),
+            VmstatType::SlabsScanned => vmstat.slabs_scanned = Some(value),
+            VmstatType::KswapdInodesteal => vmstat.kswapd_inodesteal = Some(value),
+            VmstatType::KswapdLowWmarkHitQuickly => {
+                vmstat.kswapd_low_wmark_hit_quickly = Some(value)
+            }
+            VmstatType::KswapdHighWmarkHitQuickly => {
+                vmstat.kswapd_high_wmark_hit_quickly = Some(value)
+            }
+            VmstatType::Pageoutrun => vmstat.pageoutrun = Some(value),
+            VmstatType::AllocstallDma => vmstat.allocstall_dma = Some(value),
+            VmstatType::AllocstallDma32 => vmstat.allocstall_dma32 = Some(value),
+            VmstatType::AllocstallNormal => vmstat.allocstall_normal = Some(value),
+            VmstatType::AllocstallMovable => vmstat.allocstall_movable = Some(value),
+            VmstatType::DropPagecache => vmstat.drop_pagecache = Some(value),
+            VmstatType::DropSlab => vmstat.drop_slab = Some(value),
+            VmstatType::OomKill => vmstat.oom_kill = Some(value),
+            VmstatType::WorkingsetRefaultAnon => vmstat.workingset_refault_anon = Some(value),
+            VmstatType::WorkingsetRefaultFile => vmstat.workingset_refault_file = Some(value),
+            VmstatType::WorkingsetActivateAnon => vmstat.workingset_activate_anon = Some(value),
+            VmstatType::WorkingsetActivateFile => vmstat.workingset_activate_file = Some(value),
+            VmstatType::WorkingsetRestoreAnon => vmstat.workingset_restore_anon = Some(value),
+            VmstatType::WorkingsetRestoreFile => vmstat.workingset_restore_file = Some(value),
+            VmstatType::WorkingsetNodereclaim => vmstat.workingset_nodereclaim = Some(value),
+            VmstatType::NumaHit => vmstat.numa_hit = Some(value),
+            VmstatType::NumaMiss => vmstat.numa_miss = Some(value),
+            VmstatType::NumaForeign => vmstat.numa_foreign = Some(value),
+            VmstatType::NumaInterleave => vmstat.numa_interleave = Some(value),
+            VmstatType::NumaLocal => vmstat.numa_local = Some(value),
+            VmstatType::NumaOther => vmstat.numa_other = Some(value),
+            VmstatType::CompactMigrateScanned => vmstat.compact_migrate_scanned = Some(value),
+            VmstatType::CompactFreeScanned => vmstat.compact_free_scanned = Some(value),
+            VmstatType::CompactIsolated => vmstat.compact_isolated = Some(value),
+            VmstatType::CompactStall => vmstat.compact_stall = Some(value),
+            VmstatType::CompactFail => vmstat.compact_fail = Some(value),
+            VmstatType::CompactSuccess => vmstat.compact_success = Some(value),
+            VmstatType::CompactDaemonWake => vmstat.compact_daemon_wake = Some(value),
+            VmstatType::CompactDaemonMigrateScanned => {
+                vmstat.compact_daemon_migrate_scanned = Some(value)
+            }
+            VmstatType::CompactDaemonFreeScanned => {
+                vmstat.compact_daemon_free_scanned = Some(value)
+            }
+            VmstatType::ThpFaultAlloc => vmstat.thp_fault_alloc = Some(value),
+            VmstatType::ThpFaultFallback => vmstat.thp_fault_fallback = Some(value),
+            VmstatType::ThpFaultFallbackCharge => vmstat.thp_fault_fallback_charge = Some(value),
+            VmstatType::ThpCollapseAlloc => vmstat.thp_collapse_alloc = Some(value),
+            VmstatType::ThpCollapseAllocFailed => vmstat.thp_collapse_alloc_failed = Some(value),
+            VmstatType::ThpFileAlloc => vmstat.thp_file_alloc = Some(value),
+            VmstatType::ThpFileMapped => vmstat.thp_file_mapped = Some(value),
+            VmstatType::ThpSplitPage => vmstat.thp_split_page = Some(value),
+            VmstatType::ThpSplitPageFailed => vmstat.thp_split_page_failed = Some(value),
+            VmstatType::ThpDeferredSplitPage => vmstat.thp_deferred_split_page = Some(value),
+            VmstatType::ThpSplitPmd => vmstat.thp_split_pmd = Some(value),
+            VmstatType::ThpZeroPageAlloc => vmstat.thp_zero_page_alloc = Some(value),
+            VmstatType::ThpZeroPageAllocFailed => vmstat.thp_zero_page_alloc_failed = Some(value),
+            VmstatType::ThpSwpout => vmstat.thp_swpout = Some(value),
+            VmstatType::ThpSwpoutFallback => vmstat.thp_swpout_fallback = Some(value),
+            VmstatType::Unknown => {
+                vmstat.unknown.insert(item_fields[0].to_string(), value);
+            }
+        }
+    }
+
+    Ok(vmstat)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vmstat() {
+        let vmstat = collect_from(Path::new("test_data/fixtures/proc/vmstat"))
+            .expect("virtual memory statistics");
+
+        assert_eq!(vmstat.nr_free_pages, Some(1046727));
+        assert_eq!(vmstat.nr_dirty, Some(1379));
+        assert_eq!(vmstat.pgpgin, Some(6341846));
+        assert_eq!(vmstat.pgpgout, Some(28716920));
+        assert_eq!(vmstat.pswpin, Some(1028));
+        assert_eq!(vmstat.pswpout, Some(5291));
+        assert_eq!(vmstat.pgfault, Some(330734624));
+        assert_eq!(vmstat.pgmajfault, Some(21839));
+        assert_eq!(vmstat.pgscan_kswapd, Some(1190342));
+        assert_eq!(vmstat.pgscan_direct, Some(42051));
+        assert_eq!(vmstat.pgsteal_kswapd, Some(1077205));
+        assert_eq!(vmstat.pgsteal_direct, Some(40380));
+        assert_eq!(vmstat.oom_kill, Some(2));
+        assert_eq!(vmstat.compact_stall, Some(31));
+        assert_eq!(vmstat.compact_success, Some(27));
+        assert_eq!(vmstat.thp_fault_alloc, Some(9102));
+        assert_eq!(vmstat.thp_fault_fallback, Some(114));
+
+        // not reported by this kernel
+        assert_eq!(vmstat.pgscan_anon, None);
+        assert_eq!(vmstat.workingset_refault_anon, None);
+
+        assert_eq!(vmstat.unknown.get("nr_zone_write_pending"), Some(&1379));
+        assert_eq!(vmstat.unknown.get("unevictable_pgs_culled"), Some(&46613));
+        assert!(!vmstat.unknown.contains_key("pgfault"));
+    }
+
+    #[test]
+    fn vmstat_invalid_line() {
+        let mut memfs = crate::filesystem::MemoryFileSystem::new();
+        memfs.insert_file(
+            "/proc/vmstat",
+            "nr_free_pages 1046727
+pgfault
+",
+        );
+        let procfs = ProcFs::with_filesystem("/proc", std::sync::Arc::new(memfs));
+
+        let err = procfs.vmstat().expect_err("invalid vmstat line");
+        assert_eq!(err.path(), Some(Path::new("/proc/vmstat")));
+        assert_eq!(err.line(), Some(2));
+    }
+}
@@ -0,0 +1,328 @@
+use std::{collections::HashMap, path::Path};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{CollectResult, MetricError},
+    procfs::ProcFs,
+    utils,
+};
+
+/// ZonePageset is the per-cpu page list of a zone
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ZonePageset {
+    pub cpu: usize,
+    pub count: u64,
+    pub high: u64,
+    pub batch: u64,
+    pub vm_stats_threshold: Option<u64>,
+}
+
+impl ZonePageset {
+    fn new(cpu: usize) -> Self {
+        Self {
+            cpu,
+            ..Default::default()
+        }
+    }
+}
+
+/// ZoneInfo is a memory zone of a NUMA node parsed from /proc/zoneinfo,
+/// the watermarks and page counts are in pages
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ZoneInfo {
+    pub node: String,
+    pub zone: String,
+    pub free: u64,
+    pub boost: Option<u64>,
+    pub min: u64,
+    pub low: u64,
+    pub high: u64,
+    pub spanned: u64,
+    pub present: u64,
+    pub managed: u64,
+    pub cma: Option<u64>,
+
+    /// pages kept free for allocations which may fall back from the higher zones,
+    /// indexed by zone of the node
+    pub protection: Vec<u64>,
+
+    /// zone counters (e.g. `nr_free_pages`, `numa_hit`)
+    pub stats: HashMap<String, u64>,
+
+    /// counters of the node, only reported with the first zone of each node
+    pub node_stats: HashMap<String, u64>,
+
+    pub pagesets: Vec<ZonePageset>,
+    pub node_unreclaimable: Option<u64>,
+    pub start_pfn: Option<u64>,
+}
+
+impl ZoneInfo {
+    fn new(node: &str, zone: &str) -> Self {
+        Self {
+            node: node.to_string(),
+            zone: zone.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// returns true if the free pages are below the low watermark, kswapd is
+    /// woken up to reclaim pages of the zone
+    pub fn below_low_watermark(&self) -> bool {
+        self.free < self.low
+    }
+
+    /// returns true if the free pages are below the min watermark, the allocations
+    /// of the zone go through direct reclaim
+    pub fn below_min_watermark(&self) -> bool {
+        self.free < self.min
+    }
+}
+
+// part of a zone the current line belongs to
+enum Section {
+    NodeStats,
+    Zone,
+    Pagesets,
+}
+
+/// collects the memory zones of each NUMA node
+/// # Example
+/// ```
+/// use procsys::zoneinfo;
+///
+/// let zones = zoneinfo::collect().expect("zone information");
+///
+/// for zone in &zones {
+///     println!(
+///         "node {} {}: free {} min {} low {} high {}",
+///         zone.node, zone.zone, zone.free, zone.min, zone.low, zone.high,
+///     );
+/// }
+///
+/// ```
+pub fn collect() -> CollectResult<Vec<ZoneInfo>> {
+    ProcFs::default().zoneinfo()
+}
+
+pub(crate) fn collect_from(filename: &Path) -> CollectResult<Vec<ZoneInfo>> {
+    let mut zones: Vec<ZoneInfo> = Vec::new();
+    let mut section = Section::Zone;
+
+    for (index, line) in utils::read_file_lines(filename)?.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if line.starts_with("Node") {
+            zones.push(parse_zone_header(line).map_err(|err| err.with_line(filename, index + 1))?);
+            section = Section::Zone;
+            continue;
+        }
+
+        let Some(zone) = zones.last_mut() else {
+            let err = MetricError::ParseError(format!("zoneinfo line outside of a zone: {}", line));
+            return Err(err.with_line(filename, index + 1));
+        };
+
+        parse_zone_line(zone, &mut section, line.trim())
+            .map_err(|err| err.with_line(filename, index + 1))?;
+    }
+
+    Ok(zones)
+}
+
+// parses "Node 0, zone   Normal"
+fn parse_zone_header(line: &str) -> CollectResult<ZoneInfo> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+
+    if fields.len() != 4 {
+        return Err(MetricError::InvalidFieldNumberError(
+            "zoneinfo".to_string(),
+            fields.len(),
+            line.to_string(),
+        ));
+    }
+
+    Ok(ZoneInfo::new(fields[1].trim_end_matches(','), fields[3]))
+}
+
+fn parse_zone_line(zone: &mut ZoneInfo, section: &mut Section, line: &str) -> CollectResult<()> {
+    if line == "per-node stats" {
+        *section = Section::NodeStats;
+        return Ok(());
+    }
+
+    if line == "pagesets" {
+        *section = Section::Pagesets;
+        return Ok(());
+    }
+
+    if let Some(protection) = line.strip_prefix("protection:") {
+        for value in protection
+            .trim()
+            .trim_start_matches('(')
+            .trim_end_matches(')')
+            .split(',')
+        {
+            zone.protection
+                .push(utils::convert_str_to_u64(value.trim())?);
+        }
+
+        return Ok(());
+    }
+
+    let (key, value) = match line.split_once(':') {
+        Some((key, value)) => (key.trim(), value.trim()),
+        None => {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.len() {
+                // "pages free     3975"
+                3 if fields[0] == "pages" => (fields[1], fields[2]),
+                2 => (fields[0], fields[1]),
+                _ => {
+                    return Err(MetricError::InvalidFieldNumberError(
+                        "zoneinfo".to_string(),
+                        fields.len(),
+                        line.to_string(),
+                    ))
+                }
+            }
+        }
+    };
+
+    match (key, &section) {
+        ("free", _) => {
+            *section = Section::Zone;
+            zone.free = utils::convert_str_to_u64(value)?;
+        }
+        ("node_unreclaimable", _) => {
+            zone.node_unreclaimable = Some(utils::convert_str_to_u64(value)?)
+        }
+        ("start_pfn", _) => zone.start_pfn = Some(utils::convert_str_to_u64(value)?),
+        ("cpu", Section::Pagesets) => {
+            let cpu = value
+                .parse::<usize>()
+                .map_err(|err| MetricError::ParseIntError(value.to_string(), err))?;
+            zone.pagesets.push(ZonePageset::new(cpu));
+        }
+        (_, Section::Pagesets) => {
+            let Some(pageset) = zone.pagesets.last_mut() else {
+                return Err(MetricError::ParseError(format!(
+                    "zoneinfo pageset value without cpu: {}",
+                    line
+                )));
+            };
+
+            match key {
+                "count" => pageset.count = utils::convert_str_to_u64(value)?,
+                "high" => pageset.high = utils::convert_str_to_u64(value)?,
+                "batch" => pageset.batch = utils::convert_str_to_u64(value)?,
+                "vm stats threshold" => {
+                    pageset.vm_stats_threshold = Some(utils::convert_str_to_u64(value)?)
+                }
+                _ => {}
+            }
+        }
+        (_, Section::NodeStats) => {
+            zone.node_stats
+                .insert(key.to_string(), utils::convert_str_to_u64(value)?);
+        }
+        (_, Section::Zone) => {
+            let value = utils::convert_str_to_u64(value)?;
+
+            match key {
+                "boost" => zone.boost = Some(value),
+                "min" => zone.min = value,
+                "low" => zone.low = value,
+                "high" => zone.high = value,
+                "spanned" => zone.spanned = value,
+                "present" => zone.present = value,
+                "managed" => zone.managed = value,
+                "cma" => zone.cma = Some(value),
+                _ => {
+                    zone.stats.insert(key.to_string(), value);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zoneinfo() {
+        let zones =
+            collect_from(Path::new("test_data/fixtures/proc/zoneinfo")).expect("zone information");
+
+        assert_eq!(zones.len(), 3);
+
+        let dma = &zones[0];
+        assert_eq!(dma.node, "0");
+        assert_eq!(dma.zone, "DMA");
+        assert_eq!(dma.free, 3975);
+        assert_eq!(dma.boost, Some(0));
+        assert_eq!((dma.min, dma.low, dma.high), (33, 41, 49));
+        assert_eq!((dma.spanned, dma.present, dma.managed), (4095, 3998, 3975));
+        assert_eq!(dma.protection, [0, 2973, 15685, 15685, 15685]);
+        assert_eq!(dma.node_stats.get("nr_dirty"), Some(&1379));
+        assert_eq!(dma.stats.get("nr_free_pages"), Some(&3975));
+        assert!(!dma.stats.contains_key("nr_dirty"));
+        assert_eq!(dma.start_pfn, Some(1));
+
+        let normal = &zones[1];
+        assert_eq!(normal.zone, "Normal");
+        assert!(normal.node_stats.is_empty());
+        assert_eq!(normal.stats.get("numa_hit"), Some(&1019238915));
+        assert_eq!(
+            normal.pagesets,
+            vec![
+                ZonePageset {
+                    cpu: 0,
+                    count: 183,
+                    high: 378,
+                    batch: 63,
+                    vm_stats_threshold: Some(72),
+                },
+                ZonePageset {
+                    cpu: 1,
+                    count: 347,
+                    high: 378,
+                    batch: 63,
+                    vm_stats_threshold: Some(72),
+                },
+            ]
+        );
+        assert_eq!(normal.node_unreclaimable, Some(0));
+        assert_eq!(normal.start_pfn, Some(1048576));
+        assert!(!normal.below_low_watermark());
+
+        // zones without memory have no pagesets
+        let movable = &zones[2];
+        assert_eq!(movable.zone, "Movable");
+        assert_eq!(movable.managed, 0);
+        assert!(movable.pagesets.is_empty());
+        assert_eq!(movable.start_pfn, None);
+    }
+
+    #[test]
+    fn zoneinfo_watermarks() {
+        let mut zone = ZoneInfo::new("0", "Normal");
+        (zone.min, zone.low, zone.high) = (100, 125, 150);
+
+        zone.free = 120;
+        assert!(zone.below_low_watermark());
+        assert!(!zone.below_min_watermark());
+
+        zone.free = 90;
+        assert!(zone.below_min_watermark());
+    }
+}